@@ -0,0 +1,54 @@
+use winit::{
+    event::{ElementState, Event, KeyboardInput, VirtualKeyCode, WindowEvent},
+    event_loop::{ControlFlow, EventLoop},
+    window::WindowBuilder,
+};
+
+fn main() {
+    let event_loop = EventLoop::new();
+
+    let window = WindowBuilder::new()
+        .with_title("Press Space to let clicks through, again to catch them")
+        .with_always_on_top(true)
+        .build(&event_loop)
+        .unwrap();
+
+    let mut hittest = true;
+
+    event_loop.run(move |event, _, control_flow| {
+        *control_flow = ControlFlow::Wait;
+        if let Event::WindowEvent { event, .. } = event {
+            match event {
+                WindowEvent::CloseRequested => *control_flow = ControlFlow::Exit,
+                // The window keeps the keyboard focus, so it can still be toggled back.
+                WindowEvent::KeyboardInput {
+                    input:
+                        KeyboardInput {
+                            state: ElementState::Released,
+                            virtual_keycode: Some(key),
+                            ..
+                        },
+                    ..
+                } => {
+                    match key {
+                        VirtualKeyCode::Escape => *control_flow = ControlFlow::Exit,
+                        VirtualKeyCode::Space => {
+                            match window.set_cursor_hittest(!hittest) {
+                                Ok(()) => hittest = !hittest,
+                                Err(err) => println!("Couldn't change the hittest: {}", err),
+                            }
+                            println!("hittest: {}", hittest);
+                        },
+                        _ => (),
+                    }
+                },
+                WindowEvent::MouseInput {
+                    state: ElementState::Pressed,
+                    button,
+                    ..
+                } => println!("Caught a {:?} click", button),
+                _ => (),
+            }
+        }
+    });
+}
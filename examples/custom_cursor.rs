@@ -0,0 +1,68 @@
+use winit::{
+    event::{ElementState, Event, KeyboardInput, WindowEvent},
+    event_loop::{ControlFlow, EventLoop},
+    window::{CursorIcon, WindowBuilder},
+};
+
+const SIZE: u32 = 32;
+
+fn main() {
+    let event_loop = EventLoop::new();
+
+    let window = WindowBuilder::new().build(&event_loop).unwrap();
+    window.set_title("Press any key to toggle the custom cursor");
+
+    // A ring, with the hotspot in its center, like a brush preview.
+    let mut rgba = Vec::with_capacity((SIZE * SIZE * 4) as usize);
+    for y in 0..SIZE {
+        for x in 0..SIZE {
+            let dx = x as f64 - (SIZE / 2) as f64;
+            let dy = y as f64 - (SIZE / 2) as f64;
+            let distance = (dx * dx + dy * dy).sqrt();
+            let alpha = if distance > 12.0 && distance < 15.0 {
+                255
+            } else {
+                0
+            };
+            rgba.extend_from_slice(&[255, 64, 64, alpha]);
+        }
+    }
+    let cursor = window
+        .create_custom_cursor(&rgba, SIZE, SIZE, SIZE / 2, SIZE / 2)
+        .expect("Failed to create the cursor");
+    window.set_custom_cursor(&cursor);
+
+    let mut custom = true;
+
+    event_loop.run(move |event, _, control_flow| {
+        match event {
+            Event::WindowEvent {
+                event:
+                    WindowEvent::KeyboardInput {
+                        input:
+                            KeyboardInput {
+                                state: ElementState::Pressed,
+                                ..
+                            },
+                        ..
+                    },
+                ..
+            } => {
+                custom = !custom;
+                if custom {
+                    window.set_custom_cursor(&cursor);
+                } else {
+                    window.set_cursor_icon(CursorIcon::Default);
+                }
+            },
+            Event::WindowEvent {
+                event: WindowEvent::CloseRequested,
+                ..
+            } => {
+                *control_flow = ControlFlow::Exit;
+                return;
+            },
+            _ => (),
+        }
+    });
+}
@@ -0,0 +1,75 @@
+use winit::{
+    dpi::LogicalPosition,
+    event::{ElementState, Event, KeyboardInput, MouseButton, VirtualKeyCode, WindowEvent},
+    event_loop::{ControlFlow, EventLoop},
+    window::{ResizeDirection, WindowBuilder},
+};
+
+// The height of the pretend title bar at the top of the window.
+const TITLE_BAR_HEIGHT: f64 = 32.0;
+// How close to an edge of the window the cursor has to be to resize it.
+const BORDER_SIZE: f64 = 8.0;
+
+fn main() {
+    let event_loop = EventLoop::new();
+
+    let window = WindowBuilder::new()
+        .with_title("Drag the top of the window to move it, or the edges to resize it")
+        .with_decorations(false)
+        .build(&event_loop)
+        .unwrap();
+
+    let mut cursor_position = LogicalPosition::new(0.0, 0.0);
+
+    event_loop.run(move |event, _, control_flow| {
+        *control_flow = ControlFlow::Wait;
+        if let Event::WindowEvent { event, .. } = event {
+            match event {
+                WindowEvent::CloseRequested
+                | WindowEvent::KeyboardInput {
+                    input:
+                        KeyboardInput {
+                            virtual_keycode: Some(VirtualKeyCode::Escape),
+                            ..
+                        },
+                    ..
+                } => *control_flow = ControlFlow::Exit,
+                WindowEvent::CursorMoved { position, .. } => cursor_position = position,
+                WindowEvent::MouseInput {
+                    state: ElementState::Pressed,
+                    button: MouseButton::Left,
+                    ..
+                } => {
+                    let size = window.inner_size();
+                    let result = match resize_direction(cursor_position, size.width, size.height) {
+                        Some(direction) => window.drag_resize_window(direction),
+                        None if cursor_position.y < TITLE_BAR_HEIGHT => window.drag_window(),
+                        None => Ok(()),
+                    };
+                    if let Err(err) = result {
+                        println!("Couldn't start dragging the window: {}", err);
+                    }
+                },
+                _ => (),
+            }
+        }
+    });
+}
+
+fn resize_direction(position: LogicalPosition, width: f64, height: f64) -> Option<ResizeDirection> {
+    let west = position.x < BORDER_SIZE;
+    let east = position.x > width - BORDER_SIZE;
+    let north = position.y < BORDER_SIZE;
+    let south = position.y > height - BORDER_SIZE;
+    match (north, south, west, east) {
+        (true, _, true, _) => Some(ResizeDirection::NorthWest),
+        (true, _, _, true) => Some(ResizeDirection::NorthEast),
+        (_, true, true, _) => Some(ResizeDirection::SouthWest),
+        (_, true, _, true) => Some(ResizeDirection::SouthEast),
+        (true, ..) => Some(ResizeDirection::North),
+        (_, true, ..) => Some(ResizeDirection::South),
+        (.., true, _) => Some(ResizeDirection::West),
+        (.., true) => Some(ResizeDirection::East),
+        _ => None,
+    }
+}
@@ -3,7 +3,7 @@ use winit::{
     event::{ElementState, Event, KeyboardInput, VirtualKeyCode, WindowEvent},
     event_loop::{ControlFlow, EventLoop},
     monitor::MonitorHandle,
-    window::WindowBuilder,
+    window::{FullscreenType, WindowBuilder},
 };
 
 fn main() {
@@ -39,13 +39,12 @@ fn main() {
         Some(prompt_for_monitor(&event_loop))
     };
 
-    let mut is_fullscreen = monitor.is_some();
     let mut is_maximized = false;
     let mut decorations = true;
 
     let window = WindowBuilder::new()
         .with_title("Hello world!")
-        .with_fullscreen(monitor)
+        .with_fullscreen(monitor.map(|monitor| FullscreenType::Borderless(Some(monitor))))
         .build(&event_loop)
         .unwrap();
 
@@ -73,21 +72,18 @@ fn main() {
                                 {
                                     if macos_use_simple_fullscreen {
                                         use winit::platform::macos::WindowExtMacOS;
-                                        if WindowExtMacOS::set_simple_fullscreen(
+                                        WindowExtMacOS::set_simple_fullscreen(
                                             &window,
-                                            !is_fullscreen,
-                                        ) {
-                                            is_fullscreen = !is_fullscreen;
-                                        }
+                                            !WindowExtMacOS::simple_fullscreen(&window),
+                                        );
                                         return;
                                     }
                                 }
 
-                                is_fullscreen = !is_fullscreen;
-                                if !is_fullscreen {
+                                if window.fullscreen().is_some() {
                                     window.set_fullscreen(None);
                                 } else {
-                                    window.set_fullscreen(Some(window.current_monitor()));
+                                    window.set_fullscreen(Some(FullscreenType::Borderless(None)));
                                 }
                             },
                             (VirtualKeyCode::S, ElementState::Pressed) => {
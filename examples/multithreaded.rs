@@ -4,7 +4,7 @@ use std::{collections::HashMap, sync::mpsc, thread, time::Duration};
 use winit::{
     event::{ElementState, Event, KeyboardInput, VirtualKeyCode, WindowEvent},
     event_loop::{ControlFlow, EventLoop},
-    window::{CursorIcon, WindowBuilder},
+    window::{CursorIcon, FullscreenType, WindowBuilder},
 };
 
 const WINDOW_COUNT: usize = 3;
@@ -48,7 +48,7 @@ fn main() {
                             D => window.set_decorations(!state),
                             F => {
                                 window.set_fullscreen(match state {
-                                    true => Some(window.current_monitor()),
+                                    true => Some(FullscreenType::Borderless(None)),
                                     false => None,
                                 })
                             },
@@ -86,7 +86,7 @@ fn main() {
                                         false => WINDOW_SIZE,
                                     }
                                     .into(),
-                                )
+                                );
                             },
                             W => {
                                 window
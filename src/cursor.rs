@@ -0,0 +1,153 @@
+use std::{error::Error, fmt};
+
+use crate::{icon::PIXEL_SIZE, platform_impl, window::CursorIcon};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+/// An error produced when using `Window::create_custom_cursor` with invalid arguments, or when
+/// the platform is unable to create the cursor.
+pub enum BadCursor {
+    /// Produced when the length of the `rgba` argument isn't divisible by 4, thus `rgba` can't be
+    /// safely interpreted as 32bpp RGBA pixels.
+    ByteCountNotDivisibleBy4 { byte_count: usize },
+    /// Produced when the number of pixels (`rgba.len() / 4`) isn't equal to `width * height`.
+    /// At least one of your arguments is incorrect.
+    DimensionsVsPixelCount {
+        width: u32,
+        height: u32,
+        width_x_height: usize,
+        pixel_count: usize,
+    },
+    /// Produced when the hotspot doesn't lie within the image.
+    HotspotOutOfBounds {
+        width: u32,
+        height: u32,
+        hotspot_x: u32,
+        hotspot_y: u32,
+    },
+    /// Produced when custom cursors aren't supported on the platform.
+    NotSupported,
+    /// Produced when the platform failed to create the native cursor.
+    CreationFailed,
+}
+
+impl fmt::Display for BadCursor {
+    fn fmt(&self, formatter: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let msg = match *self {
+            BadCursor::ByteCountNotDivisibleBy4 { byte_count } => format!(
+                "The length of the `rgba` argument ({:?}) isn't divisible by 4, making it impossible to interpret as 32bpp RGBA pixels.",
+                byte_count,
+            ),
+            BadCursor::DimensionsVsPixelCount {
+                width,
+                height,
+                width_x_height,
+                pixel_count,
+            } => format!(
+                "The specified dimensions ({:?}x{:?}) don't match the number of pixels supplied by the `rgba` argument ({:?}). For those dimensions, the expected pixel count is {:?}.",
+                width, height, pixel_count, width_x_height,
+            ),
+            BadCursor::HotspotOutOfBounds {
+                width,
+                height,
+                hotspot_x,
+                hotspot_y,
+            } => format!(
+                "The specified hotspot ({:?}, {:?}) lies outside of the {:?}x{:?} cursor image.",
+                hotspot_x, hotspot_y, width, height,
+            ),
+            BadCursor::NotSupported => "Custom cursors aren't supported on this platform.".to_owned(),
+            BadCursor::CreationFailed => "The platform failed to create the cursor.".to_owned(),
+        };
+        write!(formatter, "{}", msg)
+    }
+}
+
+impl Error for BadCursor {
+    fn description(&self) -> &str {
+        "A valid cursor cannot be created from these arguments"
+    }
+}
+
+/// A cursor image created with `Window::create_custom_cursor`.
+///
+/// The image is uploaded to the platform once, so the same `CustomCursor` can be passed to
+/// `Window::set_custom_cursor` any number of times. Clones share the native cursor, which is
+/// freed once the last clone is dropped.
+#[derive(Debug, Clone)]
+pub struct CustomCursor {
+    pub(crate) inner: platform_impl::CustomCursor,
+}
+
+/// A cursor that can be passed to `Window::set_cursor`.
+///
+/// This is either one of the system cursors described by `CursorIcon`, or a `CustomCursor`.
+/// System cursors are loaded by the platform the first time they're used, and reused from then
+/// on.
+#[derive(Debug, Clone)]
+pub struct Cursor {
+    pub(crate) inner: CursorInner,
+}
+
+#[derive(Debug, Clone)]
+pub(crate) enum CursorInner {
+    System(CursorIcon),
+    Custom(CustomCursor),
+}
+
+impl Cursor {
+    /// Creates a cursor that shows the system cursor for `icon`.
+    #[inline]
+    pub fn from_system(icon: CursorIcon) -> Cursor {
+        Cursor {
+            inner: CursorInner::System(icon),
+        }
+    }
+}
+
+impl From<CursorIcon> for Cursor {
+    #[inline]
+    fn from(icon: CursorIcon) -> Self {
+        Cursor::from_system(icon)
+    }
+}
+
+impl From<CustomCursor> for Cursor {
+    #[inline]
+    fn from(cursor: CustomCursor) -> Self {
+        Cursor {
+            inner: CursorInner::Custom(cursor),
+        }
+    }
+}
+
+pub(crate) fn validate_cursor(
+    rgba: &[u8],
+    width: u32,
+    height: u32,
+    hotspot_x: u32,
+    hotspot_y: u32,
+) -> Result<(), BadCursor> {
+    let pixel_count = rgba.len() / PIXEL_SIZE;
+    if pixel_count * PIXEL_SIZE != rgba.len() {
+        return Err(BadCursor::ByteCountNotDivisibleBy4 {
+            byte_count: rgba.len(),
+        });
+    }
+    if pixel_count != width as usize * height as usize {
+        return Err(BadCursor::DimensionsVsPixelCount {
+            width,
+            height,
+            width_x_height: width as usize * height as usize,
+            pixel_count,
+        });
+    }
+    if hotspot_x >= width || hotspot_y >= height {
+        return Err(BadCursor::HotspotOutOfBounds {
+            width,
+            height,
+            hotspot_x,
+            hotspot_y,
+        });
+    }
+    Ok(())
+}
@@ -28,18 +28,18 @@
 //! them entering an existential panic. Once users enter that state, they will no longer be focused on your application.
 //!
 //! There are two ways to get the DPI factor:
-//! - You can track the [`HiDpiFactorChanged`](../enum.WindowEvent.html#variant.HiDpiFactorChanged) event of your
+//! - You can track the [`ScaleFactorChanged`](../enum.WindowEvent.html#variant.ScaleFactorChanged) event of your
 //!   windows. This event is sent any time the DPI factor changes, either because the window moved to another monitor,
 //!   or because the user changed the configuration of their screen.
 //! - You can also retrieve the DPI factor of a monitor by calling
 //!   [`MonitorHandle::hidpi_factor`](../monitor/struct.MonitorHandle.html#method.hidpi_factor), or the
 //!   current DPI factor applied to a window by calling
 //!   [`Window::hidpi_factor`](../window/struct.Window.html#method.hidpi_factor), which is roughly equivalent
-//!   to `window.current_monitor().hidpi_factor()`.
+//!   to `window.current_monitor().unwrap().hidpi_factor()`.
 //!
 //! Depending on the platform, the window's actual DPI factor may only be known after
 //! the event loop has started and your window has been drawn once. To properly handle these cases,
-//! the most robust way is to monitor the [`HiDpiFactorChanged`](../enum.WindowEvent.html#variant.HiDpiFactorChanged)
+//! the most robust way is to monitor the [`ScaleFactorChanged`](../enum.WindowEvent.html#variant.ScaleFactorChanged)
 //! event and dynamically adapt your drawing logic to follow the DPI factor.
 //!
 //! Here's an overview of what sort of DPI factors you can expect, and where they come from:
@@ -60,19 +60,33 @@
 //! may be surprising on X11, but is quite standard elsewhere. Physical size changes always produce a
 //! [`Resized`](../event/enum.WindowEvent.html#variant.Resized) event, even on platforms where no resize actually occurs,
 //! such as macOS and Wayland. As a result, it's not necessary to separately handle
-//! [`HiDpiFactorChanged`](../event/enum.WindowEvent.html#variant.HiDpiFactorChanged) if you're only listening for size.
+//! [`ScaleFactorChanged`](../event/enum.WindowEvent.html#variant.ScaleFactorChanged) if you're only listening for size.
 //!
 //! Your GPU has no awareness of the concept of logical pixels, and unless you like wasting pixel density, your
-//! framebuffer's size should be in physical pixels.
+//! framebuffer's size should be in physical pixels. The types in this module keep the two apart, so converting
+//! between them always takes an explicit DPI factor:
+//!
+//! ```no_run
+//! # use winit::{event_loop::EventLoop, window::WindowBuilder};
+//! # let event_loop = EventLoop::new();
+//! let window = WindowBuilder::new()
+//!     // The size of the framebuffer, which becomes a logical size of 400x300 at a DPI factor of 2.
+//!     .with_surface_size(800, 600)
+//!     .build(&event_loop)
+//!     .unwrap();
+//!
+//! let framebuffer_size = window.inner_size().to_physical(window.hidpi_factor());
+//! let (width, height): (u32, u32) = framebuffer_size.into();
+//! ```
 //!
 //! `winit` will send [`Resized`](../enum.WindowEvent.html#variant.Resized) events whenever a window's logical size
-//! changes, and [`HiDpiFactorChanged`](../enum.WindowEvent.html#variant.HiDpiFactorChanged) events
+//! changes, and [`ScaleFactorChanged`](../enum.WindowEvent.html#variant.ScaleFactorChanged) events
 //! whenever the DPI factor changes. Receiving either of these events means that the physical size of your window has
 //! changed, and you should recompute it using the latest values you received for each. If the logical size and the
 //! DPI factor change simultaneously, `winit` will send both events together; thus, it's recommended to buffer
 //! these events and process them at the end of the queue.
 //!
-//! If you never received any [`HiDpiFactorChanged`](../enum.WindowEvent.html#variant.HiDpiFactorChanged) events,
+//! If you never received any [`ScaleFactorChanged`](../enum.WindowEvent.html#variant.ScaleFactorChanged) events,
 //! then your window's DPI factor is 1.
 
 /// Checks that the DPI factor is a normal positive `f64`.
@@ -9,6 +9,10 @@ pub enum ExternalError {
     NotSupported(NotSupportedError),
     /// The OS cannot perform the operation.
     Os(OsError),
+    /// The operation requires the window to have input focus, or to be visible, and it
+    /// currently doesn't. The operation may succeed if retried later, e.g. upon receiving
+    /// `WindowEvent::Focused(true)`.
+    NotFocused,
 }
 
 /// The error type for when the requested operation is not supported by the backend.
@@ -38,6 +42,18 @@ impl OsError {
     pub(crate) fn new(line: u32, file: &'static str, error: platform_impl::OsError) -> OsError {
         OsError { line, file, error }
     }
+
+    /// Returns the error code reported by the OS, if there is one.
+    ///
+    /// ## Platform-specific
+    ///
+    /// - **Windows:** The code returned by `GetLastError`.
+    /// - **X11:** The error code of the X error.
+    /// - **macOS:** The `CGError`, for errors coming from Core Graphics.
+    /// - **Wayland / iOS:** Always `None`.
+    pub fn raw_os_error(&self) -> Option<i32> {
+        self.error.raw_os_error()
+    }
 }
 
 #[allow(unused_macros)]
@@ -61,6 +77,9 @@ impl fmt::Display for ExternalError {
         match self {
             ExternalError::NotSupported(e) => e.fmt(formatter),
             ExternalError::Os(e) => e.fmt(formatter),
+            ExternalError::NotFocused => {
+                formatter.pad("the operation requires the window to be focused")
+            },
         }
     }
 }
@@ -77,6 +96,27 @@ impl fmt::Display for NotSupportedError {
     }
 }
 
+impl From<NotSupportedError> for ExternalError {
+    fn from(error: NotSupportedError) -> Self {
+        ExternalError::NotSupported(error)
+    }
+}
+
+impl From<OsError> for ExternalError {
+    fn from(error: OsError) -> Self {
+        ExternalError::Os(error)
+    }
+}
+
 impl error::Error for OsError {}
-impl error::Error for ExternalError {}
 impl error::Error for NotSupportedError {}
+
+impl error::Error for ExternalError {
+    fn source(&self) -> Option<&(dyn error::Error + 'static)> {
+        match self {
+            ExternalError::NotSupported(e) => Some(e),
+            ExternalError::Os(e) => Some(e),
+            ExternalError::NotFocused => None,
+        }
+    }
+}
@@ -7,9 +7,9 @@
 use std::{path::PathBuf, time::Instant};
 
 use crate::{
-    dpi::{LogicalPosition, LogicalSize},
+    dpi::{LogicalPosition, LogicalSize, PhysicalSize},
     platform_impl,
-    window::WindowId,
+    window::{Theme, WindowId},
 };
 
 /// Describes a generic event.
@@ -38,9 +38,31 @@ pub enum Event<T> {
     LoopDestroyed,
 
     /// Emitted when the application has been suspended.
+    ///
+    /// Resources tied to the native window, such as graphics surfaces, should be released while
+    /// handling this event. It's always followed by `Resumed` before the window can be used again.
+    ///
+    /// ## Platform-specific
+    ///
+    /// - **Android:** Emitted when the native window has been destroyed, e.g. because the activity
+    ///   went to the background. The destruction is only queued up for the event loop, without
+    ///   waiting for it to be handled, so the window may already be invalid by the time this is
+    ///   received. `WindowEvent::Focused` isn't emitted on Android, so nothing reports the loss of
+    ///   focus before or after it.
+    /// - **iOS:** Emitted when the application is about to become inactive. The key window
+    ///   doesn't lose focus because of that, so no `WindowEvent::Focused(false)` accompanies it.
+    /// - **Windows / macOS / X11 / Wayland:** Not emitted. Losing focus is reported through
+    ///   `WindowEvent::Focused(false)` instead.
     Suspended,
 
     /// Emitted when the application has been resumed.
+    ///
+    /// ## Platform-specific
+    ///
+    /// - **Android:** Emitted when the native window has been created again, so surfaces have to
+    ///   be recreated from it.
+    /// - **iOS:** Emitted when the application has become active.
+    /// - **Windows / macOS / X11 / Wayland:** Not emitted.
     Resumed,
 }
 
@@ -92,19 +114,36 @@ pub enum WindowEvent {
     /// The size of the window has changed. Contains the client area's new dimensions.
     Resized(LogicalSize),
 
-    /// The position of the window has changed. Contains the window's new position.
+    /// The position of the window has changed. Contains the window's new outer position.
+    ///
+    /// This is also emitted for moves caused by `Window::set_outer_position`. While the window is
+    /// being dragged, several moves may be coalesced into one event.
+    ///
+    /// ## Platform-specific
+    ///
+    /// - **iOS / Android / Wayland:** Not emitted.
     Moved(LogicalPosition),
 
-    /// The window has been requested to close.
+    /// The window has been requested to close, e.g. because the user clicked its close button.
+    ///
+    /// The window isn't closed automatically, so this can be used to ask the user for
+    /// confirmation first. To actually close it, call `Window::close` or drop the `Window`.
     CloseRequested,
 
     /// The window has been destroyed.
+    ///
+    /// This is the last event delivered for this window.
     Destroyed,
 
     /// A file has been dropped into the window.
     ///
     /// When the user drops multiple files at once, this event will be emitted for each file
     /// separately.
+    ///
+    /// ## Platform-specific
+    ///
+    /// - **Windows:** Unsupported if drag and drop was disabled with
+    ///   `WindowBuilderExtWindows::with_drag_and_drop`.
     DroppedFile(PathBuf),
 
     /// A file is being hovered over the window.
@@ -122,9 +161,25 @@ pub enum WindowEvent {
     /// The window received a unicode character.
     ReceivedCharacter(char),
 
+    /// An event from the input method (IME), for showing the text it's composing.
+    ///
+    /// ## Platform-specific
+    ///
+    /// - **X11:** Only input methods that let the application draw the text being composed
+    ///   report `Enabled`, `Preedit` and `Disabled`.
+    /// - **iOS / Android / Emscripten / Wayland:** Unsupported.
+    Ime(Ime),
+
     /// The window gained or lost focus.
     ///
     /// The parameter is true if the window has gained focus, and false if it has lost focus.
+    /// `Focused(true)` and `Focused(false)` always alternate, starting with `Focused(true)` once
+    /// the window first gains focus.
+    ///
+    /// ## Platform-specific
+    ///
+    /// - **X11:** Focus briefly moving away while the keyboard is grabbed, for instance while the
+    ///   window manager switches windows or drags one, isn't reported.
     Focused(bool),
 
     /// An event from the keyboard has been received.
@@ -133,6 +188,20 @@ pub enum WindowEvent {
         input: KeyboardInput,
     },
 
+    /// The keyboard modifiers have changed.
+    ///
+    /// This is sent to the focused window whenever the set of pressed modifiers changes, which
+    /// makes it possible to react to modifiers without keeping track of every `KeyboardInput`.
+    ///
+    /// When the window loses focus, the modifiers are cleared right before `Focused(false)`, and
+    /// when it gains focus, the modifiers that are actually pressed are sent right after
+    /// `Focused(true)`. This way, a modifier released in another window never appears stuck.
+    ///
+    /// ## Platform-specific
+    ///
+    /// - **iOS / Android:** Not emitted.
+    ModifiersChanged(ModifiersState),
+
     /// The cursor has moved on the window.
     CursorMoved {
         device_id: DeviceId,
@@ -145,12 +214,21 @@ pub enum WindowEvent {
     },
 
     /// The cursor has entered the window.
+    ///
+    /// This is always sent before the first `CursorMoved` inside the window.
     CursorEntered { device_id: DeviceId },
 
     /// The cursor has left the window.
+    ///
+    /// No `CursorMoved` events are sent after this until the cursor enters the window again,
+    /// except while a mouse button that was pressed inside the window is held down.
     CursorLeft { device_id: DeviceId },
 
     /// A mouse wheel movement or touchpad scroll occurred.
+    ///
+    /// Scroll wheels always report `TouchPhase::Moved`. On macOS, touchpad scrolling starts with
+    /// `Started` and is `Ended` once the fingers are lifted, but it may then carry on with
+    /// momentum, which is followed by another `Ended`.
     MouseWheel {
         device_id: DeviceId,
         delta: MouseScrollDelta,
@@ -168,9 +246,17 @@ pub enum WindowEvent {
 
     /// Touchpad pressure event.
     ///
-    /// At the moment, only supported on Apple forcetouch-capable macbooks.
     /// The parameters are: pressure level (value between 0 and 1 representing how hard the touchpad
-    /// is being pressed) and stage (integer representing the click level).
+    /// is being pressed) and stage (integer representing the click level). The pressure starts
+    /// over from 0 at each stage: stage 1 is a normal click, and stage 2 a force click. Stage 0
+    /// means the touchpad was let go.
+    ///
+    /// It's only sent while a mouse button pressed over the window is held down.
+    ///
+    /// ## Platform-specific
+    ///
+    /// - **macOS:** Only sent by Force Touch trackpads.
+    /// - **iOS / Android / Emscripten / Windows / X11 / Wayland:** Never sent.
     TouchpadPressure {
         device_id: DeviceId,
         pressure: f32,
@@ -178,6 +264,17 @@ pub enum WindowEvent {
     },
 
     /// Motion on some analog axis. May report data redundant to other, more specific events.
+    ///
+    /// It's only sent while the cursor is over the window or grabbed by it. Each value the device
+    /// reports is sent on its own, even when they arrive faster than the screen refreshes.
+    ///
+    /// ## Platform-specific
+    ///
+    /// - **X11:** The axes are the valuators of the XInput2 device, numbered the way the device
+    ///   lists them. Their labels are returned by `DeviceIdExtUnix::axis_label`.
+    /// - **Windows:** Only sent for pens. Axis 0 is the pressure, from 0 to 1024, axis 1 the
+    ///   rotation in degrees, and axes 2 and 3 the tilt along X and Y, from -90 to 90 degrees.
+    /// - **macOS / iOS / Android / Emscripten / Wayland:** Unsupported.
     AxisMotion {
         device_id: DeviceId,
         axis: AxisId,
@@ -185,6 +282,21 @@ pub enum WindowEvent {
     },
 
     /// The OS or application has requested that the window be redrawn.
+    ///
+    /// Both the repaints requested by the OS, e.g. when the window is exposed or restored from
+    /// being minimized, and the ones requested through `Window::request_redraw` are reported
+    /// through this event, so applications only need a single repaint path. Several requests
+    /// for the same window are merged into one event, which is sent after all pending input
+    /// events have been processed.
+    ///
+    /// ## Platform-specific
+    ///
+    /// - **X11:** Emitted for `Expose` events.
+    /// - **Windows:** Emitted for `WM_PAINT`. The update region is validated afterwards, so the
+    ///   event isn't sent repeatedly when the application doesn't draw anything.
+    /// - **macOS:** Emitted when the view's `drawRect:` is called.
+    /// - **Wayland:** Emitted when the compositor configures the window, e.g. to resize it, and
+    ///   when its DPI factor changes, since the compositor never asks for a repaint otherwise.
     RedrawRequested,
 
     /// Touch event has been received
@@ -198,8 +310,65 @@ pub enum WindowEvent {
     /// * Changing the display's DPI factor (e.g. in Control Panel on Windows).
     /// * Moving the window to a display with a different DPI factor.
     ///
+    /// This is sent before the `Resized` event that usually accompanies it, and
+    /// `Window::hidpi_factor` already returns the new factor while it's handled. Afterwards, the
+    /// window is resized to `new_inner_size`, which keeps its logical size where the platform
+    /// allows it. Calling `Window::set_inner_size` in response picks a different size instead.
+    ///
     /// For more information about DPI in general, see the [`dpi`](dpi/index.html) module.
-    HiDpiFactorChanged(f64),
+    ScaleFactorChanged {
+        scale_factor: f64,
+        new_inner_size: PhysicalSize,
+    },
+
+    /// The system switched between its light and dark appearance, for instance because the user
+    /// turned on dark mode.
+    ///
+    /// This is only sent when the theme the window is drawn with actually changes.
+    ///
+    /// ## Platform-specific
+    ///
+    /// - **Windows:** Follows the "Choose your default app mode" setting of Windows 10.
+    /// - **macOS:** Requires macOS 10.14. An appearance set on the `NSWindow` takes precedence over
+    ///   the system's.
+    /// - **iOS / Android / Emscripten / X11 / Wayland:** Unsupported.
+    ThemeChanged(Theme),
+
+    /// The window became completely hidden (`true`) or partly visible again (`false`).
+    ///
+    /// Rendering can be paused while the window is occluded, since nothing of it can be seen.
+    /// Minimizing the window occludes it too.
+    ///
+    /// ## Platform-specific
+    ///
+    /// - **X11:** Windows that are hidden, or on another workspace, are occluded as well.
+    /// - **Windows:** Being covered by other windows doesn't occlude the window, only minimizing
+    ///   it does. Moving it to another virtual desktop is reported on a best-effort basis.
+    /// - **iOS / Android / Emscripten / Wayland:** Unsupported.
+    Occluded(bool),
+}
+
+/// Describes an event from the input method (IME).
+///
+/// A composition starts with `Enabled`, changes through `Preedit` events, and ends with
+/// `Disabled`. The composed text arrives as a `Commit`, usually right before `Disabled`.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub enum Ime {
+    /// The input method started composing text. Until `Disabled`, the keys it consumes don't
+    /// produce `KeyboardInput` or `ReceivedCharacter` events.
+    Enabled,
+    /// The text being composed changed. It's meant to be drawn where the text cursor is, usually
+    /// underlined. An empty string clears it.
+    ///
+    /// The range is the cursor, or the selected part of the text, as byte offsets into the
+    /// string. `None` hides the cursor.
+    Preedit(String, Option<(usize, usize)>),
+    /// The input method produced the given text, which isn't sent as `ReceivedCharacter` events
+    /// as well.
+    Commit(String),
+    /// The composition ended, so the text being composed should be cleared.
+    Disabled,
 }
 
 /// Identifier of an input device.
@@ -207,6 +376,14 @@ pub enum WindowEvent {
 /// Whenever you receive an event arising from a particular input device, this event contains a `DeviceId` which
 /// identifies its origin. Note that devices may be virtual (representing an on-screen cursor and keyboard focus) or
 /// physical. Virtual devices typically aggregate inputs from multiple physical devices.
+///
+/// The id of a device stays the same for as long as it's connected.
+///
+/// ## Platform-specific
+///
+/// - **Wayland:** The compositor doesn't tell apart the devices of a seat, so they share one id.
+/// - **macOS:** All devices share one id.
+/// - **iOS:** Touches share the id of the screen they happen on.
 #[derive(Debug, Copy, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
 pub struct DeviceId(pub(crate) platform_impl::DeviceId);
 
@@ -228,15 +405,39 @@ impl DeviceId {
 /// window events typically arise from virtual devices (corresponding to GUI cursors and keyboard focus) the device IDs
 /// may not match.
 ///
-/// Note that these events are delivered regardless of input focus.
+/// By default these events are only delivered while one of the application's windows has focus.
+/// This can be changed with [`EventLoopWindowTarget::set_device_event_filter`].
+///
+/// [`EventLoopWindowTarget::set_device_event_filter`]: ../event_loop/struct.EventLoopWindowTarget.html#method.set_device_event_filter
+///
+/// ## Platform-specific
+///
+/// - **Wayland:** Device events are not emitted.
 #[derive(Clone, Debug, PartialEq)]
 pub enum DeviceEvent {
+    /// A new input device has been connected.
+    ///
+    /// ## Platform-specific
+    ///
+    /// - **macOS:** Not emitted.
     Added,
+    /// An input device has been disconnected.
+    ///
+    /// ## Platform-specific
+    ///
+    /// - **macOS:** Not emitted.
     Removed,
 
     /// Change in physical position of a pointing device.
     ///
     /// This represents raw, unfiltered physical motion. Not to be confused with `WindowEvent::CursorMoved`.
+    /// Pointer acceleration isn't applied, and the motion keeps being reported when the cursor
+    /// is at the edge of the screen.
+    ///
+    /// ## Platform-specific
+    ///
+    /// - **Wayland:** Only emitted while the cursor is over a window, and only if the compositor
+    ///   supports the relative pointer protocol.
     MouseMotion {
         /// (x, y) change in position in unspecified units.
         ///
@@ -252,16 +453,28 @@ pub enum DeviceEvent {
     /// Motion on some analog axis.  This event will be reported for all arbitrary input devices
     /// that winit supports on this platform, including mouse devices.  If the device is a mouse
     /// device then this will be reported alongside the MouseMotion event.
+    ///
+    /// For axes reporting a position rather than motion, such as those of graphics tablets and
+    /// joysticks, `value` is that position. On X11, these axes don't produce `MouseMotion` events.
     Motion {
         axis: AxisId,
         value: f64,
     },
 
+    /// A button of a pointing device has been pressed or released.
+    ///
+    /// The left, middle and right buttons are identified by `1`, `2` and `3` respectively.
     Button {
         button: ButtonId,
         state: ElementState,
     },
+
+    /// Raw keyboard input, delivered regardless of which window has keyboard focus.
+    ///
+    /// Not to be confused with `WindowEvent::KeyboardInput`, which goes through the platform's
+    /// text input handling.
     Key(KeyboardInput),
+
     Text {
         codepoint: char,
     },
@@ -276,6 +489,14 @@ pub struct KeyboardInput {
     /// This should not change if the user adjusts the host's keyboard map. Use when the physical location of the
     /// key is more important than the key's host GUI semantics, such as for movement controls in a first-person
     /// game.
+    ///
+    /// ## Platform-specific
+    ///
+    /// This is the scancode used by the OS, so it differs between platforms:
+    ///
+    /// - **Windows:** The PC/AT set 1 make code, without the extended-key prefix.
+    /// - **X11** and **Wayland:** The Linux evdev keycode, i.e. the X keycode minus 8.
+    /// - **macOS:** The virtual key code, as in the `kVK_*` constants.
     pub scancode: ScanCode,
 
     pub state: ElementState,
@@ -322,11 +543,76 @@ pub enum TouchPhase {
 pub struct Touch {
     pub device_id: DeviceId,
     pub phase: TouchPhase,
+    /// The position of the finger, relative to the top-left corner of the window's client area,
+    /// just like `WindowEvent::CursorMoved`.
     pub location: LogicalPosition,
+    /// Describes how hard the screen was pressed. May be `None` if the platform
+    /// does not support pressure sensitivity.
+    ///
+    /// ## Platform-specific
+    ///
+    /// - Only available on **iOS** devices that support 3D Touch, and with the Apple Pencil.
+    pub force: Option<Force>,
     /// unique identifier of a finger.
     pub id: u64,
 }
 
+/// Describes the force of a touch event
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Force {
+    /// On iOS, the force is calibrated so that the same number corresponds to
+    /// roughly the same amount of pressure on the screen regardless of the device.
+    Calibrated {
+        /// The force of the touch, where a value of 1.0 represents the force of
+        /// an average touch (predetermined by the system, not user-specific).
+        ///
+        /// The force reported by Apple Pencil is measured along the axis of the
+        /// pencil. If you want a force perpendicular to the device, you need to
+        /// calculate this value using the `altitude_angle` value.
+        force: f64,
+        /// The maximum possible force for a touch.
+        ///
+        /// The value of this field is sufficiently high to provide a wide
+        /// dynamic range for values of the `force` field.
+        max_possible_force: f64,
+        /// The altitude (in radians) of the stylus.
+        ///
+        /// A value of 0 radians indicates that the stylus is parallel to the
+        /// surface. The value of this property is Pi/2 when the stylus is
+        /// perpendicular to the surface.
+        altitude_angle: Option<f64>,
+    },
+    /// If the platform reports the force as normalized, we have no way of
+    /// knowing how much pressure 1.0 corresponds to – we know it's the maximum
+    /// amount of force, but as to how much force, you might either have to
+    /// press really really hard, or not hard at all, depending on the device.
+    Normalized(f64),
+}
+
+impl Force {
+    /// Returns the force normalized to the range between 0.0 and 1.0 inclusive.
+    ///
+    /// Instead of normalizing the force, you should prefer to handle
+    /// `Force::Calibrated` so that the amount of force the user has to apply is
+    /// consistent across devices.
+    pub fn normalized(&self) -> f64 {
+        match self {
+            Force::Calibrated {
+                force,
+                max_possible_force,
+                altitude_angle,
+            } => {
+                let force = match altitude_angle {
+                    Some(altitude_angle) => force / altitude_angle.sin(),
+                    None => *force,
+                };
+                force / max_possible_force
+            },
+            Force::Normalized(force) => *force,
+        }
+    }
+}
+
 /// Hardware-dependent keyboard scan code.
 pub type ScanCode = u32;
 
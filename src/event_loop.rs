@@ -92,6 +92,31 @@ impl Default for ControlFlow {
     }
 }
 
+/// Filters which raw [`DeviceEvent`]s are delivered to the event loop.
+///
+/// Raw input from mice and keyboards is reported continuously, which is rarely wanted while none
+/// of the application's windows have focus. Set with
+/// [`EventLoopWindowTarget::set_device_event_filter`]. Defaults to `WhenFocused`.
+///
+/// [`DeviceEvent`]: ../event/enum.DeviceEvent.html
+/// [`EventLoopWindowTarget::set_device_event_filter`]: ./struct.EventLoopWindowTarget.html#method.set_device_event_filter
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+pub enum DeviceEventFilter {
+    /// Always deliver device events, even if none of the application's windows have focus.
+    Always,
+    /// Only deliver device events while one of the application's windows has focus.
+    WhenFocused,
+    /// Never deliver device events.
+    Never,
+}
+
+impl Default for DeviceEventFilter {
+    #[inline(always)]
+    fn default() -> DeviceEventFilter {
+        DeviceEventFilter::WhenFocused
+    }
+}
+
 impl EventLoop<()> {
     /// Builds a new event loop with a `()` as the user event type.
     ///
@@ -148,7 +173,7 @@ impl<T> EventLoop<T> {
 
     /// Returns the list of all the monitors available on the system.
     #[inline]
-    pub fn available_monitors(&self) -> impl Iterator<Item = MonitorHandle> {
+    pub fn available_monitors(&self) -> AvailableMonitorsIter {
         let data = self.event_loop.available_monitors();
         AvailableMonitorsIter {
             data: data.into_iter(),
@@ -164,6 +189,23 @@ impl<T> EventLoop<T> {
     }
 }
 
+impl<T> EventLoopWindowTarget<T> {
+    /// Changes which device events are delivered to the event loop.
+    ///
+    /// See [`DeviceEventFilter`] for the available settings.
+    ///
+    /// ## Platform-specific
+    ///
+    /// - **Wayland / macOS / iOS:** Unsupported. Device events are only reported while the
+    ///   application has focus, regardless of the filter.
+    ///
+    /// [`DeviceEventFilter`]: ./enum.DeviceEventFilter.html
+    #[inline]
+    pub fn set_device_event_filter(&self, filter: DeviceEventFilter) {
+        self.p.set_device_event_filter(filter)
+    }
+}
+
 impl<T> Deref for EventLoop<T> {
     type Target = EventLoopWindowTarget<T>;
     fn deref(&self) -> &EventLoopWindowTarget<T> {
@@ -182,7 +224,13 @@ impl<T: 'static> EventLoopProxy<T> {
     /// `UserEvent(event)` event in the event loop, where `event` is the value passed to this
     /// function.
     ///
-    /// Returns an `Err` if the associated `EventLoop` no longer exists.
+    /// Events are delivered in the order they were sent, and each of them is delivered exactly
+    /// once, no matter how many are sent before the event loop gets to them. There's no limit on
+    /// the number of pending events, so sending them faster than they're handled makes the queue
+    /// grow without bound.
+    ///
+    /// Returns an `Err` if the associated `EventLoop` no longer exists. This is safe to call at
+    /// any time, including after the windows of the event loop have been destroyed.
     pub fn send_event(&self, event: T) -> Result<(), EventLoopClosed> {
         self.event_loop_proxy.send_event(event)
     }
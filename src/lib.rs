@@ -94,12 +94,12 @@ extern crate serde;
 #[cfg(target_os = "windows")]
 extern crate derivative;
 #[macro_use]
-#[cfg(target_os = "windows")]
 extern crate bitflags;
 #[cfg(any(target_os = "macos", target_os = "ios"))]
 #[macro_use]
 extern crate objc;
 
+mod cursor;
 pub mod dpi;
 #[macro_use]
 pub mod error;
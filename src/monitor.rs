@@ -10,7 +10,7 @@
 //! [monitor_iter]: ./struct.AvailableMonitorsIter.html
 //! [loop_get]: ../event_loop/struct.EventLoop.html#method.available_monitors
 //! [window_get]: ../window/struct.Window.html#method.available_monitors
-use std::collections::vec_deque::IntoIter as VecDequeIter;
+use std::{collections::vec_deque::IntoIter as VecDequeIter, fmt};
 
 use crate::{
     dpi::{PhysicalPosition, PhysicalSize},
@@ -25,9 +25,12 @@ use crate::{
 ///
 /// [loop_get]: ../event_loop/struct.EventLoop.html#method.available_monitors
 /// [window_get]: ../window/struct.Window.html#method.available_monitors
+///
+/// The list of monitors is only retrieved once, so cloning the iterator is a cheap way of going
+/// over it several times.
 // Implementation note: we retrieve the list once, then serve each element by one by one.
 // This may change in the future.
-#[derive(Debug)]
+#[derive(Debug, Clone)]
 pub struct AvailableMonitorsIter {
     pub(crate) data: VecDequeIter<platform_impl::MonitorHandle>,
 }
@@ -85,12 +88,22 @@ impl VideoMode {
     }
 }
 
+impl fmt::Display for VideoMode {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{}x{} @ {} Hz ({} bpp)",
+            self.size.0, self.size.1, self.refresh_rate, self.bit_depth
+        )
+    }
+}
+
 /// Handle to a monitor.
 ///
 /// Allows you to retrieve information about a given monitor and can be used in [`Window`] creation.
 ///
 /// [`Window`]: ../window/struct.Window.html
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
 pub struct MonitorHandle {
     pub(crate) inner: platform_impl::MonitorHandle,
 }
@@ -4,10 +4,26 @@ use std::os::raw::c_void;
 
 use crate::{
     dpi::LogicalSize,
+    error::ExternalError,
     monitor::MonitorHandle,
-    window::{Window, WindowBuilder},
+    platform_impl,
+    window::{Icon, Window, WindowBuilder},
 };
 
+/// Shows `badge` over the application's icon in the Dock, for instance a count of unread
+/// messages. `None` removes it.
+pub fn set_dock_badge(badge: Option<&str>) {
+    platform_impl::set_dock_badge(badge)
+}
+
+/// Replaces the application's icon in the Dock. `None` brings back the icon from the bundle.
+///
+/// This is the same icon `Window::set_window_icon` changes, since there are no window icons on
+/// macOS.
+pub fn set_dock_icon(icon: Option<Icon>) {
+    platform_impl::set_dock_icon(icon)
+}
+
 /// Additional methods on `Window` that are specific to MacOS.
 pub trait WindowExtMacOS {
     /// Returns a pointer to the cocoa `NSWindow` that is used by this window.
@@ -20,14 +36,6 @@ pub trait WindowExtMacOS {
     /// The pointer will become invalid when the `Window` is destroyed.
     fn ns_view(&self) -> *mut c_void;
 
-    /// Request user attention, causing the application's dock icon to bounce.
-    /// Note that this has no effect if the application is already focused.
-    ///
-    /// The `is_critical` flag has the following effects:
-    /// - `false`: the dock icon will only bounce once.
-    /// - `true`: the dock icon will bounce until the application is focused.
-    fn request_user_attention(&self, is_critical: bool);
-
     /// Returns whether or not the window is in simple fullscreen mode.
     fn simple_fullscreen(&self) -> bool;
 
@@ -39,6 +47,59 @@ pub trait WindowExtMacOS {
     /// And allows the user to have a fullscreen window without using another
     /// space or taking control over the entire monitor.
     fn set_simple_fullscreen(&self, fullscreen: bool) -> bool;
+
+    /// Sets the subtitle shown below the title in the title bar. An empty string removes it.
+    ///
+    /// Subtitles were introduced in macOS 11, so this has no effect on earlier versions.
+    fn set_subtitle(&self, subtitle: &str);
+
+    /// Hides or shows the titlebar, like `WindowBuilderExtMacOS::with_titlebar_hidden` does when
+    /// building the window.
+    ///
+    /// The window keeps its frame, so the content grows into the space of the titlebar when it's
+    /// hidden and shrinks again when it's shown, sending `WindowEvent::Resized` either way. This
+    /// has no effect on windows without decorations or in fullscreen.
+    ///
+    /// The titlebar buttons go away along with the titlebar, so `set_titlebar_buttons_hidden`
+    /// has no effect until it's shown again.
+    fn set_titlebar_hidden(&self, hidden: bool);
+
+    /// Hides or shows the close, minimize and zoom buttons in the titlebar.
+    fn set_titlebar_buttons_hidden(&self, hidden: bool);
+
+    /// Returns whether the window draws a shadow.
+    fn has_shadow(&self) -> bool;
+
+    /// Sets whether the window draws a shadow.
+    fn set_has_shadow(&self, has_shadow: bool);
+
+    /// Switches to the next tab of the window's tab group, wrapping around at the end.
+    ///
+    /// Tabs were introduced in macOS 10.12, so this has no effect on earlier versions.
+    fn select_next_tab(&self);
+
+    /// Switches to the previous tab of the window's tab group, wrapping around at the start.
+    ///
+    /// Tabs were introduced in macOS 10.12, so this has no effect on earlier versions.
+    fn select_previous_tab(&self);
+
+    /// Returns the number of tabs in the window's tab group, which is 1 for a window that isn't
+    /// in one.
+    fn num_tabs(&self) -> usize;
+
+    /// Recomputes the shadow from the window's current content.
+    ///
+    /// The shadow of a window with transparent content follows the opaque pixels, but it's only
+    /// computed once, so it needs to be invalidated after drawing a different shape.
+    fn invalidate_shadow(&self);
+
+    /// Blurs whatever is behind the transparent parts of the window, the way the sidebars of
+    /// Finder do.
+    ///
+    /// Returns `Err(ExternalError::NotSupported(_))` if the window wasn't built with
+    /// `with_transparent(true)`, or on macOS versions before 10.10, which don't have
+    /// `NSVisualEffectView`.
+    fn set_blur(&self, blur: bool) -> Result<(), ExternalError>;
 }
 
 impl WindowExtMacOS for Window {
@@ -52,11 +113,6 @@ impl WindowExtMacOS for Window {
         self.window.ns_view()
     }
 
-    #[inline]
-    fn request_user_attention(&self, is_critical: bool) {
-        self.window.request_user_attention(is_critical)
-    }
-
     #[inline]
     fn simple_fullscreen(&self) -> bool {
         self.window.simple_fullscreen()
@@ -66,6 +122,56 @@ impl WindowExtMacOS for Window {
     fn set_simple_fullscreen(&self, fullscreen: bool) -> bool {
         self.window.set_simple_fullscreen(fullscreen)
     }
+
+    #[inline]
+    fn set_subtitle(&self, subtitle: &str) {
+        self.window.set_subtitle(subtitle)
+    }
+
+    #[inline]
+    fn set_titlebar_hidden(&self, hidden: bool) {
+        self.window.set_titlebar_hidden(hidden)
+    }
+
+    #[inline]
+    fn set_titlebar_buttons_hidden(&self, hidden: bool) {
+        self.window.set_titlebar_buttons_hidden(hidden)
+    }
+
+    #[inline]
+    fn has_shadow(&self) -> bool {
+        self.window.has_shadow()
+    }
+
+    #[inline]
+    fn set_has_shadow(&self, has_shadow: bool) {
+        self.window.set_has_shadow(has_shadow)
+    }
+
+    #[inline]
+    fn select_next_tab(&self) {
+        self.window.select_next_tab()
+    }
+
+    #[inline]
+    fn select_previous_tab(&self) {
+        self.window.select_previous_tab()
+    }
+
+    #[inline]
+    fn num_tabs(&self) -> usize {
+        self.window.num_tabs()
+    }
+
+    #[inline]
+    fn invalidate_shadow(&self) {
+        self.window.invalidate_shadow()
+    }
+
+    #[inline]
+    fn set_blur(&self, blur: bool) -> Result<(), ExternalError> {
+        self.window.set_blur(blur)
+    }
 }
 
 /// Corresponds to `NSApplicationActivationPolicy`.
@@ -101,7 +207,9 @@ pub trait WindowBuilderExtMacOS {
     /// Enables click-and-drag behavior for the entire window, not just the titlebar.
     fn with_movable_by_window_background(self, movable_by_window_background: bool)
         -> WindowBuilder;
-    /// Makes the titlebar transparent and allows the content to appear behind it.
+    /// Makes the titlebar transparent and allows the content to appear behind it, like in Safari
+    /// or Finder. This implies `with_fullsize_content_view(true)`, and is usually combined with
+    /// `with_title_hidden(true)`.
     fn with_titlebar_transparent(self, titlebar_transparent: bool) -> WindowBuilder;
     /// Hides the window title.
     fn with_title_hidden(self, title_hidden: bool) -> WindowBuilder;
@@ -113,6 +221,18 @@ pub trait WindowBuilderExtMacOS {
     fn with_fullsize_content_view(self, fullsize_content_view: bool) -> WindowBuilder;
     /// Build window with `resizeIncrements` property. Values must not be 0.
     fn with_resize_increments(self, increments: LogicalSize) -> WindowBuilder;
+    /// Sets whether the window draws a shadow.
+    ///
+    /// Windows have a shadow by default, except for transparent windows without decorations,
+    /// which usually draw a shape of their own that a rectangular shadow wouldn't match.
+    fn with_has_shadow(self, has_shadow: bool) -> WindowBuilder;
+    /// Groups the window with the other windows that have the same identifier. While the user
+    /// prefers tabs in the Dock settings, such windows open as tabs of one another.
+    ///
+    /// Each tab is still its own window, with its own `WindowId` and events.
+    fn with_tabbing_identifier(self, tabbing_identifier: &str) -> WindowBuilder;
+    /// Opens the window as a new tab next to `window`, regardless of the user's preference.
+    fn with_tab_of(self, window: &Window) -> WindowBuilder;
 }
 
 impl WindowBuilderExtMacOS for WindowBuilder {
@@ -166,6 +286,24 @@ impl WindowBuilderExtMacOS for WindowBuilder {
         self.platform_specific.resize_increments = Some(increments.into());
         self
     }
+
+    #[inline]
+    fn with_has_shadow(mut self, has_shadow: bool) -> WindowBuilder {
+        self.platform_specific.has_shadow = Some(has_shadow);
+        self
+    }
+
+    #[inline]
+    fn with_tabbing_identifier(mut self, tabbing_identifier: &str) -> WindowBuilder {
+        self.platform_specific.tabbing_identifier = Some(tabbing_identifier.to_owned());
+        self
+    }
+
+    #[inline]
+    fn with_tab_of(mut self, window: &Window) -> WindowBuilder {
+        self.platform_specific.tab_of = Some(window.id().0);
+        self
+    }
 }
 
 /// Additional methods on `MonitorHandle` that are specific to MacOS.
@@ -6,6 +6,8 @@ use smithay_client_toolkit::window::{ButtonState, Theme};
 
 use crate::{
     dpi::LogicalSize,
+    error::{ExternalError, NotSupportedError},
+    event::{AxisId, DeviceId},
     event_loop::EventLoop,
     monitor::MonitorHandle,
     window::{Window, WindowBuilder},
@@ -191,6 +193,19 @@ pub trait WindowExtUnix {
     /// Set window urgency hint (`XUrgencyHint`). Only relevant on X.
     fn set_urgent(&self, is_urgent: bool);
 
+    /// Grabs the keyboard, so that shortcuts that the window manager would normally handle, like
+    /// Alt+Tab, are delivered to the window as `WindowEvent::KeyboardInput` instead. This is
+    /// meant for kiosk applications.
+    ///
+    /// If the window manager takes the keyboard back for a moment, the grab is restored once the
+    /// window regains focus. Hiding or closing the window releases the grab.
+    ///
+    /// Returns `Err(ExternalError::NotFocused)` if another client holds the keyboard or the
+    /// window isn't visible yet, in which case the grab can be retried later.
+    ///
+    /// Always returns `Err(ExternalError::NotSupported(_))` on Wayland.
+    fn set_keyboard_grab(&self, grab: bool) -> Result<(), ExternalError>;
+
     /// This function returns the underlying `xcb_connection_t` of an xlib `Display`.
     ///
     /// Returns `None` if the window doesn't use xlib (if it uses wayland for example).
@@ -274,6 +289,14 @@ impl WindowExtUnix for Window {
         }
     }
 
+    #[inline]
+    fn set_keyboard_grab(&self, grab: bool) -> Result<(), ExternalError> {
+        match self.window {
+            LinuxWindow::X(ref w) => w.set_keyboard_grab(grab),
+            _ => Err(ExternalError::NotSupported(NotSupportedError::new())),
+        }
+    }
+
     #[inline]
     fn wayland_surface(&self) -> Option<*mut raw::c_void> {
         match self.window {
@@ -325,6 +348,8 @@ pub trait WindowBuilderExtUnix {
     /// Build window with a given application ID. It should match the `.desktop` file distributed with
     /// your program. Only relevant on Wayland.
     ///
+    /// Defaults to the file name of the executable.
+    ///
     /// For details about application ID conventions, see the
     /// [Desktop Entry Spec](https://specifications.freedesktop.org/desktop-entry-spec/desktop-entry-spec-latest.html#desktop-file-id)
     fn with_app_id(self, app_id: String) -> WindowBuilder;
@@ -399,3 +424,20 @@ impl MonitorHandleExtUnix for MonitorHandle {
         self.inner.native_identifier()
     }
 }
+
+/// Additional methods on `DeviceId` that are specific to Linux.
+pub trait DeviceIdExtUnix {
+    /// Returns the label the X server gives to the axis reported as `axis` by `AxisMotion`
+    /// events, like `"Abs Pressure"` or `"Abs Tilt X"`.
+    ///
+    /// Returns `None` on Wayland, if the device is no longer available, or if the axis doesn't
+    /// have a label.
+    fn axis_label(&self, axis: AxisId) -> Option<String>;
+}
+
+impl DeviceIdExtUnix for DeviceId {
+    #[inline]
+    fn axis_label(&self, axis: AxisId) -> Option<String> {
+        self.0.axis_label(axis)
+    }
+}
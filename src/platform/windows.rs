@@ -6,6 +6,7 @@ use libc;
 use winapi::shared::windef::HWND;
 
 use crate::{
+    error::ExternalError,
     event::DeviceId,
     event_loop::EventLoop,
     monitor::MonitorHandle,
@@ -32,6 +33,18 @@ impl<T> EventLoopExtWindows for EventLoop<T> {
     }
 }
 
+/// The backdrop drawn behind the transparent parts of a window.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+pub enum BlurEffect {
+    /// Whatever is behind the window shows through as is.
+    None,
+    /// Whatever is behind the window is blurred.
+    Blur,
+    /// Whatever is behind the window is blurred, tinted with `(red, green, blue, alpha)` and
+    /// given some noise, like the Fluent acrylic material.
+    Acrylic { tint: (u8, u8, u8, u8) },
+}
+
 /// Additional methods on `Window` that are specific to Windows.
 pub trait WindowExtWindows {
     /// Returns the native handle that is used by this window.
@@ -41,6 +54,22 @@ pub trait WindowExtWindows {
 
     /// This sets `ICON_BIG`. A good ceiling here is 256x256.
     fn set_taskbar_icon(&self, taskbar_icon: Option<Icon>);
+
+    /// Shows a small icon over the window's taskbar button, for instance to badge it with a
+    /// count of unread messages. `None` removes it. The icon should be 16x16.
+    ///
+    /// `description` is read out by screen readers in place of the icon.
+    fn set_taskbar_overlay_icon(&self, overlay_icon: Option<Icon>, description: &str);
+
+    /// Sets the backdrop drawn behind the transparent parts of the window. This can be called
+    /// again at any time, for instance to change the tint.
+    ///
+    /// Returns `Err(ExternalError::NotSupported(_))` if the window wasn't built with
+    /// `with_transparent(true)`, or if this version of Windows doesn't have the effect. Acrylic
+    /// needs Windows 10 version 1803. On Windows 8, `Blur` succeeds but nothing gets blurred.
+    ///
+    /// Without decorations, the effect covers the whole window.
+    fn set_blur_behind(&self, effect: BlurEffect) -> Result<(), ExternalError>;
 }
 
 impl WindowExtWindows for Window {
@@ -53,6 +82,17 @@ impl WindowExtWindows for Window {
     fn set_taskbar_icon(&self, taskbar_icon: Option<Icon>) {
         self.window.set_taskbar_icon(taskbar_icon)
     }
+
+    #[inline]
+    fn set_taskbar_overlay_icon(&self, overlay_icon: Option<Icon>, description: &str) {
+        self.window
+            .set_taskbar_overlay_icon(overlay_icon, description)
+    }
+
+    #[inline]
+    fn set_blur_behind(&self, effect: BlurEffect) -> Result<(), ExternalError> {
+        self.window.set_blur_behind(effect)
+    }
 }
 
 /// Additional methods on `WindowBuilder` that are specific to Windows.
@@ -65,6 +105,13 @@ pub trait WindowBuilderExtWindows {
 
     /// This sets `WS_EX_NOREDIRECTIONBITMAP`.
     fn with_no_redirection_bitmap(self, flag: bool) -> WindowBuilder;
+
+    /// Enables or disables drag and drop support (enabled by default).
+    ///
+    /// Drag and drop requires initializing OLE on the window's thread, which fails if COM was
+    /// already initialized there in multithreaded mode. Disable it if your application (or a
+    /// library it uses, such as an audio library) needs `COINIT_MULTITHREADED` on that thread.
+    fn with_drag_and_drop(self, flag: bool) -> WindowBuilder;
 }
 
 impl WindowBuilderExtWindows for WindowBuilder {
@@ -85,6 +132,12 @@ impl WindowBuilderExtWindows for WindowBuilder {
         self.platform_specific.no_redirection_bitmap = flag;
         self
     }
+
+    #[inline]
+    fn with_drag_and_drop(mut self, flag: bool) -> WindowBuilder {
+        self.platform_specific.drag_and_drop = flag;
+        self
+    }
 }
 
 /// Additional methods on `MonitorHandle` that are specific to Windows.
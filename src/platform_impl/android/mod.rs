@@ -15,7 +15,10 @@ use std::{
 use crate::{
     error::{ExternalError, NotSupportedError},
     events::{Touch, TouchPhase},
-    window::MonitorHandle as RootMonitorHandle,
+    window::{
+        BadCursor, FullscreenType, MonitorHandle as RootMonitorHandle, Rect, ResizeDirection,
+        UserAttentionType, WindowButtons, WindowPlacement,
+    },
     CreationError, CursorIcon, Event, LogicalPosition, LogicalSize, PhysicalPosition, PhysicalSize,
     WindowAttributes, WindowEvent, WindowId as RootWindowId,
 };
@@ -75,6 +78,7 @@ impl EventLoop {
                                 android_glue::MotionAction::Cancel => TouchPhase::Cancelled,
                             },
                             location,
+                            force: None,
                             id: motion.pointer_id as u64,
                             device_id: DEVICE_ID,
                         }),
@@ -130,6 +134,8 @@ impl EventLoop {
         *self.suspend_callback.borrow_mut() = cb;
     }
 
+    // TODO: Port this to `EventLoop::run`, which the other backends implement by now, so that the
+    // loop owns the call stack and honors `ControlFlow::{Wait, WaitUntil}`.
     pub fn run_forever<F>(&mut self, mut callback: F)
     where
         F: FnMut(::Event) -> ::ControlFlow,
@@ -179,11 +185,14 @@ impl DeviceId {
     }
 }
 
+#[derive(Debug, Clone)]
+pub struct CustomCursor;
+
 pub struct Window {
     native_window: *const c_void,
 }
 
-#[derive(Clone)]
+#[derive(Clone, PartialEq, Eq, Hash)]
 pub struct MonitorHandle;
 
 impl fmt::Debug for MonitorHandle {
@@ -270,6 +279,16 @@ impl Window {
         // N/A
     }
 
+    #[inline]
+    pub fn get_title(&self) -> String {
+        String::new()
+    }
+
+    #[inline]
+    pub fn is_focused(&self) -> bool {
+        true
+    }
+
     #[inline]
     pub fn show(&self) {
         // N/A
@@ -280,6 +299,16 @@ impl Window {
         // N/A
     }
 
+    #[inline]
+    pub fn close(&self) {
+        // N/A
+    }
+
+    #[inline]
+    pub fn is_visible(&self) -> Option<bool> {
+        None
+    }
+
     #[inline]
     pub fn outer_position(&self) -> Option<LogicalPosition> {
         // N/A
@@ -307,6 +336,16 @@ impl Window {
         // N/A
     }
 
+    #[inline]
+    pub fn get_placement(&self) -> Option<WindowPlacement> {
+        None
+    }
+
+    #[inline]
+    pub fn set_placement(&self, _placement: &WindowPlacement) {
+        // N/A
+    }
+
     #[inline]
     pub fn set_resizable(&self, _resizable: bool) {
         // N/A
@@ -318,7 +357,7 @@ impl Window {
             None
         } else {
             let dpi_factor = self.hidpi_factor();
-            let physical_size = self.current_monitor().size();
+            let physical_size = MonitorHandle.size();
             Some(LogicalSize::from_physical(physical_size, dpi_factor))
         }
     }
@@ -329,13 +368,14 @@ impl Window {
     }
 
     #[inline]
-    pub fn set_inner_size(&self, _size: LogicalSize) {
+    pub fn set_inner_size(&self, _size: LogicalSize) -> Option<LogicalSize> {
         // N/A
+        None
     }
 
     #[inline]
     pub fn hidpi_factor(&self) -> f64 {
-        self.current_monitor().hidpi_factor()
+        MonitorHandle.hidpi_factor()
     }
 
     #[inline]
@@ -343,6 +383,23 @@ impl Window {
         // N/A
     }
 
+    #[inline]
+    pub fn create_custom_cursor(
+        &self,
+        _rgba: &[u8],
+        _width: u32,
+        _height: u32,
+        _hotspot_x: u32,
+        _hotspot_y: u32,
+    ) -> Result<CustomCursor, BadCursor> {
+        Err(BadCursor::NotSupported)
+    }
+
+    #[inline]
+    pub fn set_custom_cursor(&self, _cursor: &CustomCursor) {
+        // N/A
+    }
+
     #[inline]
     pub fn set_cursor_grab(&self, _grab: bool) -> Result<(), ExternalError> {
         Err(ExternalError::NotSupported(NotSupportedError::new()))
@@ -358,6 +415,36 @@ impl Window {
         Err(ExternalError::NotSupported(NotSupportedError::new()))
     }
 
+    #[inline]
+    pub fn request_user_attention(&self, _request_type: Option<UserAttentionType>) {
+        // N/A
+    }
+
+    #[inline]
+    pub fn drag_window(&self) -> Result<(), ExternalError> {
+        Err(ExternalError::NotSupported(NotSupportedError::new()))
+    }
+
+    #[inline]
+    pub fn drag_resize_window(&self, _direction: ResizeDirection) -> Result<(), ExternalError> {
+        Err(ExternalError::NotSupported(NotSupportedError::new()))
+    }
+
+    #[inline]
+    pub fn reset_dead_keys(&self) {
+        // N/A
+    }
+
+    #[inline]
+    pub fn set_enabled(&self, _enabled: bool) {
+        // N/A
+    }
+
+    #[inline]
+    pub fn show_window_menu(&self, _position: LogicalPosition) {
+        // N/A
+    }
+
     #[inline]
     pub fn set_maximized(&self, _maximized: bool) {
         // N/A
@@ -365,14 +452,19 @@ impl Window {
     }
 
     #[inline]
-    pub fn fullscreen(&self) -> Option<RootMonitorHandle> {
+    pub fn get_maximized(&self) -> bool {
+        false
+    }
+
+    #[inline]
+    pub fn fullscreen(&self) -> Option<FullscreenType> {
         // N/A
         // Android has single screen maximized apps so nothing to do
         None
     }
 
     #[inline]
-    pub fn set_fullscreen(&self, _monitor: Option<RootMonitorHandle>) {
+    pub fn set_fullscreen(&self, _fullscreen: Option<FullscreenType>) {
         // N/A
         // Android has single screen maximized apps so nothing to do
     }
@@ -382,11 +474,56 @@ impl Window {
         // N/A
     }
 
+    #[inline]
+    pub fn get_resizable(&self) -> bool {
+        false
+    }
+
+    #[inline]
+    pub fn pre_present_notify(&self) {
+        // N/A
+    }
+
+    #[inline]
+    pub fn get_decorations(&self) -> bool {
+        true
+    }
+
     #[inline]
     pub fn set_always_on_top(&self, _always_on_top: bool) {
         // N/A
     }
 
+    #[inline]
+    pub fn set_skip_taskbar(&self, _skip: bool) {
+        // N/A
+    }
+
+    #[inline]
+    pub fn set_enabled_buttons(&self, _buttons: WindowButtons) {
+        // N/A
+    }
+
+    #[inline]
+    pub fn set_content_protected(&self, _protected: bool) {
+        // N/A
+    }
+
+    #[inline]
+    pub fn set_opacity(&self, _opacity: f32) {
+        // N/A
+    }
+
+    #[inline]
+    pub fn set_shape(&self, _shape: Option<&[Rect]>) -> Result<(), ExternalError> {
+        Err(ExternalError::NotSupported(NotSupportedError::new()))
+    }
+
+    #[inline]
+    pub fn set_cursor_hittest(&self, _hittest: bool) -> Result<(), ExternalError> {
+        Err(ExternalError::NotSupported(NotSupportedError::new()))
+    }
+
     #[inline]
     pub fn set_window_icon(&self, _icon: Option<::Icon>) {
         // N/A
@@ -398,10 +535,15 @@ impl Window {
     }
 
     #[inline]
-    pub fn current_monitor(&self) -> RootMonitorHandle {
-        RootMonitorHandle {
+    pub fn set_ime_allowed(&self, _allowed: bool) {
+        // N/A
+    }
+
+    #[inline]
+    pub fn current_monitor(&self) -> Option<RootMonitorHandle> {
+        Some(RootMonitorHandle {
             inner: MonitorHandle,
-        }
+        })
     }
 
     #[inline]
@@ -17,7 +17,10 @@ use std::{
 use crate::{
     dpi::{LogicalPosition, LogicalSize, PhysicalPosition, PhysicalSize},
     error::{ExternalError, NotSupportedError},
-    window::MonitorHandle as RootMonitorHandle,
+    window::{
+        BadCursor, MonitorHandle as RootMonitorHandle, Rect, ResizeDirection, UserAttentionType,
+        WindowButtons, WindowPlacement,
+    },
 };
 
 const DOCUMENT_NAME: &'static str = "#document\0";
@@ -43,10 +46,13 @@ impl DeviceId {
     }
 }
 
+#[derive(Debug, Clone)]
+pub struct CustomCursor;
+
 #[derive(Clone, Default)]
 pub struct PlatformSpecificHeadlessBuilderAttributes;
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
 pub struct MonitorHandle;
 
 impl MonitorHandle {
@@ -154,6 +160,8 @@ impl EventLoop {
         }
     }
 
+    // TODO: Port this to `EventLoop::run`, handing `ControlFlow` to the closure instead of
+    // expecting it back. `set_main_loop_callback` already gives the browser the call stack.
     pub fn run_forever<F>(&self, mut callback: F)
     where
         F: FnMut(::Event) -> ::ControlFlow,
@@ -362,6 +370,7 @@ extern "C" fn touch_callback(
                         phase,
                         id: touch.identifier as u64,
                         location,
+                        force: None,
                     }),
                 });
             }
@@ -520,6 +529,16 @@ impl Window {
     #[inline]
     pub fn set_title(&self, _title: &str) {}
 
+    #[inline]
+    pub fn get_title(&self) -> String {
+        String::new()
+    }
+
+    #[inline]
+    pub fn is_focused(&self) -> bool {
+        true
+    }
+
     #[inline]
     pub fn outer_position(&self) -> Option<LogicalPosition> {
         Some((0, 0).into())
@@ -558,7 +577,10 @@ impl Window {
     }
 
     #[inline]
-    pub fn set_inner_size(&self, size: LogicalSize) {
+    pub fn set_inner_size(&self, size: LogicalSize) -> Option<LogicalSize> {
+        if self.inner_size() == Some(size) {
+            return Some(size);
+        }
         unsafe {
             let dpi_factor = self.hidpi_factor();
             let physical = PhysicalSize::from_logical(size, dpi_factor);
@@ -569,6 +591,16 @@ impl Window {
                 height as c_double,
             );
         }
+        // Changing the CSS size of the canvas doesn't produce any event.
+        self.window
+            .events
+            .lock()
+            .unwrap()
+            .push_back(::Event::WindowEvent {
+                window_id: ::WindowId(WindowId(0)),
+                event: ::WindowEvent::Resized(size),
+            });
+        Some(size)
     }
 
     #[inline]
@@ -581,6 +613,16 @@ impl Window {
         // N/A
     }
 
+    #[inline]
+    pub fn get_placement(&self) -> Option<WindowPlacement> {
+        None
+    }
+
+    #[inline]
+    pub fn set_placement(&self, _placement: &WindowPlacement) {
+        // N/A
+    }
+
     #[inline]
     pub fn set_resizable(&self, _resizable: bool) {
         // N/A
@@ -596,11 +638,38 @@ impl Window {
         // N/A
     }
 
+    #[inline]
+    pub fn close(&self) {
+        // N/A
+    }
+
+    #[inline]
+    pub fn is_visible(&self) -> Option<bool> {
+        None
+    }
+
     #[inline]
     pub fn set_cursor_icon(&self, _cursor: ::CursorIcon) {
         // N/A
     }
 
+    #[inline]
+    pub fn create_custom_cursor(
+        &self,
+        _rgba: &[u8],
+        _width: u32,
+        _height: u32,
+        _hotspot_x: u32,
+        _hotspot_y: u32,
+    ) -> Result<CustomCursor, BadCursor> {
+        Err(BadCursor::NotSupported)
+    }
+
+    #[inline]
+    pub fn set_custom_cursor(&self, _cursor: &CustomCursor) {
+        // N/A
+    }
+
     #[inline]
     pub fn set_cursor_grab(&self, grab: bool) -> Result<(), ExternalError> {
         let mut grabbed_lock = self.window.cursor_grabbed.lock().unwrap();
@@ -654,7 +723,37 @@ impl Window {
 
     #[inline]
     pub fn set_cursor_position(&self, _position: LogicalPosition) -> Result<(), ExternalError> {
-        Err("Setting cursor position is not possible on Emscripten.".to_owned())
+        Err(ExternalError::NotSupported(NotSupportedError::new()))
+    }
+
+    #[inline]
+    pub fn request_user_attention(&self, _request_type: Option<UserAttentionType>) {
+        // N/A
+    }
+
+    #[inline]
+    pub fn drag_window(&self) -> Result<(), ExternalError> {
+        Err(ExternalError::NotSupported(NotSupportedError::new()))
+    }
+
+    #[inline]
+    pub fn drag_resize_window(&self, _direction: ResizeDirection) -> Result<(), ExternalError> {
+        Err(ExternalError::NotSupported(NotSupportedError::new()))
+    }
+
+    #[inline]
+    pub fn reset_dead_keys(&self) {
+        // N/A
+    }
+
+    #[inline]
+    pub fn set_enabled(&self, _enabled: bool) {
+        // N/A
+    }
+
+    #[inline]
+    pub fn show_window_menu(&self, _position: LogicalPosition) {
+        // N/A
     }
 
     #[inline]
@@ -663,12 +762,17 @@ impl Window {
     }
 
     #[inline]
-    pub fn fullscreen(&self) -> Option<::MonitorHandle> {
+    pub fn get_maximized(&self) -> bool {
+        false
+    }
+
+    #[inline]
+    pub fn fullscreen(&self) -> Option<::FullscreenType> {
         None
     }
 
     #[inline]
-    pub fn set_fullscreen(&self, _monitor: Option<::MonitorHandle>) {
+    pub fn set_fullscreen(&self, _fullscreen: Option<::FullscreenType>) {
         // iOS has single screen maximized apps so nothing to do
     }
 
@@ -677,11 +781,56 @@ impl Window {
         // N/A
     }
 
+    #[inline]
+    pub fn get_resizable(&self) -> bool {
+        false
+    }
+
+    #[inline]
+    pub fn pre_present_notify(&self) {
+        // N/A
+    }
+
+    #[inline]
+    pub fn get_decorations(&self) -> bool {
+        true
+    }
+
     #[inline]
     pub fn set_always_on_top(&self, _always_on_top: bool) {
         // N/A
     }
 
+    #[inline]
+    pub fn set_skip_taskbar(&self, _skip: bool) {
+        // N/A
+    }
+
+    #[inline]
+    pub fn set_enabled_buttons(&self, _buttons: WindowButtons) {
+        // N/A
+    }
+
+    #[inline]
+    pub fn set_content_protected(&self, _protected: bool) {
+        // N/A
+    }
+
+    #[inline]
+    pub fn set_opacity(&self, _opacity: f32) {
+        // N/A
+    }
+
+    #[inline]
+    pub fn set_shape(&self, _shape: Option<&[Rect]>) -> Result<(), ExternalError> {
+        Err(ExternalError::NotSupported(NotSupportedError::new()))
+    }
+
+    #[inline]
+    pub fn set_cursor_hittest(&self, _hittest: bool) -> Result<(), ExternalError> {
+        Err(ExternalError::NotSupported(NotSupportedError::new()))
+    }
+
     #[inline]
     pub fn set_window_icon(&self, _icon: Option<::Icon>) {
         // N/A
@@ -693,10 +842,15 @@ impl Window {
     }
 
     #[inline]
-    pub fn current_monitor(&self) -> RootMonitorHandle {
-        RootMonitorHandle {
+    pub fn set_ime_allowed(&self, _allowed: bool) {
+        // N/A
+    }
+
+    #[inline]
+    pub fn current_monitor(&self) -> Option<RootMonitorHandle> {
+        Some(RootMonitorHandle {
             inner: MonitorHandle,
-        }
+        })
     }
 
     #[inline]
@@ -10,7 +10,8 @@ use std::{
 use crate::{
     event::Event,
     event_loop::{
-        ControlFlow, EventLoopClosed, EventLoopWindowTarget as RootEventLoopWindowTarget,
+        ControlFlow, DeviceEventFilter, EventLoopClosed,
+        EventLoopWindowTarget as RootEventLoopWindowTarget,
     },
     platform::ios::Idiom,
 };
@@ -39,6 +40,14 @@ impl<T: 'static> EventLoopWindowTarget<T> {
     pub fn capabilities(&self) -> &Capabilities {
         &self.capabilities
     }
+
+    #[inline]
+    pub fn set_device_event_filter(&self, _filter: DeviceEventFilter) {}
+
+    pub fn primary_monitor(&self) -> MonitorHandle {
+        // guaranteed to be on main thread
+        unsafe { monitor::main_uiscreen() }
+    }
 }
 
 pub struct EventLoop<T: 'static> {
@@ -142,8 +151,7 @@ pub struct EventLoopProxy<T> {
     source: CFRunLoopSourceRef,
 }
 
-unsafe impl<T> Send for EventLoopProxy<T> {}
-unsafe impl<T> Sync for EventLoopProxy<T> {}
+unsafe impl<T: Send> Send for EventLoopProxy<T> {}
 
 impl<T> Clone for EventLoopProxy<T> {
     fn clone(&self) -> EventLoopProxy<T> {
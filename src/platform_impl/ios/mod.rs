@@ -99,9 +99,18 @@ impl DeviceId {
 unsafe impl Send for DeviceId {}
 unsafe impl Sync for DeviceId {}
 
+#[derive(Debug, Clone)]
+pub struct CustomCursor;
+
 #[derive(Debug)]
 pub enum OsError {}
 
+impl OsError {
+    pub fn raw_os_error(&self) -> Option<i32> {
+        match *self {}
+    }
+}
+
 impl fmt::Display for OsError {
     fn fmt(&self, _: &mut fmt::Formatter<'_>) -> fmt::Result {
         match self {
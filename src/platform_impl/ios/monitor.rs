@@ -1,6 +1,7 @@
 use std::{
     collections::{HashSet, VecDeque},
     fmt,
+    hash::{Hash, Hasher},
     ops::{Deref, DerefMut},
 };
 
@@ -62,6 +63,20 @@ impl Clone for MonitorHandle {
     }
 }
 
+impl PartialEq for MonitorHandle {
+    fn eq(&self, other: &Self) -> bool {
+        self.inner.uiscreen == other.inner.uiscreen
+    }
+}
+
+impl Eq for MonitorHandle {}
+
+impl Hash for MonitorHandle {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.inner.uiscreen.hash(state);
+    }
+}
+
 impl Drop for MonitorHandle {
     fn drop(&mut self) {
         unsafe {
@@ -6,7 +6,7 @@ use objc::{
 };
 
 use crate::{
-    event::{DeviceId as RootDeviceId, Event, Touch, TouchPhase, WindowEvent},
+    event::{DeviceId as RootDeviceId, Event, Force, Touch, TouchPhase, WindowEvent},
     platform::ios::MonitorHandleExtIOS,
     window::{WindowAttributes, WindowId as RootWindowId},
 };
@@ -14,7 +14,10 @@ use crate::{
 use crate::platform_impl::platform::{
     app_state::AppState,
     event_loop,
-    ffi::{id, nil, CGFloat, CGPoint, CGRect, UIInterfaceOrientationMask, UITouchPhase},
+    ffi::{
+        id, nil, CGFloat, CGPoint, CGRect, NSInteger, UIInterfaceOrientationMask, UITouchPhase,
+        UITouchType,
+    },
     window::PlatformSpecificWindowBuilderAttributes,
     DeviceId,
 };
@@ -189,6 +192,17 @@ unsafe fn get_window_class() -> &'static Class {
                 let uiscreen = msg_send![object, screen];
                 let touches_enum: id = msg_send![touches, objectEnumerator];
                 let mut touch_events = Vec::new();
+                // 3D Touch was introduced in iOS 9, and isn't available on every device.
+                let trait_collection: id = msg_send![object, traitCollection];
+                let responds: BOOL = msg_send![
+                    trait_collection,
+                    respondsToSelector: sel!(forceTouchCapability)
+                ];
+                let force_touch_available = responds == YES && {
+                    let capability: NSInteger = msg_send![trait_collection, forceTouchCapability];
+                    // `UIForceTouchCapabilityAvailable`
+                    capability == 2
+                };
                 loop {
                     let touch: id = msg_send![touches_enum, nextObject];
                     if touch == nil {
@@ -196,6 +210,24 @@ unsafe fn get_window_class() -> &'static Class {
                     }
                     let location: CGPoint = msg_send![touch, locationInView: nil];
                     let touch_id = touch as u64;
+                    let touch_type: UITouchType = msg_send![touch, type];
+                    let force = if force_touch_available || touch_type == UITouchType::Stylus {
+                        let force: CGFloat = msg_send![touch, force];
+                        let max_possible_force: CGFloat = msg_send![touch, maximumPossibleForce];
+                        let altitude_angle: Option<f64> = if touch_type == UITouchType::Stylus {
+                            let angle: CGFloat = msg_send![touch, altitudeAngle];
+                            Some(angle as _)
+                        } else {
+                            None
+                        };
+                        Some(Force::Calibrated {
+                            force: force as _,
+                            max_possible_force: max_possible_force as _,
+                            altitude_angle,
+                        })
+                    } else {
+                        None
+                    };
                     let phase: UITouchPhase = msg_send![touch, phase];
                     let phase = match phase {
                         UITouchPhase::Began => TouchPhase::Started,
@@ -212,6 +244,7 @@ unsafe fn get_window_class() -> &'static Class {
                             device_id: RootDeviceId(DeviceId { uiscreen }),
                             id: touch_id,
                             location: (location.x as f64, location.y as f64).into(),
+                            force,
                             phase,
                         }),
                     });
@@ -241,7 +274,10 @@ unsafe fn get_window_class() -> &'static Class {
                 AppState::handle_nonuser_events(
                     std::iter::once(Event::WindowEvent {
                         window_id: RootWindowId(object.into()),
-                        event: WindowEvent::HiDpiFactorChanged(hidpi_factor as _),
+                        event: WindowEvent::ScaleFactorChanged {
+                            scale_factor: hidpi_factor as _,
+                            new_inner_size: size.to_physical(hidpi_factor as _),
+                        },
                     })
                     .chain(std::iter::once(Event::WindowEvent {
                         window_id: RootWindowId(object.into()),
@@ -366,7 +402,11 @@ pub unsafe fn create_window(
     if let Some(hidpi_factor) = platform_attributes.hidpi_factor {
         let () = msg_send![window, setContentScaleFactor: hidpi_factor as CGFloat];
     }
-    if let &Some(ref monitor) = &window_attributes.fullscreen {
+    if let Some(monitor) = window_attributes
+        .fullscreen
+        .as_ref()
+        .and_then(|fullscreen| fullscreen.monitor())
+    {
         let () = msg_send![window, setScreen:monitor.ui_screen()];
     }
 
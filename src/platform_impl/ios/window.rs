@@ -1,9 +1,10 @@
 use std::{
+    cell::RefCell,
     collections::VecDeque,
     ops::{Deref, DerefMut},
 };
 
-use objc::runtime::{Class, Object, NO, YES};
+use objc::runtime::{Class, Object, BOOL, NO, YES};
 
 use crate::{
     dpi::{self, LogicalPosition, LogicalSize},
@@ -15,9 +16,12 @@ use crate::{
         app_state::AppState,
         event_loop,
         ffi::{id, CGFloat, CGPoint, CGRect, CGSize, UIEdgeInsets, UIInterfaceOrientationMask},
-        monitor, view, EventLoopWindowTarget, MonitorHandle,
+        monitor, view, CustomCursor, EventLoopWindowTarget, MonitorHandle,
+    },
+    window::{
+        BadCursor, CursorIcon, FullscreenType, Rect, ResizeDirection, UserAttentionType,
+        WindowAttributes, WindowButtons, WindowPlacement,
     },
-    window::{CursorIcon, WindowAttributes},
 };
 
 pub struct Inner {
@@ -25,6 +29,7 @@ pub struct Inner {
     pub view_controller: id,
     pub view: id,
     supports_safe_area: bool,
+    fullscreen: RefCell<Option<FullscreenType>>,
 }
 
 impl Drop for Inner {
@@ -42,6 +47,15 @@ impl Inner {
         debug!("`Window::set_title` is ignored on iOS")
     }
 
+    pub fn get_title(&self) -> String {
+        String::new()
+    }
+
+    pub fn is_focused(&self) -> bool {
+        let is_key_window: BOOL = unsafe { msg_send![self.window, isKeyWindow] };
+        is_key_window == YES
+    }
+
     pub fn set_visible(&self, visible: bool) {
         match visible {
             true => unsafe {
@@ -53,12 +67,23 @@ impl Inner {
         }
     }
 
+    pub fn close(&self) {
+        warn!("`Window::close` is ignored on iOS")
+    }
+
+    pub fn is_visible(&self) -> Option<bool> {
+        let is_hidden: BOOL = unsafe { msg_send![self.window, isHidden] };
+        Some(is_hidden == NO)
+    }
+
     pub fn request_redraw(&self) {
         unsafe {
             let () = msg_send![self.view, setNeedsDisplay];
         }
     }
 
+    pub fn pre_present_notify(&self) {}
+
     pub fn inner_position(&self) -> Result<LogicalPosition, NotSupportedError> {
         unsafe {
             let safe_area = self.safe_area_screen_space();
@@ -114,7 +139,7 @@ impl Inner {
         }
     }
 
-    pub fn set_inner_size(&self, _size: LogicalSize) {
+    pub fn set_inner_size(&self, _size: LogicalSize) -> Option<LogicalSize> {
         unimplemented!("not clear what `Window::set_inner_size` means on iOS");
     }
 
@@ -126,6 +151,14 @@ impl Inner {
         warn!("`Window::set_max_inner_size` is ignored on iOS")
     }
 
+    pub fn get_placement(&self) -> Option<WindowPlacement> {
+        None
+    }
+
+    pub fn set_placement(&self, _placement: &WindowPlacement) {
+        warn!("`Window::set_placement` is ignored on iOS")
+    }
+
     pub fn set_resizable(&self, _resizable: bool) {
         warn!("`Window::set_resizable` is ignored on iOS")
     }
@@ -141,10 +174,49 @@ impl Inner {
         debug!("`Window::set_cursor_icon` ignored on iOS")
     }
 
+    pub fn create_custom_cursor(
+        &self,
+        _rgba: &[u8],
+        _width: u32,
+        _height: u32,
+        _hotspot_x: u32,
+        _hotspot_y: u32,
+    ) -> Result<CustomCursor, BadCursor> {
+        Err(BadCursor::NotSupported)
+    }
+
+    pub fn set_custom_cursor(&self, _cursor: &CustomCursor) {
+        debug!("`Window::set_custom_cursor` ignored on iOS")
+    }
+
     pub fn set_cursor_position(&self, _position: LogicalPosition) -> Result<(), ExternalError> {
         Err(ExternalError::NotSupported(NotSupportedError::new()))
     }
 
+    pub fn request_user_attention(&self, _request_type: Option<UserAttentionType>) {
+        warn!("`Window::request_user_attention` is ignored on iOS")
+    }
+
+    pub fn drag_window(&self) -> Result<(), ExternalError> {
+        Err(ExternalError::NotSupported(NotSupportedError::new()))
+    }
+
+    pub fn drag_resize_window(&self, _direction: ResizeDirection) -> Result<(), ExternalError> {
+        Err(ExternalError::NotSupported(NotSupportedError::new()))
+    }
+
+    pub fn reset_dead_keys(&self) {
+        warn!("`Window::reset_dead_keys` is ignored on iOS")
+    }
+
+    pub fn set_enabled(&self, _enabled: bool) {
+        warn!("`Window::set_enabled` is ignored on iOS")
+    }
+
+    pub fn show_window_menu(&self, _position: LogicalPosition) {
+        warn!("`Window::show_window_menu` is ignored on iOS")
+    }
+
     pub fn set_cursor_grab(&self, _grab: bool) -> Result<(), ExternalError> {
         Err(ExternalError::NotSupported(NotSupportedError::new()))
     }
@@ -157,12 +229,25 @@ impl Inner {
         warn!("`Window::set_maximized` is ignored on iOS")
     }
 
-    pub fn set_fullscreen(&self, monitor: Option<RootMonitorHandle>) {
+    pub fn get_maximized(&self) -> bool {
+        false
+    }
+
+    pub fn set_fullscreen(&self, fullscreen: Option<FullscreenType>) {
         unsafe {
-            match monitor {
-                Some(monitor) => {
-                    let uiscreen = monitor.ui_screen() as id;
+            match fullscreen {
+                Some(fullscreen) => {
                     let current: id = msg_send![self.window, screen];
+                    let uiscreen = match fullscreen.monitor() {
+                        Some(monitor) => monitor.ui_screen() as id,
+                        None => current,
+                    };
+                    *self.fullscreen.borrow_mut() = Some(match fullscreen {
+                        FullscreenType::Borderless(None) => {
+                            FullscreenType::Borderless(self.current_monitor())
+                        },
+                        fullscreen => fullscreen,
+                    });
                     let bounds: CGRect = msg_send![uiscreen, bounds];
 
                     // this is pretty slow on iOS, so avoid doing it if we can
@@ -176,24 +261,8 @@ impl Inner {
         }
     }
 
-    pub fn fullscreen(&self) -> Option<RootMonitorHandle> {
-        unsafe {
-            let monitor = self.current_monitor();
-            let uiscreen = monitor.inner.ui_screen();
-            let screen_space_bounds = self.screen_frame();
-            let screen_bounds: CGRect = msg_send![uiscreen, bounds];
-
-            // TODO: track fullscreen instead of relying on brittle float comparisons
-            if screen_space_bounds.origin.x == screen_bounds.origin.x
-                && screen_space_bounds.origin.y == screen_bounds.origin.y
-                && screen_space_bounds.size.width == screen_bounds.size.width
-                && screen_space_bounds.size.height == screen_bounds.size.height
-            {
-                Some(monitor)
-            } else {
-                None
-            }
-        }
+    pub fn fullscreen(&self) -> Option<FullscreenType> {
+        self.fullscreen.borrow().clone()
     }
 
     pub fn set_decorations(&self, decorations: bool) {
@@ -206,10 +275,44 @@ impl Inner {
         }
     }
 
+    pub fn get_resizable(&self) -> bool {
+        false
+    }
+
+    pub fn get_decorations(&self) -> bool {
+        let status_bar_hidden: BOOL =
+            unsafe { msg_send![self.view_controller, prefersStatusBarHidden] };
+        status_bar_hidden == NO
+    }
+
     pub fn set_always_on_top(&self, _always_on_top: bool) {
         warn!("`Window::set_always_on_top` is ignored on iOS")
     }
 
+    pub fn set_skip_taskbar(&self, _skip: bool) {
+        warn!("`Window::set_skip_taskbar` is ignored on iOS")
+    }
+
+    pub fn set_enabled_buttons(&self, _buttons: WindowButtons) {
+        warn!("`Window::set_enabled_buttons` is ignored on iOS")
+    }
+
+    pub fn set_content_protected(&self, _protected: bool) {
+        warn!("`Window::set_content_protected` is ignored on iOS")
+    }
+
+    pub fn set_opacity(&self, _opacity: f32) {
+        warn!("`Window::set_opacity` is ignored on iOS")
+    }
+
+    pub fn set_shape(&self, _shape: Option<&[Rect]>) -> Result<(), ExternalError> {
+        Err(ExternalError::NotSupported(NotSupportedError::new()))
+    }
+
+    pub fn set_cursor_hittest(&self, _hittest: bool) -> Result<(), ExternalError> {
+        Err(ExternalError::NotSupported(NotSupportedError::new()))
+    }
+
     pub fn set_window_icon(&self, _icon: Option<Icon>) {
         warn!("`Window::set_window_icon` is ignored on iOS")
     }
@@ -218,12 +321,16 @@ impl Inner {
         warn!("`Window::set_ime_position` is ignored on iOS")
     }
 
-    pub fn current_monitor(&self) -> RootMonitorHandle {
+    pub fn set_ime_allowed(&self, _allowed: bool) {
+        warn!("`Window::set_ime_allowed` is ignored on iOS")
+    }
+
+    pub fn current_monitor(&self) -> Option<RootMonitorHandle> {
         unsafe {
             let uiscreen: id = msg_send![self.window, screen];
-            RootMonitorHandle {
+            Some(RootMonitorHandle {
                 inner: MonitorHandle::retained_new(uiscreen),
-            }
+            })
         }
     }
 
@@ -290,18 +397,22 @@ impl Window {
         if window_attributes.always_on_top {
             warn!("`WindowAttributes::always_on_top` is unsupported on iOS");
         }
+        if window_attributes.skip_taskbar {
+            warn!("`WindowAttributes::skip_taskbar` is unsupported on iOS");
+        }
         // TODO: transparency, visible
 
         unsafe {
             let screen = window_attributes
                 .fullscreen
                 .as_ref()
+                .and_then(|fullscreen| fullscreen.monitor())
                 .map(|screen| screen.ui_screen() as _)
                 .unwrap_or_else(|| monitor::main_uiscreen().ui_screen());
             let screen_bounds: CGRect = msg_send![screen, bounds];
 
             let frame = match window_attributes.inner_size {
-                Some(dim) => {
+                Some(dim) if window_attributes.fullscreen.is_none() => {
                     CGRect {
                         origin: screen_bounds.origin,
                         size: CGSize {
@@ -310,7 +421,7 @@ impl Window {
                         },
                     }
                 },
-                None => screen_bounds,
+                _ => screen_bounds,
             };
 
             let view = view::create_view(&window_attributes, &platform_attributes, frame.clone());
@@ -324,6 +435,16 @@ impl Window {
             );
 
             let supports_safe_area = event_loop.capabilities().supports_safe_area;
+            let fullscreen = window_attributes.fullscreen.map(|fullscreen| {
+                match fullscreen {
+                    FullscreenType::Borderless(None) => {
+                        FullscreenType::Borderless(Some(RootMonitorHandle {
+                            inner: monitor::main_uiscreen(),
+                        }))
+                    },
+                    fullscreen => fullscreen,
+                }
+            });
 
             let result = Window {
                 inner: Inner {
@@ -331,6 +452,7 @@ impl Window {
                     view_controller,
                     view,
                     supports_safe_area,
+                    fullscreen: RefCell::new(fullscreen),
                 },
             };
             AppState::set_key_window(window);
@@ -10,11 +10,16 @@ use self::x11::{ffi::XVisualInfo, XConnection, XError};
 use crate::{
     dpi::{LogicalPosition, LogicalSize, PhysicalPosition, PhysicalSize},
     error::{ExternalError, NotSupportedError, OsError as RootOsError},
-    event::Event,
-    event_loop::{ControlFlow, EventLoopClosed, EventLoopWindowTarget as RootELW},
+    event::{AxisId, Event},
+    event_loop::{
+        ControlFlow, DeviceEventFilter, EventLoopClosed, EventLoopWindowTarget as RootELW,
+    },
     icon::Icon,
     monitor::{MonitorHandle as RootMonitorHandle, VideoMode},
-    window::{CursorIcon, WindowAttributes},
+    window::{
+        BadCursor, CursorIcon, FullscreenType, Rect, ResizeDirection, UserAttentionType,
+        WindowAttributes, WindowButtons, WindowPlacement,
+    },
 };
 
 mod dlopen;
@@ -54,10 +59,19 @@ pub enum OsError {
     XMisc(&'static str),
 }
 
+impl OsError {
+    pub fn raw_os_error(&self) -> Option<i32> {
+        match self {
+            OsError::XError(e) => Some(e.error_code as i32),
+            OsError::XMisc(_) => None,
+        }
+    }
+}
+
 impl fmt::Display for OsError {
     fn fmt(&self, formatter: &mut fmt::Formatter<'_>) -> Result<(), fmt::Error> {
         match self {
-            OsError::XError(e) => formatter.pad(&e.description),
+            OsError::XError(e) => formatter.pad(&e.to_string()),
             OsError::XMisc(e) => formatter.pad(e),
         }
     }
@@ -90,9 +104,25 @@ impl DeviceId {
     pub unsafe fn dummy() -> Self {
         DeviceId::Wayland(wayland::DeviceId::dummy())
     }
+
+    pub fn axis_label(&self, axis: AxisId) -> Option<String> {
+        match *self {
+            DeviceId::X(ref id) => {
+                let xconn = X11_BACKEND.lock().as_ref().ok()?.clone();
+                id.axis_label(&xconn, axis)
+            },
+            DeviceId::Wayland(_) => None,
+        }
+    }
 }
 
 #[derive(Debug, Clone)]
+pub enum CustomCursor {
+    X(x11::CustomCursor),
+    Wayland(wayland::CustomCursor),
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
 pub enum MonitorHandle {
     X(x11::MonitorHandle),
     Wayland(wayland::MonitorHandle),
@@ -181,6 +211,22 @@ impl Window {
         }
     }
 
+    #[inline]
+    pub fn get_title(&self) -> String {
+        match self {
+            Window::X(w) => w.get_title(),
+            Window::Wayland(w) => w.get_title(),
+        }
+    }
+
+    #[inline]
+    pub fn is_focused(&self) -> bool {
+        match self {
+            Window::X(w) => w.is_focused(),
+            Window::Wayland(w) => w.is_focused(),
+        }
+    }
+
     #[inline]
     pub fn set_visible(&self, visible: bool) {
         match self {
@@ -189,6 +235,22 @@ impl Window {
         }
     }
 
+    #[inline]
+    pub fn close(&self) {
+        match self {
+            Window::X(w) => w.close(),
+            Window::Wayland(w) => w.close(),
+        }
+    }
+
+    #[inline]
+    pub fn is_visible(&self) -> Option<bool> {
+        match self {
+            Window::X(w) => w.is_visible(),
+            Window::Wayland(_) => None,
+        }
+    }
+
     #[inline]
     pub fn outer_position(&self) -> Result<LogicalPosition, NotSupportedError> {
         match self {
@@ -230,7 +292,7 @@ impl Window {
     }
 
     #[inline]
-    pub fn set_inner_size(&self, size: LogicalSize) {
+    pub fn set_inner_size(&self, size: LogicalSize) -> Option<LogicalSize> {
         match self {
             &Window::X(ref w) => w.set_inner_size(size),
             &Window::Wayland(ref w) => w.set_inner_size(size),
@@ -253,6 +315,22 @@ impl Window {
         }
     }
 
+    #[inline]
+    pub fn get_placement(&self) -> Option<WindowPlacement> {
+        match self {
+            Window::X(w) => w.get_placement(),
+            Window::Wayland(_) => None,
+        }
+    }
+
+    #[inline]
+    pub fn set_placement(&self, placement: &WindowPlacement) {
+        match self {
+            Window::X(w) => w.set_placement(placement),
+            Window::Wayland(w) => w.set_placement(placement),
+        }
+    }
+
     #[inline]
     pub fn set_resizable(&self, resizable: bool) {
         match self {
@@ -269,6 +347,37 @@ impl Window {
         }
     }
 
+    #[inline]
+    pub fn create_custom_cursor(
+        &self,
+        rgba: &[u8],
+        width: u32,
+        height: u32,
+        hotspot_x: u32,
+        hotspot_y: u32,
+    ) -> Result<CustomCursor, BadCursor> {
+        match self {
+            Window::X(w) => {
+                w.create_custom_cursor(rgba, width, height, hotspot_x, hotspot_y)
+                    .map(CustomCursor::X)
+            },
+            Window::Wayland(w) => {
+                w.create_custom_cursor(rgba, width, height, hotspot_x, hotspot_y)
+                    .map(CustomCursor::Wayland)
+            },
+        }
+    }
+
+    #[inline]
+    pub fn set_custom_cursor(&self, cursor: &CustomCursor) {
+        match (self, cursor) {
+            (Window::X(w), CustomCursor::X(cursor)) => w.set_custom_cursor(cursor),
+            (Window::Wayland(w), CustomCursor::Wayland(cursor)) => w.set_custom_cursor(cursor),
+            // A cursor can only have been created by a window of the same backend.
+            _ => unreachable!(),
+        }
+    }
+
     #[inline]
     pub fn set_cursor_grab(&self, grab: bool) -> Result<(), ExternalError> {
         match self {
@@ -301,6 +410,62 @@ impl Window {
         }
     }
 
+    #[inline]
+    pub fn request_user_attention(&self, request_type: Option<UserAttentionType>) {
+        match self {
+            Window::X(w) => w.request_user_attention(request_type),
+            Window::Wayland(_) => (),
+        }
+    }
+
+    #[inline]
+    pub fn drag_window(&self) -> Result<(), ExternalError> {
+        match self {
+            Window::X(w) => w.drag_window(),
+            Window::Wayland(w) => w.drag_window(),
+        }
+    }
+
+    #[inline]
+    pub fn set_cursor_hittest(&self, hittest: bool) -> Result<(), ExternalError> {
+        match self {
+            Window::X(w) => w.set_cursor_hittest(hittest),
+            Window::Wayland(w) => w.set_cursor_hittest(hittest),
+        }
+    }
+
+    #[inline]
+    pub fn drag_resize_window(&self, direction: ResizeDirection) -> Result<(), ExternalError> {
+        match self {
+            Window::X(w) => w.drag_resize_window(direction),
+            Window::Wayland(w) => w.drag_resize_window(direction),
+        }
+    }
+
+    #[inline]
+    pub fn reset_dead_keys(&self) {
+        match self {
+            Window::X(w) => w.reset_dead_keys(),
+            Window::Wayland(_) => (),
+        }
+    }
+
+    #[inline]
+    pub fn set_enabled(&self, enabled: bool) {
+        match self {
+            Window::X(w) => w.set_enabled(enabled),
+            Window::Wayland(_) => (),
+        }
+    }
+
+    #[inline]
+    pub fn show_window_menu(&self, position: LogicalPosition) {
+        match self {
+            Window::X(w) => w.show_window_menu(position),
+            Window::Wayland(w) => w.show_window_menu(position),
+        }
+    }
+
     #[inline]
     pub fn set_maximized(&self, maximized: bool) {
         match self {
@@ -310,24 +475,26 @@ impl Window {
     }
 
     #[inline]
-    pub fn fullscreen(&self) -> Option<RootMonitorHandle> {
+    pub fn get_maximized(&self) -> bool {
+        match self {
+            Window::X(w) => w.get_maximized(),
+            Window::Wayland(w) => w.get_maximized(),
+        }
+    }
+
+    #[inline]
+    pub fn fullscreen(&self) -> Option<FullscreenType> {
         match self {
             &Window::X(ref w) => w.fullscreen(),
-            &Window::Wayland(ref w) => {
-                w.fullscreen().map(|monitor_id| {
-                    RootMonitorHandle {
-                        inner: MonitorHandle::Wayland(monitor_id),
-                    }
-                })
-            },
+            &Window::Wayland(ref w) => w.fullscreen(),
         }
     }
 
     #[inline]
-    pub fn set_fullscreen(&self, monitor: Option<RootMonitorHandle>) {
+    pub fn set_fullscreen(&self, fullscreen: Option<FullscreenType>) {
         match self {
-            &Window::X(ref w) => w.set_fullscreen(monitor),
-            &Window::Wayland(ref w) => w.set_fullscreen(monitor),
+            &Window::X(ref w) => w.set_fullscreen(fullscreen),
+            &Window::Wayland(ref w) => w.set_fullscreen(fullscreen),
         }
     }
 
@@ -339,6 +506,22 @@ impl Window {
         }
     }
 
+    #[inline]
+    pub fn get_resizable(&self) -> bool {
+        match self {
+            Window::X(w) => w.get_resizable(),
+            Window::Wayland(w) => w.get_resizable(),
+        }
+    }
+
+    #[inline]
+    pub fn get_decorations(&self) -> bool {
+        match self {
+            Window::X(w) => w.get_decorations(),
+            Window::Wayland(w) => w.get_decorations(),
+        }
+    }
+
     #[inline]
     pub fn set_always_on_top(&self, always_on_top: bool) {
         match self {
@@ -347,6 +530,40 @@ impl Window {
         }
     }
 
+    #[inline]
+    pub fn set_skip_taskbar(&self, skip: bool) {
+        match self {
+            &Window::X(ref w) => w.set_skip_taskbar(skip),
+            &Window::Wayland(_) => (),
+        }
+    }
+
+    #[inline]
+    pub fn set_enabled_buttons(&self, _buttons: WindowButtons) {
+        // N/A
+    }
+
+    #[inline]
+    pub fn set_content_protected(&self, _protected: bool) {
+        // N/A
+    }
+
+    #[inline]
+    pub fn set_opacity(&self, opacity: f32) {
+        match self {
+            Window::X(w) => w.set_opacity(opacity),
+            Window::Wayland(_) => (),
+        }
+    }
+
+    #[inline]
+    pub fn set_shape(&self, shape: Option<&[Rect]>) -> Result<(), ExternalError> {
+        match self {
+            Window::X(w) => w.set_shape(shape),
+            Window::Wayland(w) => w.set_shape(shape),
+        }
+    }
+
     #[inline]
     pub fn set_window_icon(&self, window_icon: Option<Icon>) {
         match self {
@@ -363,6 +580,14 @@ impl Window {
         }
     }
 
+    #[inline]
+    pub fn set_ime_allowed(&self, allowed: bool) {
+        match self {
+            Window::X(ref w) => w.set_ime_allowed(allowed),
+            Window::Wayland(_) => (),
+        }
+    }
+
     #[inline]
     pub fn request_redraw(&self) {
         match self {
@@ -372,21 +597,22 @@ impl Window {
     }
 
     #[inline]
-    pub fn current_monitor(&self) -> RootMonitorHandle {
+    pub fn pre_present_notify(&self) {
         match self {
-            &Window::X(ref window) => {
-                RootMonitorHandle {
-                    inner: MonitorHandle::X(window.current_monitor()),
-                }
-            },
-            &Window::Wayland(ref window) => {
-                RootMonitorHandle {
-                    inner: MonitorHandle::Wayland(window.current_monitor()),
-                }
-            },
+            Window::X(w) => w.pre_present_notify(),
+            Window::Wayland(w) => w.pre_present_notify(),
         }
     }
 
+    #[inline]
+    pub fn current_monitor(&self) -> Option<RootMonitorHandle> {
+        let inner = match self {
+            Window::X(window) => MonitorHandle::X(window.visible_monitor()?),
+            Window::Wayland(window) => MonitorHandle::Wayland(window.current_monitor()?),
+        };
+        Some(RootMonitorHandle { inner })
+    }
+
     #[inline]
     pub fn available_monitors(&self) -> VecDeque<MonitorHandle> {
         match self {
@@ -592,6 +818,24 @@ pub enum EventLoopWindowTarget<T> {
     X(x11::EventLoopWindowTarget<T>),
 }
 
+impl<T> EventLoopWindowTarget<T> {
+    #[inline]
+    pub fn set_device_event_filter(&self, filter: DeviceEventFilter) {
+        match *self {
+            EventLoopWindowTarget::Wayland(_) => (),
+            EventLoopWindowTarget::X(ref wt) => wt.set_device_event_filter(filter),
+        }
+    }
+
+    #[inline]
+    pub fn primary_monitor(&self) -> MonitorHandle {
+        match self {
+            EventLoopWindowTarget::Wayland(wt) => MonitorHandle::Wayland(wt.primary_monitor()),
+            EventLoopWindowTarget::X(wt) => MonitorHandle::X(wt.primary_monitor()),
+        }
+    }
+}
+
 fn sticky_exit_callback<T, F>(
     evt: Event<T>,
     target: &RootELW<T>,
@@ -0,0 +1,169 @@
+use std::{
+    io::Read,
+    path::PathBuf,
+    sync::{Arc, Mutex},
+    thread,
+};
+
+use percent_encoding::percent_decode;
+
+use crate::event::WindowEvent;
+
+use super::{window::WindowStore, WindowId};
+
+use smithay_client_toolkit::{
+    data_device::{DataDevice, DndAction, DndEvent},
+    reexports::client::{
+        protocol::{wl_data_device_manager::WlDataDeviceManager, wl_seat},
+        Display,
+    },
+};
+
+const URI_LIST_MIME_TYPE: &str = "text/uri-list";
+
+type EventSender = ::calloop::channel::Sender<(WindowEvent, WindowId)>;
+
+// Files being dragged over one of our windows. Their paths are read on another thread, since the
+// source client may take its time writing them.
+struct Transfer {
+    wid: WindowId,
+    // `None` until the whole `text/uri-list` has been read
+    paths: Option<Vec<PathBuf>>,
+    dropped: bool,
+    cancelled: bool,
+}
+
+pub(crate) fn implement_data_device(
+    manager: &WlDataDeviceManager,
+    seat: &wl_seat::WlSeat,
+    display: Arc<Display>,
+    sender: EventSender,
+    store: Arc<Mutex<WindowStore>>,
+) -> DataDevice {
+    // `wl_data_offer.finish` only exists since version 3
+    let can_finish = manager.as_ref().version() >= 3;
+    let mut hovered: Option<Arc<Mutex<Transfer>>> = None;
+    DataDevice::init_for_seat(manager, seat, move |evt| {
+        match evt {
+            DndEvent::Enter {
+                offer: Some(offer),
+                surface,
+                ..
+            } => {
+                let wid = match store.lock().unwrap().find_wid(&surface) {
+                    Some(wid) => wid,
+                    None => return,
+                };
+                let has_uri_list =
+                    offer.with_mime_types(|types| types.iter().any(|t| t == URI_LIST_MIME_TYPE));
+                let pipe = match has_uri_list {
+                    true => offer.receive(URI_LIST_MIME_TYPE.into()).ok(),
+                    false => None,
+                };
+                let mut pipe = match pipe {
+                    Some(pipe) => pipe,
+                    None => {
+                        offer.accept(None);
+                        return;
+                    },
+                };
+                // The list is only known once it has been read, so the offer is accepted even if
+                // it turns out not to contain any local files.
+                offer.accept(Some(URI_LIST_MIME_TYPE.into()));
+                offer.set_actions(DndAction::Copy, DndAction::Copy);
+                // The source client only sees the request once it has been sent, so reading
+                // before flushing would block forever.
+                let _ = display.flush();
+
+                let transfer = Arc::new(Mutex::new(Transfer {
+                    wid,
+                    paths: None,
+                    dropped: false,
+                    cancelled: false,
+                }));
+                hovered = Some(transfer.clone());
+                let sender = sender.clone();
+                thread::spawn(move || {
+                    let mut uri_list = String::new();
+                    let paths = match pipe.read_to_string(&mut uri_list) {
+                        Ok(_) => parse_uri_list(&uri_list),
+                        Err(_) => Vec::new(),
+                    };
+                    let mut transfer = transfer.lock().unwrap();
+                    if !transfer.cancelled {
+                        send_paths(&sender, &paths, wid, WindowEvent::HoveredFile);
+                        if transfer.dropped {
+                            send_paths(&sender, &paths, wid, WindowEvent::DroppedFile);
+                        }
+                    }
+                    transfer.paths = Some(paths);
+                });
+            },
+            DndEvent::Leave => {
+                if let Some(transfer) = hovered.take() {
+                    let mut transfer = transfer.lock().unwrap();
+                    match transfer.paths {
+                        Some(ref paths) if !paths.is_empty() => {
+                            let _ = sender.send((WindowEvent::HoveredFileCancelled, transfer.wid));
+                        },
+                        Some(_) => (),
+                        None => transfer.cancelled = true,
+                    }
+                }
+            },
+            DndEvent::Drop { offer: Some(offer) } => {
+                // Some compositors send a `leave` after the drop, which mustn't cancel anything
+                if let Some(transfer) = hovered.take() {
+                    let mut transfer = transfer.lock().unwrap();
+                    match transfer.paths {
+                        Some(ref paths) => {
+                            send_paths(&sender, paths, transfer.wid, WindowEvent::DroppedFile)
+                        },
+                        // The reading thread sends them once it's done
+                        None => transfer.dropped = true,
+                    }
+                    // Only copies are accepted, so the source has nothing left to do with the
+                    // data once the drop is finished, even if the list is still being read.
+                    if can_finish {
+                        offer.finish();
+                    }
+                }
+            },
+            _ => (),
+        }
+    })
+}
+
+// Everything goes through the channel, so that the events of the reading threads stay in order with
+// the ones sent from the dispatch.
+fn send_paths(
+    sender: &EventSender,
+    paths: &[PathBuf],
+    wid: WindowId,
+    event: fn(PathBuf) -> WindowEvent,
+) {
+    for path in paths {
+        let _ = sender.send((event(path.clone()), wid));
+    }
+}
+
+// Returns the local files in a `text/uri-list`.
+fn parse_uri_list(uri_list: &str) -> Vec<PathBuf> {
+    uri_list
+        .split("\r\n")
+        .filter(|uri| !uri.is_empty() && !uri.starts_with('#'))
+        .filter_map(|uri| {
+            // Only local files can be handed out, so the host has to be empty or `localhost`
+            let path = uri.strip_prefix("file://")?;
+            let path = path
+                .strip_prefix("localhost")
+                .filter(|path| path.starts_with('/'))
+                .unwrap_or(path);
+            if !path.starts_with('/') {
+                return None;
+            }
+            let path = percent_decode(path.as_bytes()).decode_utf8().ok()?;
+            Some(PathBuf::from(path.into_owned()))
+        })
+        .collect()
+}
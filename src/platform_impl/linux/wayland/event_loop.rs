@@ -2,6 +2,7 @@ use std::{
     cell::RefCell,
     collections::VecDeque,
     fmt,
+    hash::{Hash, Hasher},
     rc::Rc,
     sync::{Arc, Mutex},
     time::Instant,
@@ -18,16 +19,26 @@ use crate::{
 use super::{window::WindowStore, WindowId};
 
 use smithay_client_toolkit::{
+    data_device::DataDevice,
     output::OutputMgr,
-    reexports::client::{
-        protocol::{wl_keyboard, wl_output, wl_pointer, wl_registry, wl_seat, wl_touch},
-        ConnectError, Display, EventQueue, GlobalEvent,
+    reexports::{
+        client::{
+            protocol::{
+                wl_data_device_manager, wl_keyboard, wl_output, wl_pointer, wl_registry, wl_seat,
+                wl_touch,
+            },
+            ConnectError, Display, EventQueue, GlobalEvent,
+        },
+        protocols::unstable::relative_pointer::v1::client::{
+            zwp_relative_pointer_manager_v1::ZwpRelativePointerManagerV1,
+            zwp_relative_pointer_v1::ZwpRelativePointerV1,
+        },
     },
     Environment,
 };
 
 pub struct WindowEventsSink {
-    buffer: VecDeque<(crate::event::WindowEvent, crate::window::WindowId)>,
+    buffer: VecDeque<crate::event::Event<()>>,
 }
 
 impl WindowEventsSink {
@@ -38,21 +49,30 @@ impl WindowEventsSink {
     }
 
     pub fn send_event(&mut self, evt: crate::event::WindowEvent, wid: WindowId) {
-        self.buffer.push_back((
-            evt,
-            crate::window::WindowId(crate::platform_impl::WindowId::Wayland(wid)),
-        ));
+        self.buffer.push_back(crate::event::Event::WindowEvent {
+            event: evt,
+            window_id: crate::window::WindowId(crate::platform_impl::WindowId::Wayland(wid)),
+        });
+    }
+
+    pub fn send_device_event(
+        &mut self,
+        evt: crate::event::DeviceEvent,
+        device_id: crate::event::DeviceId,
+    ) {
+        self.buffer.push_back(crate::event::Event::DeviceEvent {
+            event: evt,
+            device_id,
+        });
     }
 
     fn empty_with<F, T>(&mut self, mut callback: F)
     where
         F: FnMut(crate::event::Event<T>),
     {
-        for (evt, wid) in self.buffer.drain(..) {
-            callback(crate::event::Event::WindowEvent {
-                event: evt,
-                window_id: wid,
-            })
+        for evt in self.buffer.drain(..) {
+            // There are no user events in the buffer.
+            callback(evt.map_nonuser_event().unwrap())
         }
     }
 }
@@ -132,6 +152,10 @@ impl<T: 'static> EventLoop<T> {
             store: store.clone(),
             seats: seats.clone(),
             kbd_sender,
+            display: display.clone(),
+            data_device_manager: None,
+            data_devices: Vec::new(),
+            relative_pointer_manager: Arc::new(Mutex::new(None)),
         };
 
         let env = Environment::from_display_with_cb(
@@ -146,6 +170,10 @@ impl<T: 'static> EventLoop<T> {
                     } => {
                         if interface == "wl_seat" {
                             seat_manager.add_seat(id, version, registry)
+                        } else if interface == "wl_data_device_manager" {
+                            seat_manager.set_data_device_manager(id, version, registry)
+                        } else if interface == "zwp_relative_pointer_manager_v1" {
+                            seat_manager.set_relative_pointer_manager(id, registry)
                         }
                     },
                     GlobalEvent::Removed { id, ref interface } => {
@@ -301,10 +329,13 @@ impl<T: 'static> EventLoop<T> {
                     );
                 },
                 ControlFlow::Wait => {
+                    let start = Instant::now();
+                    // blocks in `epoll` on the wayland socket and our channels until one of them
+                    // becomes readable
                     self.inner_loop.dispatch(None, &mut ()).unwrap();
                     callback(
                         crate::event::Event::NewEvents(crate::event::StartCause::WaitCancelled {
-                            start: Instant::now(),
+                            start,
                             requested_resume: None,
                         }),
                         &self.window_target,
@@ -376,6 +407,12 @@ impl<T: 'static> EventLoop<T> {
  * Private EventLoop Internals
  */
 
+impl<T> EventLoopWindowTarget<T> {
+    pub fn primary_monitor(&self) -> MonitorHandle {
+        primary_monitor(&self.env.outputs)
+    }
+}
+
 impl<T> EventLoop<T> {
     fn post_dispatch_triggers(&mut self) {
         let mut sink = self.sink.lock().unwrap();
@@ -397,6 +434,20 @@ impl<T> EventLoop<T> {
         // process pending resize/refresh
         window_target.store.lock().unwrap().for_each(
             |newsize, size, new_dpi, refresh, frame_refresh, closed, wid, frame| {
+                // This has to come before the `Resized` that usually accompanies it.
+                if let Some(dpi) = new_dpi {
+                    // The surface keeps its logical size, and the buffer scale follows the dpi.
+                    let (w, h) = newsize.unwrap_or(*size);
+                    let new_inner_size =
+                        crate::dpi::PhysicalSize::new(w as f64 * dpi as f64, h as f64 * dpi as f64);
+                    sink.send_event(
+                        crate::event::WindowEvent::ScaleFactorChanged {
+                            scale_factor: dpi as f64,
+                            new_inner_size,
+                        },
+                        wid,
+                    );
+                }
                 if let Some(frame) = frame {
                     if let Some((w, h)) = newsize {
                         frame.resize(w, h);
@@ -411,13 +462,8 @@ impl<T> EventLoop<T> {
                         }
                     }
                 }
-                if let Some(dpi) = new_dpi {
-                    sink.send_event(
-                        crate::event::WindowEvent::HiDpiFactorChanged(dpi as f64),
-                        wid,
-                    );
-                }
-                if refresh {
+                // The buffer has to be redrawn at the new scale, too.
+                if refresh || new_dpi.is_some() {
                     sink.send_event(crate::event::WindowEvent::RedrawRequested, wid);
                 }
                 if closed {
@@ -437,6 +483,12 @@ struct SeatManager {
     store: Arc<Mutex<WindowStore>>,
     seats: Arc<Mutex<Vec<(u32, wl_seat::WlSeat)>>>,
     kbd_sender: ::calloop::channel::Sender<(crate::event::WindowEvent, super::WindowId)>,
+    display: Arc<Display>,
+    // Drag and drop is handled per seat, but the manager may be advertised after the seats
+    data_device_manager: Option<wl_data_device_manager::WlDataDeviceManager>,
+    data_devices: Vec<(u32, DataDevice)>,
+    // Shared with the seats, which need it to create relative pointers for their pointers
+    relative_pointer_manager: Arc<Mutex<Option<ZwpRelativePointerManagerV1>>>,
 }
 
 impl SeatManager {
@@ -449,6 +501,8 @@ impl SeatManager {
             pointer: None,
             keyboard: None,
             touch: None,
+            relative_pointer: None,
+            relative_pointer_manager: self.relative_pointer_manager.clone(),
             kbd_sender: self.kbd_sender.clone(),
             modifiers_tracker: Arc::new(Mutex::new(ModifiersState::default())),
         };
@@ -458,13 +512,55 @@ impl SeatManager {
             })
             .unwrap();
         self.store.lock().unwrap().new_seat(&seat);
+        self.add_data_device(id, &seat);
         self.seats.lock().unwrap().push((id, seat));
     }
 
+    fn set_data_device_manager(
+        &mut self,
+        id: u32,
+        version: u32,
+        registry: wl_registry::WlRegistry,
+    ) {
+        use std::cmp::min;
+
+        let manager = registry
+            .bind(min(version, 3), id, |manager| manager.implement_dummy())
+            .unwrap();
+        self.data_device_manager = Some(manager);
+        let seats = self.seats.lock().unwrap().clone();
+        for (id, seat) in &seats {
+            self.add_data_device(*id, seat);
+        }
+    }
+
+    fn set_relative_pointer_manager(&mut self, id: u32, registry: wl_registry::WlRegistry) {
+        // Pointers only get their relative pointer when they're created, which happens once
+        // the seats have announced their capabilities, so this is normally bound in time.
+        let manager = registry
+            .bind(1, id, |manager| manager.implement_dummy())
+            .unwrap();
+        *self.relative_pointer_manager.lock().unwrap() = Some(manager);
+    }
+
+    fn add_data_device(&mut self, id: u32, seat: &wl_seat::WlSeat) {
+        if let Some(ref manager) = self.data_device_manager {
+            let data_device = super::dnd::implement_data_device(
+                manager,
+                seat,
+                self.display.clone(),
+                self.kbd_sender.clone(),
+                self.store.clone(),
+            );
+            self.data_devices.push((id, data_device));
+        }
+    }
+
     fn remove_seat(&mut self, id: u32) {
         let mut seats = self.seats.lock().unwrap();
         if let Some(idx) = seats.iter().position(|&(i, _)| i == id) {
             let (_, seat) = seats.swap_remove(idx);
+            self.data_devices.retain(|&(i, _)| i != id);
             if seat.as_ref().version() >= 5 {
                 seat.release();
             }
@@ -479,6 +575,8 @@ struct SeatData {
     pointer: Option<wl_pointer::WlPointer>,
     keyboard: Option<wl_keyboard::WlKeyboard>,
     touch: Option<wl_touch::WlTouch>,
+    relative_pointer: Option<ZwpRelativePointerV1>,
+    relative_pointer_manager: Arc<Mutex<Option<ZwpRelativePointerManagerV1>>>,
     modifiers_tracker: Arc<Mutex<ModifiersState>>,
 }
 
@@ -489,15 +587,27 @@ impl SeatData {
             wl_seat::Event::Capabilities { capabilities } => {
                 // create pointer if applicable
                 if capabilities.contains(wl_seat::Capability::Pointer) && self.pointer.is_none() {
-                    self.pointer = Some(super::pointer::implement_pointer(
+                    let pointer = super::pointer::implement_pointer(
                         &seat,
                         self.sink.clone(),
                         self.store.clone(),
                         self.modifiers_tracker.clone(),
-                    ))
+                    );
+                    if let Some(ref manager) = *self.relative_pointer_manager.lock().unwrap() {
+                        self.relative_pointer = Some(super::pointer::implement_relative_pointer(
+                            manager,
+                            &pointer,
+                            &seat,
+                            self.sink.clone(),
+                        ));
+                    }
+                    self.pointer = Some(pointer);
                 }
                 // destroy pointer if applicable
                 if !capabilities.contains(wl_seat::Capability::Pointer) {
+                    if let Some(relative_pointer) = self.relative_pointer.take() {
+                        relative_pointer.destroy();
+                    }
                     if let Some(pointer) = self.pointer.take() {
                         if pointer.as_ref().version() >= 3 {
                             pointer.release();
@@ -509,6 +619,7 @@ impl SeatData {
                     self.keyboard = Some(super::keyboard::init_keyboard(
                         &seat,
                         self.kbd_sender.clone(),
+                        self.store.clone(),
                         self.modifiers_tracker.clone(),
                     ))
                 }
@@ -544,6 +655,9 @@ impl SeatData {
 
 impl Drop for SeatData {
     fn drop(&mut self) {
+        if let Some(relative_pointer) = self.relative_pointer.take() {
+            relative_pointer.destroy();
+        }
         if let Some(pointer) = self.pointer.take() {
             if pointer.as_ref().version() >= 3 {
                 pointer.release();
@@ -580,6 +694,20 @@ impl Clone for MonitorHandle {
     }
 }
 
+impl PartialEq for MonitorHandle {
+    fn eq(&self, other: &Self) -> bool {
+        self.native_identifier() == other.native_identifier()
+    }
+}
+
+impl Eq for MonitorHandle {}
+
+impl Hash for MonitorHandle {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.native_identifier().hash(state);
+    }
+}
+
 impl fmt::Debug for MonitorHandle {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         #[derive(Debug)]
@@ -1,6 +1,6 @@
 use std::sync::{Arc, Mutex};
 
-use super::{make_wid, DeviceId};
+use super::{window::WindowStore, DeviceId};
 use smithay_client_toolkit::{
     keyboard::{
         self, map_keyboard_auto_with_repeat, Event as KbEvent, KeyRepeatEvent, KeyRepeatKind,
@@ -13,10 +13,15 @@ use crate::event::{ElementState, KeyboardInput, ModifiersState, VirtualKeyCode,
 pub fn init_keyboard(
     seat: &wl_seat::WlSeat,
     sink: ::calloop::channel::Sender<(crate::event::WindowEvent, super::WindowId)>,
+    store: Arc<Mutex<WindowStore>>,
     modifiers_tracker: Arc<Mutex<ModifiersState>>,
 ) -> wl_keyboard::WlKeyboard {
     // { variables to be captured by the closures
+    let device_id = crate::event::DeviceId(crate::platform_impl::DeviceId::Wayland(
+        DeviceId::from_seat(seat),
+    ));
     let target = Arc::new(Mutex::new(None));
+    let my_store = store.clone();
     let my_sink = sink.clone();
     let repeat_sink = sink.clone();
     let repeat_target = target.clone();
@@ -28,13 +33,25 @@ pub fn init_keyboard(
         move |evt: KbEvent<'_>, _| {
             match evt {
                 KbEvent::Enter { surface, .. } => {
-                    let wid = make_wid(&surface);
-                    my_sink.send((WindowEvent::Focused(true), wid)).unwrap();
-                    *target.lock().unwrap() = Some(wid);
+                    let wid = my_store.lock().unwrap().find_wid(&surface);
+                    if let Some(wid) = wid {
+                        my_sink.send((WindowEvent::Focused(true), wid)).unwrap();
+                    }
+                    *target.lock().unwrap() = wid;
                 },
                 KbEvent::Leave { surface, .. } => {
-                    let wid = make_wid(&surface);
-                    my_sink.send((WindowEvent::Focused(false), wid)).unwrap();
+                    let wid = my_store.lock().unwrap().find_wid(&surface);
+                    if let Some(wid) = wid {
+                        // The compositor sends the real modifiers again on the next `Enter`.
+                        let mut tracker = modifiers_tracker.lock().unwrap();
+                        if *tracker != ModifiersState::default() {
+                            *tracker = ModifiersState::default();
+                            my_sink
+                                .send((WindowEvent::ModifiersChanged(*tracker), wid))
+                                .unwrap();
+                        }
+                        my_sink.send((WindowEvent::Focused(false), wid)).unwrap();
+                    }
                     *target.lock().unwrap() = None;
                 },
                 KbEvent::Key {
@@ -54,9 +71,7 @@ pub fn init_keyboard(
                         my_sink
                             .send((
                                 WindowEvent::KeyboardInput {
-                                    device_id: crate::event::DeviceId(
-                                        crate::platform_impl::DeviceId::Wayland(DeviceId),
-                                    ),
+                                    device_id,
                                     input: KeyboardInput {
                                         state,
                                         scancode: rawkey,
@@ -83,7 +98,18 @@ pub fn init_keyboard(
                 KbEvent::RepeatInfo { .. } => { /* Handled by smithay client toolkit */ },
                 KbEvent::Modifiers {
                     modifiers: event_modifiers,
-                } => *modifiers_tracker.lock().unwrap() = event_modifiers.into(),
+                } => {
+                    let modifiers = event_modifiers.into();
+                    let mut tracker = modifiers_tracker.lock().unwrap();
+                    if *tracker != modifiers {
+                        *tracker = modifiers;
+                        if let Some(wid) = *target.lock().unwrap() {
+                            my_sink
+                                .send((WindowEvent::ModifiersChanged(modifiers), wid))
+                                .unwrap();
+                        }
+                    }
+                },
             }
         },
         move |repeat_event: KeyRepeatEvent, _| {
@@ -93,9 +119,7 @@ pub fn init_keyboard(
                 repeat_sink
                     .send((
                         WindowEvent::KeyboardInput {
-                            device_id: crate::event::DeviceId(
-                                crate::platform_impl::DeviceId::Wayland(DeviceId),
-                            ),
+                            device_id,
                             input: KeyboardInput {
                                 state,
                                 scancode: repeat_event.rawkey,
@@ -136,13 +160,17 @@ pub fn init_keyboard(
                     move |evt, _| {
                         match evt {
                             wl_keyboard::Event::Enter { surface, .. } => {
-                                let wid = make_wid(&surface);
-                                my_sink.send((WindowEvent::Focused(true), wid)).unwrap();
-                                target = Some(wid);
+                                let wid = store.lock().unwrap().find_wid(&surface);
+                                if let Some(wid) = wid {
+                                    my_sink.send((WindowEvent::Focused(true), wid)).unwrap();
+                                }
+                                target = wid;
                             },
                             wl_keyboard::Event::Leave { surface, .. } => {
-                                let wid = make_wid(&surface);
-                                my_sink.send((WindowEvent::Focused(false), wid)).unwrap();
+                                let wid = store.lock().unwrap().find_wid(&surface);
+                                if let Some(wid) = wid {
+                                    my_sink.send((WindowEvent::Focused(false), wid)).unwrap();
+                                }
                                 target = None;
                             },
                             wl_keyboard::Event::Key { key, state, .. } => {
@@ -155,11 +183,7 @@ pub fn init_keyboard(
                                     my_sink
                                         .send((
                                             WindowEvent::KeyboardInput {
-                                                device_id: crate::event::DeviceId(
-                                                    crate::platform_impl::DeviceId::Wayland(
-                                                        DeviceId,
-                                                    ),
-                                                ),
+                                                device_id,
                                                 input: KeyboardInput {
                                                     state,
                                                     scancode: key,
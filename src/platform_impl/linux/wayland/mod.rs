@@ -5,23 +5,32 @@ pub use self::{
     event_loop::{
         EventLoop, EventLoopProxy, EventLoopWindowTarget, MonitorHandle, WindowEventsSink,
     },
-    window::Window,
+    window::{CustomCursor, Window},
 };
 
-use smithay_client_toolkit::reexports::client::protocol::wl_surface;
+use std::sync::atomic::{AtomicUsize, Ordering};
 
+use smithay_client_toolkit::reexports::client::protocol::wl_seat;
+
+mod dnd;
 mod event_loop;
 mod keyboard;
 mod pointer;
 mod touch;
 mod window;
 
+// The id of the seat the device belongs to, since the compositor doesn't tell the devices of a
+// seat apart.
 #[derive(Debug, Copy, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
-pub struct DeviceId;
+pub struct DeviceId(u32);
 
 impl DeviceId {
     pub unsafe fn dummy() -> Self {
-        DeviceId
+        DeviceId(0)
+    }
+
+    pub(crate) fn from_seat(seat: &wl_seat::WlSeat) -> Self {
+        DeviceId(seat.as_ref().id())
     }
 }
 
@@ -34,7 +43,10 @@ impl WindowId {
     }
 }
 
-#[inline]
-fn make_wid(s: &wl_surface::WlSurface) -> WindowId {
-    WindowId(s.as_ref().c_ptr() as usize)
+// Ids are handed out in order instead of being derived from the surface, since a new surface can
+// end up at the address of one that was destroyed, and the id of a closed window must never match
+// a live one.
+fn new_wid() -> WindowId {
+    static NEXT_ID: AtomicUsize = AtomicUsize::new(1);
+    WindowId(NEXT_ID.fetch_add(1, Ordering::Relaxed))
 }
@@ -1,14 +1,21 @@
 use std::sync::{Arc, Mutex};
 
 use crate::event::{
-    ElementState, ModifiersState, MouseButton, MouseScrollDelta, TouchPhase, WindowEvent,
+    DeviceEvent, ElementState, ModifiersState, MouseButton, MouseScrollDelta, TouchPhase,
+    WindowEvent,
 };
 
 use super::{event_loop::WindowEventsSink, window::WindowStore, DeviceId};
 
-use smithay_client_toolkit::reexports::client::protocol::{
-    wl_pointer::{self, Event as PtrEvent, WlPointer},
-    wl_seat,
+use smithay_client_toolkit::reexports::{
+    client::protocol::{
+        wl_pointer::{self, Event as PtrEvent, WlPointer},
+        wl_seat,
+    },
+    protocols::unstable::relative_pointer::v1::client::{
+        zwp_relative_pointer_manager_v1::ZwpRelativePointerManagerV1,
+        zwp_relative_pointer_v1::{Event as RelEvent, ZwpRelativePointerV1},
+    },
 };
 
 pub fn implement_pointer(
@@ -17,6 +24,9 @@ pub fn implement_pointer(
     store: Arc<Mutex<WindowStore>>,
     modifiers_tracker: Arc<Mutex<ModifiersState>>,
 ) -> WlPointer {
+    let device_id = crate::event::DeviceId(crate::platform_impl::DeviceId::Wayland(
+        DeviceId::from_seat(seat),
+    ));
     let mut mouse_focus = None;
     let mut axis_buffer = None;
     let mut axis_discrete_buffer = None;
@@ -26,30 +36,22 @@ pub fn implement_pointer(
         pointer.implement_closure(
             move |evt, pointer| {
                 let mut sink = sink.lock().unwrap();
-                let store = store.lock().unwrap();
+                let mut store = store.lock().unwrap();
                 match evt {
                     PtrEvent::Enter {
+                        serial,
                         surface,
                         surface_x,
                         surface_y,
-                        ..
                     } => {
+                        store.pointer_entered(&pointer, &surface, serial);
                         let wid = store.find_wid(&surface);
                         if let Some(wid) = wid {
                             mouse_focus = Some(wid);
-                            sink.send_event(
-                                WindowEvent::CursorEntered {
-                                    device_id: crate::event::DeviceId(
-                                        crate::platform_impl::DeviceId::Wayland(DeviceId),
-                                    ),
-                                },
-                                wid,
-                            );
+                            sink.send_event(WindowEvent::CursorEntered { device_id }, wid);
                             sink.send_event(
                                 WindowEvent::CursorMoved {
-                                    device_id: crate::event::DeviceId(
-                                        crate::platform_impl::DeviceId::Wayland(DeviceId),
-                                    ),
+                                    device_id,
                                     position: (surface_x, surface_y).into(),
                                     modifiers: modifiers_tracker.lock().unwrap().clone(),
                                 },
@@ -59,16 +61,10 @@ pub fn implement_pointer(
                     },
                     PtrEvent::Leave { surface, .. } => {
                         mouse_focus = None;
+                        store.pointer_left(&pointer);
                         let wid = store.find_wid(&surface);
                         if let Some(wid) = wid {
-                            sink.send_event(
-                                WindowEvent::CursorLeft {
-                                    device_id: crate::event::DeviceId(
-                                        crate::platform_impl::DeviceId::Wayland(DeviceId),
-                                    ),
-                                },
-                                wid,
-                            );
+                            sink.send_event(WindowEvent::CursorLeft { device_id }, wid);
                         }
                     },
                     PtrEvent::Motion {
@@ -79,9 +75,7 @@ pub fn implement_pointer(
                         if let Some(wid) = mouse_focus {
                             sink.send_event(
                                 WindowEvent::CursorMoved {
-                                    device_id: crate::event::DeviceId(
-                                        crate::platform_impl::DeviceId::Wayland(DeviceId),
-                                    ),
+                                    device_id,
                                     position: (surface_x, surface_y).into(),
                                     modifiers: modifiers_tracker.lock().unwrap().clone(),
                                 },
@@ -105,9 +99,7 @@ pub fn implement_pointer(
                             };
                             sink.send_event(
                                 WindowEvent::MouseInput {
-                                    device_id: crate::event::DeviceId(
-                                        crate::platform_impl::DeviceId::Wayland(DeviceId),
-                                    ),
+                                    device_id,
                                     state,
                                     button,
                                     modifiers: modifiers_tracker.lock().unwrap().clone(),
@@ -129,9 +121,7 @@ pub fn implement_pointer(
                                 }
                                 sink.send_event(
                                     WindowEvent::MouseWheel {
-                                        device_id: crate::event::DeviceId(
-                                            crate::platform_impl::DeviceId::Wayland(DeviceId),
-                                        ),
+                                        device_id,
                                         delta: MouseScrollDelta::PixelDelta(
                                             (x as f64, y as f64).into(),
                                         ),
@@ -163,9 +153,7 @@ pub fn implement_pointer(
                             if let Some((x, y)) = axis_discrete_buffer {
                                 sink.send_event(
                                     WindowEvent::MouseWheel {
-                                        device_id: crate::event::DeviceId(
-                                            crate::platform_impl::DeviceId::Wayland(DeviceId),
-                                        ),
+                                        device_id,
                                         delta: MouseScrollDelta::LineDelta(x as f32, y as f32),
                                         phase: axis_state,
                                         modifiers: modifiers_tracker.lock().unwrap().clone(),
@@ -175,9 +163,7 @@ pub fn implement_pointer(
                             } else if let Some((x, y)) = axis_buffer {
                                 sink.send_event(
                                     WindowEvent::MouseWheel {
-                                        device_id: crate::event::DeviceId(
-                                            crate::platform_impl::DeviceId::Wayland(DeviceId),
-                                        ),
+                                        device_id,
                                         delta: MouseScrollDelta::PixelDelta(
                                             (x as f64, y as f64).into(),
                                         ),
@@ -215,3 +201,36 @@ pub fn implement_pointer(
     })
     .unwrap()
 }
+
+pub fn implement_relative_pointer(
+    manager: &ZwpRelativePointerManagerV1,
+    pointer: &WlPointer,
+    seat: &wl_seat::WlSeat,
+    sink: Arc<Mutex<WindowEventsSink>>,
+) -> ZwpRelativePointerV1 {
+    let device_id = crate::event::DeviceId(crate::platform_impl::DeviceId::Wayland(
+        DeviceId::from_seat(seat),
+    ));
+    manager
+        .get_relative_pointer(pointer, |relative_pointer| {
+            relative_pointer.implement_closure(
+                move |evt, _| {
+                    if let RelEvent::RelativeMotion {
+                        dx_unaccel,
+                        dy_unaccel,
+                        ..
+                    } = evt
+                    {
+                        sink.lock().unwrap().send_device_event(
+                            DeviceEvent::MouseMotion {
+                                delta: (dx_unaccel, dy_unaccel),
+                            },
+                            device_id,
+                        );
+                    }
+                },
+                (),
+            )
+        })
+        .unwrap()
+}
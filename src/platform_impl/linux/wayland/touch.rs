@@ -20,6 +20,9 @@ pub(crate) fn implement_touch(
     sink: Arc<Mutex<WindowEventsSink>>,
     store: Arc<Mutex<WindowStore>>,
 ) -> WlTouch {
+    let device_id = crate::event::DeviceId(crate::platform_impl::DeviceId::Wayland(
+        DeviceId::from_seat(seat),
+    ));
     let mut pending_ids = Vec::new();
     seat.get_touch(|touch| {
         touch.implement_closure(
@@ -34,11 +37,10 @@ pub(crate) fn implement_touch(
                         if let Some(wid) = wid {
                             sink.send_event(
                                 WindowEvent::Touch(crate::event::Touch {
-                                    device_id: crate::event::DeviceId(
-                                        crate::platform_impl::DeviceId::Wayland(DeviceId),
-                                    ),
+                                    device_id,
                                     phase: TouchPhase::Started,
                                     location: (x, y).into(),
+                                    force: None,
                                     id: id as u64,
                                 }),
                                 wid,
@@ -56,11 +58,10 @@ pub(crate) fn implement_touch(
                             let pt = pending_ids.remove(idx);
                             sink.send_event(
                                 WindowEvent::Touch(crate::event::Touch {
-                                    device_id: crate::event::DeviceId(
-                                        crate::platform_impl::DeviceId::Wayland(DeviceId),
-                                    ),
+                                    device_id,
                                     phase: TouchPhase::Ended,
                                     location: pt.location.into(),
+                                    force: None,
                                     id: id as u64,
                                 }),
                                 pt.wid,
@@ -73,11 +74,10 @@ pub(crate) fn implement_touch(
                             pt.location = (x, y);
                             sink.send_event(
                                 WindowEvent::Touch(crate::event::Touch {
-                                    device_id: crate::event::DeviceId(
-                                        crate::platform_impl::DeviceId::Wayland(DeviceId),
-                                    ),
+                                    device_id,
                                     phase: TouchPhase::Moved,
                                     location: (x, y).into(),
+                                    force: None,
                                     id: id as u64,
                                 }),
                                 pt.wid,
@@ -89,11 +89,10 @@ pub(crate) fn implement_touch(
                         for pt in pending_ids.drain(..) {
                             sink.send_event(
                                 WindowEvent::Touch(crate::event::Touch {
-                                    device_id: crate::event::DeviceId(
-                                        crate::platform_impl::DeviceId::Wayland(DeviceId),
-                                    ),
+                                    device_id,
                                     phase: TouchPhase::Cancelled,
                                     location: pt.location.into(),
+                                    force: None,
                                     id: pt.id as u64,
                                 }),
                                 pt.wid,
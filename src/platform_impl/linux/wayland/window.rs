@@ -1,5 +1,6 @@
 use std::{
     collections::VecDeque,
+    env, fmt,
     io::{Seek, SeekFrom, Write},
     sync::{Arc, Mutex, Weak},
 };
@@ -12,34 +13,55 @@ use crate::{
         MonitorHandle as PlatformMonitorHandle,
         PlatformSpecificWindowBuilderAttributes as PlAttributes,
     },
-    window::{CursorIcon, WindowAttributes},
+    window::{
+        clamp_inner_size, BadCursor, CursorIcon, FullscreenType, Rect, ResizeDirection,
+        WindowAttributes, WindowPlacement,
+    },
 };
 
 use smithay_client_toolkit::{
     output::OutputMgr,
     reexports::client::{
-        protocol::{wl_seat, wl_shm, wl_subsurface, wl_surface},
+        cursor::{self as wayland_cursor, CursorTheme},
+        protocol::{
+            wl_buffer, wl_compositor, wl_output, wl_pointer, wl_seat, wl_shm, wl_subsurface,
+            wl_surface,
+        },
         Display, NewProxy,
     },
     surface::{get_dpi_factor, get_outputs},
+    utils::MemPool,
     window::{ConceptFrame, Event as WEvent, State as WState, Theme, Window as SWindow},
 };
 
-use super::{make_wid, EventLoopWindowTarget, MonitorHandle, WindowId};
+use super::{new_wid, EventLoopWindowTarget, MonitorHandle, WindowId};
 use crate::platform_impl::platform::wayland::event_loop::{available_monitors, primary_monitor};
 
 pub struct Window {
-    _bg_surface: wl_surface::WlSurface,
+    id: WindowId,
+    bg_surface: wl_surface::WlSurface,
     user_surface: wl_surface::WlSurface,
     _user_subsurface: wl_subsurface::WlSubsurface,
-    frame: Arc<Mutex<SWindow<ConceptFrame>>>,
+    // `None` once the window has been closed.
+    frame: Arc<Mutex<Option<SWindow<ConceptFrame>>>>,
     outputs: OutputMgr, // Access to info for all monitors
     size: Arc<Mutex<(u32, u32)>>,
+    // Kept for clamping the size passed to `set_inner_size`.
+    size_limits: Arc<Mutex<(Option<LogicalSize>, Option<LogicalSize>)>>,
     kill_switch: (Arc<Mutex<bool>>, Arc<Mutex<bool>>),
     display: Arc<Display>,
     need_frame_refresh: Arc<Mutex<bool>>,
     need_refresh: Arc<Mutex<bool>>,
-    fullscreen: Arc<Mutex<bool>>,
+    fullscreen: Arc<Mutex<Option<FullscreenType>>>,
+    title: Mutex<String>,
+    decorated: Mutex<bool>,
+    resizable: Mutex<bool>,
+    // As last configured by the compositor.
+    maximized: Arc<Mutex<bool>>,
+    activated: Arc<Mutex<bool>>,
+    store: Arc<Mutex<WindowStore>>,
+    compositor: wl_compositor::WlCompositor,
+    shm: wl_shm::WlShm,
 }
 
 impl Window {
@@ -51,7 +73,9 @@ impl Window {
         let (width, height) = attributes.inner_size.map(Into::into).unwrap_or((800, 600));
         // Create the window
         let size = Arc::new(Mutex::new((width, height)));
-        let fullscreen = Arc::new(Mutex::new(false));
+        let fullscreen = Arc::new(Mutex::new(attributes.fullscreen.clone()));
+        let maximized = Arc::new(Mutex::new(false));
+        let activated = Arc::new(Mutex::new(false));
 
         let window_store = evlp.store.clone();
         let bg_surface = evlp
@@ -91,12 +115,29 @@ impl Window {
                     WEvent::Configure { new_size, states } => {
                         let mut store = window_store.lock().unwrap();
                         let is_fullscreen = states.contains(&WState::Fullscreen);
+                        let is_maximized = states.contains(&WState::Maximized);
+                        let is_activated = states.contains(&WState::Activated);
 
                         for window in &mut store.windows {
                             if window.surface.as_ref().equals(&my_surface.as_ref()) {
                                 window.newsize = new_size;
+                                *window.maximized.lock().unwrap() = is_maximized;
+                                *window.activated.lock().unwrap() = is_activated;
                                 *(window.need_refresh.lock().unwrap()) = true;
-                                *(window.fullscreen.lock().unwrap()) = is_fullscreen;
+                                // Requests are tracked as soon as they're made, so only the
+                                // compositor's own changes have to be picked up here.
+                                if is_fullscreen != window.configured_fullscreen {
+                                    window.configured_fullscreen = is_fullscreen;
+                                    let mut fullscreen = window.fullscreen.lock().unwrap();
+                                    *fullscreen = match is_fullscreen {
+                                        true => {
+                                            fullscreen
+                                                .take()
+                                                .or(Some(FullscreenType::Borderless(None)))
+                                        },
+                                        false => None,
+                                    };
+                                }
                                 *(window.need_frame_refresh.lock().unwrap()) = true;
                                 if !window.configured {
                                     // this is our first configure event, display ourselves !
@@ -131,7 +172,16 @@ impl Window {
         )
         .unwrap();
 
-        if let Some(app_id) = pl_attribs.app_id {
+        // Default to the name of the binary, like `WM_CLASS` does on X11
+        let app_id = pl_attribs.app_id.or_else(|| {
+            env::current_exe()
+                .ok()
+                .as_ref()
+                .and_then(|path| path.file_name())
+                .and_then(|bin_name| bin_name.to_str())
+                .map(|bin_name| bin_name.to_owned())
+        });
+        if let Some(app_id) = app_id {
             frame.set_app_id(app_id);
         }
 
@@ -140,11 +190,8 @@ impl Window {
         }
 
         // Check for fullscreen requirements
-        if let Some(RootMonitorHandle {
-            inner: PlatformMonitorHandle::Wayland(ref monitor_id),
-        }) = attributes.fullscreen
-        {
-            frame.set_fullscreen(Some(&monitor_id.proxy));
+        if let Some(ref fullscreen) = attributes.fullscreen {
+            frame.set_fullscreen(fullscreen_output(fullscreen));
         } else if attributes.maximized {
             frame.set_maximized();
         }
@@ -155,7 +202,7 @@ impl Window {
         frame.set_decorate(attributes.decorations);
 
         // set title
-        frame.set_title(attributes.title);
+        frame.set_title(attributes.title.clone());
 
         // min-max dimensions
         frame.set_min_size(attributes.min_inner_size.map(Into::into));
@@ -163,15 +210,19 @@ impl Window {
 
         let kill_switch = Arc::new(Mutex::new(false));
         let need_frame_refresh = Arc::new(Mutex::new(true));
-        let frame = Arc::new(Mutex::new(frame));
+        let frame = Arc::new(Mutex::new(Some(frame)));
         let need_refresh = Arc::new(Mutex::new(true));
 
+        let id = new_wid();
         evlp.store.lock().unwrap().windows.push(InternalWindow {
+            id,
             closed: false,
             newsize: None,
             size: size.clone(),
             need_refresh: need_refresh.clone(),
             fullscreen: fullscreen.clone(),
+            maximized: maximized.clone(),
+            activated: activated.clone(),
             need_frame_refresh: need_frame_refresh.clone(),
             surface: user_surface.clone(),
             kill_switch: kill_switch.clone(),
@@ -179,30 +230,51 @@ impl Window {
             current_dpi: 1,
             new_dpi: None,
             configured: false,
+            configured_fullscreen: false,
+            custom_cursor: None,
+            cursor_visible: true,
         });
 
         Ok(Window {
+            id,
             display: evlp.display.clone(),
-            _bg_surface: bg_surface,
+            bg_surface,
             user_surface,
             _user_subsurface: user_subsurface,
             frame,
             outputs: evlp.env.outputs.clone(),
             size,
+            size_limits: Arc::new(Mutex::new((
+                attributes.min_inner_size,
+                attributes.max_inner_size,
+            ))),
             kill_switch: (kill_switch, evlp.cleanup_needed.clone()),
             need_frame_refresh,
             need_refresh,
             fullscreen,
+            title: Mutex::new(attributes.title),
+            decorated: Mutex::new(attributes.decorations),
+            resizable: Mutex::new(attributes.resizable),
+            maximized,
+            activated,
+            store: evlp.store.clone(),
+            compositor: evlp.env.compositor.clone(),
+            shm: evlp.env.shm.clone(),
         })
     }
 
     #[inline]
     pub fn id(&self) -> WindowId {
-        make_wid(&self.user_surface)
+        self.id
     }
 
     pub fn set_title(&self, title: &str) {
-        self.frame.lock().unwrap().set_title(title.into());
+        self.with_frame(|frame| frame.set_title(title.into()));
+        *self.title.lock().unwrap() = title.into();
+    }
+
+    pub fn get_title(&self) -> String {
+        self.title.lock().unwrap().clone()
     }
 
     pub fn set_visible(&self, _visible: bool) {
@@ -232,6 +304,12 @@ impl Window {
         *self.need_refresh.lock().unwrap() = true;
     }
 
+    pub fn pre_present_notify(&self) {
+        // A frame callback requested before the commit lets the compositor pace the next frame
+        // to the output's refresh and treat the commit as a new frame when presenting it.
+        let _ = self.user_surface.frame(NewProxy::implement_dummy);
+    }
+
     #[inline]
     pub fn outer_size(&self) -> LogicalSize {
         let (w, h) = self.size.lock().unwrap().clone();
@@ -241,31 +319,61 @@ impl Window {
 
     #[inline]
     // NOTE: This will only resize the borders, the contents must be updated by the user
-    pub fn set_inner_size(&self, size: LogicalSize) {
+    pub fn set_inner_size(&self, size: LogicalSize) -> Option<LogicalSize> {
+        let available = {
+            let monitor = self
+                .current_monitor()
+                .unwrap_or_else(|| self.primary_monitor());
+            let (width, height): (u32, u32) = monitor.size().into();
+            // The size is unknown until the output has sent its current mode.
+            if width > 0 && height > 0 {
+                Some(LogicalSize::from_physical(
+                    (width, height),
+                    monitor.hidpi_factor() as f64,
+                ))
+            } else {
+                None
+            }
+        };
+        let (min_size, max_size) = *self.size_limits.lock().unwrap();
+        let size = clamp_inner_size(size, min_size, max_size, available);
         let (w, h) = size.into();
-        self.frame.lock().unwrap().resize(w, h);
-        *(self.size.lock().unwrap()) = (w, h);
+        {
+            let mut size_lock = self.size.lock().unwrap();
+            if *size_lock == (w, h) {
+                return Some(size);
+            }
+            *size_lock = (w, h);
+        }
+        // The frame is resized by the event loop, which also sends the `Resized` event, like it
+        // does for a size coming from the compositor.
+        self.store
+            .lock()
+            .unwrap()
+            .request_resize(&self.user_surface, (w, h));
+        Some(size)
     }
 
     #[inline]
     pub fn set_min_inner_size(&self, dimensions: Option<LogicalSize>) {
-        self.frame
-            .lock()
-            .unwrap()
-            .set_min_size(dimensions.map(Into::into));
+        self.size_limits.lock().unwrap().0 = dimensions;
+        self.with_frame(|frame| frame.set_min_size(dimensions.map(Into::into)));
     }
 
     #[inline]
     pub fn set_max_inner_size(&self, dimensions: Option<LogicalSize>) {
-        self.frame
-            .lock()
-            .unwrap()
-            .set_max_size(dimensions.map(Into::into));
+        self.size_limits.lock().unwrap().1 = dimensions;
+        self.with_frame(|frame| frame.set_max_size(dimensions.map(Into::into)));
     }
 
     #[inline]
     pub fn set_resizable(&self, resizable: bool) {
-        self.frame.lock().unwrap().set_resizable(resizable);
+        self.with_frame(|frame| frame.set_resizable(resizable));
+        *self.resizable.lock().unwrap() = resizable;
+    }
+
+    pub fn get_resizable(&self) -> bool {
+        *self.resizable.lock().unwrap()
     }
 
     #[inline]
@@ -274,52 +382,151 @@ impl Window {
     }
 
     pub fn set_decorations(&self, decorate: bool) {
-        self.frame.lock().unwrap().set_decorate(decorate);
+        self.with_frame(|frame| frame.set_decorate(decorate));
+        *self.decorated.lock().unwrap() = decorate;
         *(self.need_frame_refresh.lock().unwrap()) = true;
     }
 
+    pub fn get_decorations(&self) -> bool {
+        *self.decorated.lock().unwrap()
+    }
+
+    pub fn set_placement(&self, placement: &WindowPlacement) {
+        // Windows can't choose where they go on Wayland.
+        self.set_inner_size(placement.inner_size);
+        self.set_maximized(placement.maximized);
+    }
+
+    #[inline]
     pub fn set_maximized(&self, maximized: bool) {
-        if maximized {
-            self.frame.lock().unwrap().set_maximized();
-        } else {
-            self.frame.lock().unwrap().unset_maximized();
-        }
+        self.with_frame(|frame| {
+            if maximized {
+                frame.set_maximized();
+            } else {
+                frame.unset_maximized();
+            }
+        });
     }
 
-    pub fn fullscreen(&self) -> Option<MonitorHandle> {
-        if *(self.fullscreen.lock().unwrap()) {
-            Some(self.current_monitor())
-        } else {
-            None
-        }
+    pub fn is_focused(&self) -> bool {
+        self.frame.lock().unwrap().is_some() && *self.activated.lock().unwrap()
     }
 
-    pub fn set_fullscreen(&self, monitor: Option<RootMonitorHandle>) {
-        if let Some(RootMonitorHandle {
-            inner: PlatformMonitorHandle::Wayland(ref monitor_id),
-        }) = monitor
-        {
-            self.frame
-                .lock()
-                .unwrap()
-                .set_fullscreen(Some(&monitor_id.proxy));
-        } else {
-            self.frame.lock().unwrap().unset_fullscreen();
+    pub fn get_maximized(&self) -> bool {
+        self.frame.lock().unwrap().is_some() && *self.maximized.lock().unwrap()
+    }
+
+    pub fn fullscreen(&self) -> Option<FullscreenType> {
+        match *self.fullscreen.lock().unwrap() {
+            // The compositor chose the output, so report wherever the window ended up.
+            Some(FullscreenType::Borderless(None)) => {
+                Some(FullscreenType::Borderless(self.current_monitor().map(
+                    |monitor| {
+                        RootMonitorHandle {
+                            inner: PlatformMonitorHandle::Wayland(monitor),
+                        }
+                    },
+                )))
+            },
+            ref fullscreen => fullscreen.clone(),
         }
     }
 
+    pub fn set_fullscreen(&self, fullscreen: Option<FullscreenType>) {
+        *self.fullscreen.lock().unwrap() = fullscreen.clone();
+        self.with_frame(|frame| {
+            if let Some(ref fullscreen) = fullscreen {
+                frame.set_fullscreen(fullscreen_output(fullscreen));
+            } else {
+                frame.unset_fullscreen();
+            }
+        });
+    }
+
     pub fn set_theme<T: Theme>(&self, theme: T) {
-        self.frame.lock().unwrap().set_theme(theme)
+        self.with_frame(|frame| frame.set_theme(theme));
     }
 
     #[inline]
     pub fn set_cursor_icon(&self, _cursor: CursorIcon) {
-        // TODO
+        // TODO: Named cursors aren't supported yet, so this merely replaces a custom cursor with
+        // the theme's default one.
+        self.store.lock().unwrap().set_custom_cursor(
+            &self.user_surface,
+            None,
+            &self.compositor,
+            &self.shm,
+        );
+    }
+
+    pub fn create_custom_cursor(
+        &self,
+        rgba: &[u8],
+        width: u32,
+        height: u32,
+        hotspot_x: u32,
+        hotspot_y: u32,
+    ) -> Result<CustomCursor, BadCursor> {
+        // `Argb8888` is a premultiplied format, stored in little-endian order.
+        let mut data = Vec::with_capacity(rgba.len());
+        for pixel in rgba.chunks(4) {
+            let alpha = pixel[3] as u32;
+            let premultiply = |channel: u8| (channel as u32 * alpha / 255) as u8;
+            data.extend_from_slice(&[
+                premultiply(pixel[2]),
+                premultiply(pixel[1]),
+                premultiply(pixel[0]),
+                pixel[3],
+            ]);
+        }
+
+        let mut pool = MemPool::new(&self.shm, || {}).map_err(|_| BadCursor::CreationFailed)?;
+        pool.resize(data.len())
+            .and_then(|()| pool.seek(SeekFrom::Start(0)))
+            .and_then(|_| pool.write_all(&data))
+            .and_then(|()| pool.flush())
+            .map_err(|_| BadCursor::CreationFailed)?;
+        let buffer = pool.buffer(
+            0,
+            width as i32,
+            height as i32,
+            4 * width as i32,
+            wl_shm::Format::Argb8888,
+        );
+
+        let surface = self
+            .compositor
+            .create_surface(NewProxy::implement_dummy)
+            .map_err(|()| BadCursor::CreationFailed)?;
+        surface.attach(Some(&buffer), 0, 0);
+        surface.commit();
+
+        Ok(CustomCursor {
+            inner: Arc::new(CursorSurface {
+                surface,
+                hotspot: (hotspot_x as i32, hotspot_y as i32),
+                buffer: Some((buffer, pool)),
+            }),
+        })
+    }
+
+    pub fn set_custom_cursor(&self, cursor: &CustomCursor) {
+        self.store.lock().unwrap().set_custom_cursor(
+            &self.user_surface,
+            Some(cursor.clone()),
+            &self.compositor,
+            &self.shm,
+        );
     }
 
     #[inline]
-    pub fn set_cursor_visible(&self, _visible: bool) {
-        // TODO: This isn't possible on Wayland yet
+    pub fn set_cursor_visible(&self, visible: bool) {
+        self.store.lock().unwrap().set_cursor_visible(
+            &self.user_surface,
+            visible,
+            &self.compositor,
+            &self.shm,
+        );
     }
 
     #[inline]
@@ -332,6 +539,69 @@ impl Window {
         Err(ExternalError::NotSupported(NotSupportedError::new()))
     }
 
+    #[inline]
+    pub fn set_shape(&self, _shape: Option<&[Rect]>) -> Result<(), ExternalError> {
+        Err(ExternalError::NotSupported(NotSupportedError::new()))
+    }
+
+    #[inline]
+    pub fn set_cursor_hittest(&self, hittest: bool) -> Result<(), ExternalError> {
+        // An empty input region lets all pointer events through, while no input region at all
+        // covers the whole surface again.
+        let region = match hittest {
+            true => None,
+            false => {
+                Some(
+                    self.compositor
+                        .create_region(NewProxy::implement_dummy)
+                        .unwrap(),
+                )
+            },
+        };
+        for surface in &[&self.user_surface, &self.bg_surface] {
+            surface.set_input_region(region.as_ref());
+            surface.commit();
+        }
+        if let Some(region) = region {
+            region.destroy();
+        }
+        Ok(())
+    }
+
+    #[inline]
+    pub fn drag_window(&self) -> Result<(), ExternalError> {
+        // SCTK only starts `xdg_toplevel.move` from its own decorations, and doesn't give us
+        // access to the shell surface to do it ourselves.
+        Err(ExternalError::NotSupported(NotSupportedError::new()))
+    }
+
+    #[inline]
+    pub fn drag_resize_window(&self, _direction: ResizeDirection) -> Result<(), ExternalError> {
+        // Likewise for `xdg_toplevel.resize`.
+        Err(ExternalError::NotSupported(NotSupportedError::new()))
+    }
+
+    #[inline]
+    pub fn show_window_menu(&self, _position: LogicalPosition) {
+        // SCTK doesn't give us access to the shell surface, so `xdg_toplevel.show_window_menu`
+        // can't be sent either.
+    }
+
+    pub fn close(&self) {
+        // Dropping the frame destroys the shell surface, while the event loop destroys the
+        // surface itself and sends `Destroyed` on its next iteration.
+        if self.frame.lock().unwrap().take().is_some() {
+            *(self.kill_switch.0.lock().unwrap()) = true;
+            *(self.kill_switch.1.lock().unwrap()) = true;
+        }
+    }
+
+    fn with_frame<F: FnOnce(&mut SWindow<ConceptFrame>)>(&self, f: F) {
+        if let Some(ref mut frame) = *self.frame.lock().unwrap() {
+            f(frame);
+        }
+    }
+
     pub fn display(&self) -> &Display {
         &*self.display
     }
@@ -340,12 +610,14 @@ impl Window {
         &self.user_surface
     }
 
-    pub fn current_monitor(&self) -> MonitorHandle {
-        let output = get_outputs(&self.user_surface).last().unwrap().clone();
-        MonitorHandle {
-            proxy: output,
-            mgr: self.outputs.clone(),
-        }
+    pub fn current_monitor(&self) -> Option<MonitorHandle> {
+        // The surface is on no output at all until it's first mapped.
+        get_outputs(&self.user_surface).last().map(|output| {
+            MonitorHandle {
+                proxy: output.clone(),
+                mgr: self.outputs.clone(),
+            }
+        })
     }
 
     pub fn available_monitors(&self) -> VecDeque<MonitorHandle> {
@@ -357,10 +629,58 @@ impl Window {
     }
 }
 
+/// The output to pass to `xdg_toplevel.set_fullscreen`. Wayland has no notion of exclusive
+/// fullscreen, so both kinds are treated the same, and `None` lets the compositor pick.
+fn fullscreen_output(fullscreen: &FullscreenType) -> Option<&wl_output::WlOutput> {
+    match fullscreen.monitor() {
+        Some(&RootMonitorHandle {
+            inner: PlatformMonitorHandle::Wayland(ref monitor_id),
+        }) => Some(&monitor_id.proxy),
+        _ => None,
+    }
+}
+
 impl Drop for Window {
     fn drop(&mut self) {
-        *(self.kill_switch.0.lock().unwrap()) = true;
-        *(self.kill_switch.1.lock().unwrap()) = true;
+        self.close();
+    }
+}
+
+/// A cursor image committed to its own surface, which can be handed to `wl_pointer.set_cursor`
+/// any number of times.
+#[derive(Clone)]
+pub struct CustomCursor {
+    inner: Arc<CursorSurface>,
+}
+
+struct CursorSurface {
+    surface: wl_surface::WlSurface,
+    hotspot: (i32, i32),
+    // `None` when the buffer belongs to a cursor theme.
+    buffer: Option<(wl_buffer::WlBuffer, MemPool)>,
+}
+
+impl CustomCursor {
+    fn apply(&self, pointer: &wl_pointer::WlPointer, serial: u32) {
+        let (hotspot_x, hotspot_y) = self.inner.hotspot;
+        pointer.set_cursor(serial, Some(&self.inner.surface), hotspot_x, hotspot_y);
+    }
+}
+
+impl fmt::Debug for CustomCursor {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("CustomCursor")
+            .field("hotspot", &self.inner.hotspot)
+            .finish()
+    }
+}
+
+impl Drop for CursorSurface {
+    fn drop(&mut self) {
+        self.surface.destroy();
+        if let Some((ref buffer, _)) = self.buffer {
+            buffer.destroy();
+        }
     }
 }
 
@@ -369,35 +689,185 @@ impl Drop for Window {
  */
 
 struct InternalWindow {
+    id: WindowId,
     surface: wl_surface::WlSurface,
     newsize: Option<(u32, u32)>,
     size: Arc<Mutex<(u32, u32)>>,
     need_refresh: Arc<Mutex<bool>>,
-    fullscreen: Arc<Mutex<bool>>,
+    fullscreen: Arc<Mutex<Option<FullscreenType>>>,
+    maximized: Arc<Mutex<bool>>,
+    activated: Arc<Mutex<bool>>,
     need_frame_refresh: Arc<Mutex<bool>>,
     closed: bool,
     kill_switch: Arc<Mutex<bool>>,
-    frame: Weak<Mutex<SWindow<ConceptFrame>>>,
+    frame: Weak<Mutex<Option<SWindow<ConceptFrame>>>>,
     current_dpi: i32,
     new_dpi: Option<i32>,
     configured: bool,
+    // Whether the last configure event had the fullscreen state.
+    configured_fullscreen: bool,
+    custom_cursor: Option<CustomCursor>,
+    cursor_visible: bool,
+}
+
+// The surface a pointer is currently over, along with the serial of the enter event, which
+// `wl_pointer.set_cursor` requires.
+struct PointerFocus {
+    pointer: wl_pointer::WlPointer,
+    surface: wl_surface::WlSurface,
+    serial: u32,
 }
 
 pub struct WindowStore {
     windows: Vec<InternalWindow>,
+    pointer_focus: Vec<PointerFocus>,
+    default_cursor: Option<(CursorTheme, CustomCursor)>,
 }
 
 impl WindowStore {
     pub fn new() -> WindowStore {
         WindowStore {
             windows: Vec::new(),
+            pointer_focus: Vec::new(),
+            default_cursor: None,
         }
     }
 
+    pub fn pointer_entered(
+        &mut self,
+        pointer: &wl_pointer::WlPointer,
+        surface: &wl_surface::WlSurface,
+        serial: u32,
+    ) {
+        self.pointer_left(pointer);
+        self.pointer_focus.push(PointerFocus {
+            pointer: pointer.clone(),
+            surface: surface.clone(),
+            serial,
+        });
+        for window in &self.windows {
+            if surface.as_ref().equals(window.surface.as_ref()) {
+                if !window.cursor_visible {
+                    pointer.set_cursor(serial, None, 0, 0);
+                } else if let Some(ref cursor) = window.custom_cursor {
+                    cursor.apply(pointer, serial);
+                }
+            }
+        }
+    }
+
+    pub fn pointer_left(&mut self, pointer: &wl_pointer::WlPointer) {
+        self.pointer_focus
+            .retain(|focus| !focus.pointer.as_ref().equals(pointer.as_ref()));
+    }
+
+    fn set_custom_cursor(
+        &mut self,
+        surface: &wl_surface::WlSurface,
+        cursor: Option<CustomCursor>,
+        compositor: &wl_compositor::WlCompositor,
+        shm: &wl_shm::WlShm,
+    ) {
+        if let Some(window) = self
+            .windows
+            .iter_mut()
+            .find(|window| surface.as_ref().equals(window.surface.as_ref()))
+        {
+            if cursor.is_none() && window.custom_cursor.is_none() {
+                return;
+            }
+            window.custom_cursor = cursor;
+            self.refresh_cursor(surface, compositor, shm);
+        }
+    }
+
+    fn set_cursor_visible(
+        &mut self,
+        surface: &wl_surface::WlSurface,
+        visible: bool,
+        compositor: &wl_compositor::WlCompositor,
+        shm: &wl_shm::WlShm,
+    ) {
+        if let Some(window) = self
+            .windows
+            .iter_mut()
+            .find(|window| surface.as_ref().equals(window.surface.as_ref()))
+        {
+            if window.cursor_visible == visible {
+                return;
+            }
+            window.cursor_visible = visible;
+            self.refresh_cursor(surface, compositor, shm);
+        }
+    }
+
+    // Applies the window's cursor to the pointers currently over it.
+    fn refresh_cursor(
+        &mut self,
+        surface: &wl_surface::WlSurface,
+        compositor: &wl_compositor::WlCompositor,
+        shm: &wl_shm::WlShm,
+    ) {
+        let (visible, custom_cursor) = match self
+            .windows
+            .iter()
+            .find(|window| surface.as_ref().equals(window.surface.as_ref()))
+        {
+            Some(window) => (window.cursor_visible, window.custom_cursor.clone()),
+            None => return,
+        };
+        let cursor = if visible {
+            match custom_cursor.or_else(|| self.default_cursor(compositor, shm)) {
+                Some(cursor) => Some(cursor),
+                None => return,
+            }
+        } else {
+            None
+        };
+        for focus in &self.pointer_focus {
+            if surface.as_ref().equals(focus.surface.as_ref()) {
+                match cursor {
+                    Some(ref cursor) => cursor.apply(&focus.pointer, focus.serial),
+                    None => focus.pointer.set_cursor(focus.serial, None, 0, 0),
+                }
+            }
+        }
+    }
+
+    // Lazily loads the theme's arrow, to restore once a custom or hidden cursor is unset.
+    fn default_cursor(
+        &mut self,
+        compositor: &wl_compositor::WlCompositor,
+        shm: &wl_shm::WlShm,
+    ) -> Option<CustomCursor> {
+        if self.default_cursor.is_none() && wayland_cursor::is_available() {
+            let theme = wayland_cursor::load_theme(None, 24, shm);
+            let cursor = {
+                let arrow = theme.get_cursor("left_ptr")?;
+                let buffer = arrow.frame_buffer(0)?;
+                let (_, _, hotspot_x, hotspot_y, _) = arrow.frame_info(0)?;
+                let surface = compositor.create_surface(NewProxy::implement_dummy).ok()?;
+                surface.attach(Some(&buffer), 0, 0);
+                surface.commit();
+                CustomCursor {
+                    inner: Arc::new(CursorSurface {
+                        surface,
+                        hotspot: (hotspot_x as i32, hotspot_y as i32),
+                        buffer: None,
+                    }),
+                }
+            };
+            self.default_cursor = Some((theme, cursor));
+        }
+        self.default_cursor
+            .as_ref()
+            .map(|(_, cursor)| cursor.clone())
+    }
+
     pub fn find_wid(&self, surface: &wl_surface::WlSurface) -> Option<WindowId> {
         for window in &self.windows {
             if surface.as_ref().equals(&window.surface.as_ref()) {
-                return Some(make_wid(surface));
+                return Some(window.id);
             }
         }
         None
@@ -408,7 +878,7 @@ impl WindowStore {
         self.windows.retain(|w| {
             if *w.kill_switch.lock().unwrap() {
                 // window is dead, cleanup
-                pruned.push(make_wid(&w.surface));
+                pruned.push(w.id);
                 w.surface.destroy();
                 false
             } else {
@@ -421,7 +891,17 @@ impl WindowStore {
     pub fn new_seat(&self, seat: &wl_seat::WlSeat) {
         for window in &self.windows {
             if let Some(w) = window.frame.upgrade() {
-                w.lock().unwrap().new_seat(seat);
+                if let Some(ref mut frame) = *w.lock().unwrap() {
+                    frame.new_seat(seat);
+                }
+            }
+        }
+    }
+
+    fn request_resize(&mut self, surface: &wl_surface::WlSurface, size: (u32, u32)) {
+        for window in &mut self.windows {
+            if surface.as_ref().equals(window.surface.as_ref()) {
+                window.newsize = Some(size);
             }
         }
     }
@@ -457,8 +937,8 @@ impl WindowStore {
                 ::std::mem::replace(&mut *window.need_refresh.lock().unwrap(), false),
                 ::std::mem::replace(&mut *window.need_frame_refresh.lock().unwrap(), false),
                 window.closed,
-                make_wid(&window.surface),
-                opt_mutex_lock.as_mut().map(|m| &mut **m),
+                window.id,
+                opt_mutex_lock.as_mut().and_then(|m| m.as_mut()),
             );
             if let Some(dpi) = window.new_dpi.take() {
                 window.current_dpi = dpi;
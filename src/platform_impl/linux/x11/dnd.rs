@@ -2,7 +2,7 @@ use std::{
     io,
     os::raw::*,
     path::{Path, PathBuf},
-    str::Utf8Error,
+    str::{self, Utf8Error},
     sync::Arc,
 };
 
@@ -191,12 +191,23 @@ impl Dnd {
     pub fn parse_data(&self, data: &mut Vec<c_uchar>) -> Result<Vec<PathBuf>, DndDataParseError> {
         if !data.is_empty() {
             let mut path_list = Vec::new();
-            let decoded = percent_decode(data).decode_utf8()?.into_owned();
-            for uri in decoded.split("\r\n").filter(|u| !u.is_empty()) {
+            // Each URI has to be decoded on its own, since an escaped newline would otherwise
+            // split a path in two.
+            let uri_list = str::from_utf8(data)?;
+            let uris = uri_list
+                .split("\r\n")
+                .filter(|u| !u.is_empty() && !u.starts_with('#'));
+            for uri in uris {
                 // The format is specified as protocol://host/path
                 // However, it's typically simply protocol:///path
-                let path_str = if uri.starts_with("file://") {
-                    let path_str = uri.replace("file://", "");
+                let path_str = if let Some(path_str) = uri.strip_prefix("file://") {
+                    let path_str = path_str
+                        .strip_prefix("localhost")
+                        .filter(|path_str| path_str.starts_with('/'))
+                        .unwrap_or(path_str);
+                    let path_str = percent_decode(path_str.as_bytes())
+                        .decode_utf8()?
+                        .into_owned();
                     if !path_str.starts_with('/') {
                         // A hostname is specified
                         // Supporting this case is beyond the scope of my mental health
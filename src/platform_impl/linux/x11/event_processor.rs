@@ -1,26 +1,42 @@
-use std::{cell::RefCell, collections::HashMap, ptr, rc::Rc, slice};
+use std::{
+    cell::{Cell, RefCell},
+    collections::HashMap,
+    mem, ptr,
+    rc::Rc,
+    slice,
+};
 
 use libc::{c_char, c_int, c_long, c_uint, c_ulong};
 
 use super::{
-    events, ffi, get_xtarget, mkdid, mkwid, monitor, util, Device, DeviceId, DeviceInfo, Dnd,
-    DndState, GenericEventCookie, ImeReceiver, ScrollOrientation, UnownedWindow, WindowId,
-    XExtension,
+    events, ffi, get_xtarget, mkdid, mkwid, monitor, util, window::Visibility, Device, DeviceId,
+    DeviceInfo, Dnd, DndState, GenericEventCookie, ImeEventReceiver, ImeReceiver, ImeRequest,
+    ScrollOrientation, UnownedWindow, WindowId, XExtension,
 };
 
 use crate::{
-    dpi::{LogicalPosition, LogicalSize},
-    event::{DeviceEvent, Event, KeyboardInput, ModifiersState, WindowEvent},
-    event_loop::EventLoopWindowTarget as RootELW,
+    dpi::{LogicalPosition, LogicalSize, PhysicalSize},
+    event::{DeviceEvent, Event, Ime as ImeEvent, KeyboardInput, ModifiersState, WindowEvent},
+    event_loop::{DeviceEventFilter, EventLoopWindowTarget as RootELW},
 };
 
 pub(super) struct EventProcessor<T: 'static> {
     pub(super) dnd: Dnd,
     pub(super) ime_receiver: ImeReceiver,
+    pub(super) ime_event_receiver: ImeEventReceiver,
+    /// The window whose composition was started with `Ime::Enabled`, and which the text committed
+    /// by the input method goes to.
+    pub(super) ime_window: Option<ffi::Window>,
     pub(super) randr_event_offset: c_int,
     pub(super) devices: RefCell<HashMap<DeviceId, Device>>,
     pub(super) xi2ext: XExtension,
     pub(super) target: Rc<RootELW<T>>,
+    /// The modifiers last reported through `ModifiersChanged`.
+    pub(super) modifiers: Cell<ModifiersState>,
+    /// The window that currently has input focus, if it's one of ours.
+    pub(super) focused_window: Cell<Option<ffi::Window>>,
+    /// The window the cursor is currently in, if it's one of ours.
+    pub(super) cursor_window: Cell<Option<ffi::Window>>,
 }
 
 impl<T: 'static> EventProcessor<T> {
@@ -34,6 +50,14 @@ impl<T: 'static> EventProcessor<T> {
         }
     }
 
+    fn device_events_enabled(&self) -> bool {
+        match get_xtarget(&self.target).device_event_filter.get() {
+            DeviceEventFilter::Always => true,
+            DeviceEventFilter::WhenFocused => self.focused_window.get().is_some(),
+            DeviceEventFilter::Never => false,
+        }
+    }
+
     fn with_window<F, Ret>(&self, window_id: ffi::Window, callback: F) -> Option<Ret>
     where
         F: Fn(&UnownedWindow) -> Ret,
@@ -62,6 +86,11 @@ impl<T: 'static> EventProcessor<T> {
         self.with_window(window_id, |_| ()).is_some()
     }
 
+    fn input_enabled(&self, window_id: ffi::Window) -> bool {
+        self.with_window(window_id, |window| window.is_enabled())
+            .unwrap_or(true)
+    }
+
     pub(super) unsafe fn poll_one_event(&mut self, event_ptr: *mut ffi::XEvent) -> bool {
         let wt = get_xtarget(&self.target);
         // This function is used to poll and remove a single event
@@ -94,22 +123,63 @@ impl<T: 'static> EventProcessor<T> {
         F: FnMut(Event<T>),
     {
         let wt = get_xtarget(&self.target);
+
+        // These have to be handled before the event reaches the input method, since a `Reset`
+        // must affect the very next key press.
+        while let Ok(request) = self.ime_receiver.try_recv() {
+            match request {
+                ImeRequest::Position(window_id, x, y) => {
+                    wt.ime.borrow_mut().send_xim_spot(window_id, x, y);
+                },
+                ImeRequest::Allow(window_id, allowed) => {
+                    if let Err(err) = wt.ime.borrow_mut().set_allowed(window_id, allowed) {
+                        warn!("Failed to change IME state: {:?}", err);
+                    }
+                },
+                ImeRequest::Reset(window_id) => {
+                    if let Err(err) = wt.ime.borrow_mut().reset(window_id) {
+                        warn!("Failed to reset IME state: {:?}", err);
+                    }
+                },
+            }
+        }
+
+        let event_type = xev.get_type();
+        // Key presses for disabled windows are dropped before they can reach the input method.
+        if event_type == ffi::KeyPress || event_type == ffi::KeyRelease {
+            let xkev: &ffi::XKeyEvent = xev.as_ref();
+            if !self.input_enabled(xkev.window) {
+                return;
+            }
+        }
         // XFilterEvent tells us when an event has been discarded by the input method.
         // Specifically, this involves all of the KeyPress events in compose/pre-edit sequences,
         // along with an extra copy of the KeyRelease events. This also prevents backspace and
         // arrow keys from being detected twice.
-        if ffi::True
+        let filtered = ffi::True
             == unsafe {
                 (wt.xconn.xlib.XFilterEvent)(xev, {
                     let xev: &ffi::XAnyEvent = xev.as_ref();
                     xev.window
                 })
+            };
+
+        // The preedit callbacks are run while the input method filters events, or while it's
+        // handling the requests above.
+        while let Ok((window, event)) = self.ime_event_receiver.try_recv() {
+            if event == ImeEvent::Enabled {
+                self.ime_window = Some(window);
             }
-        {
+            callback(Event::WindowEvent {
+                window_id: mkwid(window),
+                event: WindowEvent::Ime(event),
+            });
+        }
+
+        if filtered {
             return;
         }
 
-        let event_type = xev.get_type();
         match event_type {
             ffi::MappingNotify => {
                 unsafe {
@@ -280,14 +350,14 @@ impl<T: 'static> EventProcessor<T> {
                     // `XSendEvent` (synthetic `ConfigureNotify`) -> position relative to root
                     // `XConfigureNotify` (real `ConfigureNotify`) -> position relative to parent
                     // https://tronche.com/gui/x/icccm/sec-4.html#s-4.1.5
-                    // We don't want to send `Moved` when this is false, since then every `Resized`
-                    // (whether the window moved or not) is accompanied by an extraneous `Moved` event
-                    // that has a position relative to the parent window.
+                    // A real `ConfigureNotify` thus has to be translated to root coordinates, or else
+                    // every `Resized` (whether the window moved or not) would be accompanied by an
+                    // extraneous `Moved` event that has a position relative to the parent window.
                     let is_synthetic = xev.send_event == ffi::True;
 
                     // These are both in physical space.
                     let new_inner_size = (xev.width as u32, xev.height as u32);
-                    let new_inner_position = (xev.x as i32, xev.y as i32);
+                    let mut new_inner_position = (xev.x as i32, xev.y as i32);
 
                     let mut monitor = window.current_monitor(); // This must be done *before* locking!
                     let mut shared_state_lock = window.shared_state.lock();
@@ -305,16 +375,33 @@ impl<T: 'static> EventProcessor<T> {
                             // Since this isn't synthetic, as per the notes above, this position is relative to the
                             // parent window.
                             let rel_parent = new_inner_position;
-                            if util::maybe_change(
+                            let frame_changed = util::maybe_change(
                                 &mut shared_state_lock.inner_position_rel_parent,
                                 rel_parent,
-                            ) {
-                                // This ensures we process the next `Moved`.
-                                shared_state_lock.inner_position = None;
+                            );
+                            if frame_changed {
                                 // Extra insurance against stale frame extents.
                                 shared_state_lock.frame_extents = None;
                             }
-                            false
+                            // Without a reparenting WM, the synthetic event may never come, so the
+                            // position relative to the root is what decides whether we moved.
+                            match wt.xconn.translate_coords(xwindow, wt.root) {
+                                Ok(coords) => {
+                                    new_inner_position = (coords.x_rel_root, coords.y_rel_root);
+                                    // New frame extents move the outer position along with them.
+                                    util::maybe_change(
+                                        &mut shared_state_lock.inner_position,
+                                        new_inner_position,
+                                    ) || frame_changed
+                                },
+                                // The window is already gone, so the last position still stands.
+                                Err(_) => {
+                                    new_inner_position = shared_state_lock
+                                        .inner_position
+                                        .unwrap_or(new_inner_position);
+                                    false
+                                },
+                            }
                         };
                         (resized, moved)
                     };
@@ -335,8 +422,10 @@ impl<T: 'static> EventProcessor<T> {
                             });
                         let outer = frame_extents
                             .inner_pos_to_outer(new_inner_position.0, new_inner_position.1);
-                        shared_state_lock.position = Some(outer);
-                        if moved {
+                        // Coalesces the real and the synthetic event for the same move.
+                        let outer_changed =
+                            util::maybe_change(&mut shared_state_lock.position, outer);
+                        if moved && outer_changed {
                             let logical_position =
                                 LogicalPosition::from_physical(outer, monitor.hidpi_factor);
                             events.moved = Some(WindowEvent::Moved(logical_position));
@@ -368,8 +457,6 @@ impl<T: 'static> EventProcessor<T> {
                             new_hidpi_factor
                         };
                         if last_hidpi_factor != new_hidpi_factor {
-                            events.dpi_changed =
-                                Some(WindowEvent::HiDpiFactorChanged(new_hidpi_factor));
                             let (new_width, new_height, flusher) = window.adjust_for_dpi(
                                 last_hidpi_factor,
                                 new_hidpi_factor,
@@ -377,6 +464,10 @@ impl<T: 'static> EventProcessor<T> {
                                 height,
                             );
                             flusher.queue();
+                            events.dpi_changed = Some(WindowEvent::ScaleFactorChanged {
+                                scale_factor: new_hidpi_factor,
+                                new_inner_size: PhysicalSize::new(new_width, new_height),
+                            });
                             shared_state_lock.dpi_adjusted = Some((new_width, new_height));
                             // if the DPI factor changed, force a resize event to ensure the logical
                             // size is computed with the right DPI factor
@@ -446,12 +537,70 @@ impl<T: 'static> EventProcessor<T> {
                 });
             },
 
+            ffi::PropertyNotify => {
+                let xev: &ffi::XPropertyEvent = xev.as_ref();
+
+                let state_atom = unsafe { wt.xconn.get_atom_unchecked(b"_NET_WM_STATE\0") };
+                let extents_atom = unsafe { wt.xconn.get_atom_unchecked(b"_NET_FRAME_EXTENTS\0") };
+                if xev.atom == state_atom {
+                    self.with_window(xev.window, |window| {
+                        window.refresh_fullscreen_state();
+                    });
+                } else if xev.atom == extents_atom {
+                    // The window manager usually sets this after the window is mapped, by which
+                    // time the extents may already have been cached without it.
+                    self.with_window(xev.window, |window| {
+                        window.invalidate_cached_frame_extents();
+                    });
+                }
+            },
+
+            ffi::MapNotify => {
+                let xev: &ffi::XMapEvent = xev.as_ref();
+
+                // `UnmapNotify` is left alone, since the window manager also unmaps windows that
+                // are merely on another workspace or minimized.
+                self.with_window(xev.window, |window| {
+                    let mut shared_state = window.shared_state.lock();
+                    if shared_state.visibility == Visibility::YesWait {
+                        shared_state.visibility = Visibility::Yes;
+                    }
+                });
+            },
+
+            // The window manager unmaps windows that are minimized or on another workspace, which
+            // hides them as much as being covered does.
+            ffi::UnmapNotify | ffi::VisibilityNotify => {
+                let (window, occluded) = if xev.get_type() == ffi::UnmapNotify {
+                    let xev: &ffi::XUnmapEvent = xev.as_ref();
+                    (xev.window, true)
+                } else {
+                    let xev: &ffi::XVisibilityEvent = xev.as_ref();
+                    (xev.window, xev.state == ffi::VisibilityFullyObscured)
+                };
+
+                let changed = self.with_window(window, |window| {
+                    let mut shared_state = window.shared_state.lock();
+                    mem::replace(&mut shared_state.occluded, occluded) != occluded
+                });
+                if changed == Some(true) {
+                    callback(Event::WindowEvent {
+                        window_id: mkwid(window),
+                        event: WindowEvent::Occluded(occluded),
+                    });
+                }
+            },
+
             ffi::DestroyNotify => {
                 let xev: &ffi::XDestroyWindowEvent = xev.as_ref();
 
                 let window = xev.window;
                 let window_id = mkwid(window);
 
+                self.with_window(window, |window| {
+                    window.shared_state.lock().visibility = Visibility::Destroyed;
+                });
+
                 // In the event that the window's been destroyed without being dropped first, we
                 // cleanup again here.
                 wt.windows.borrow_mut().remove(&WindowId(window));
@@ -472,13 +621,15 @@ impl<T: 'static> EventProcessor<T> {
             ffi::Expose => {
                 let xev: &ffi::XExposeEvent = xev.as_ref();
 
-                let window = xev.window;
-                let window_id = mkwid(window);
-
-                callback(Event::WindowEvent {
-                    window_id,
-                    event: WindowEvent::RedrawRequested,
-                });
+                // The server sends one `Expose` per damaged rectangle. Rather than emitting a
+                // `RedrawRequested` for each of them, we merge them with the redraws requested
+                // through `Window::request_redraw`, which are dispatched once after all pending
+                // events have been processed.
+                let wt = get_xtarget(&self.target);
+                wt.pending_redraws
+                    .lock()
+                    .unwrap()
+                    .insert(WindowId(xev.window));
             },
 
             ffi::KeyPress | ffi::KeyRelease => {
@@ -537,6 +688,27 @@ impl<T: 'static> EventProcessor<T> {
                             },
                         },
                     });
+
+                    // The state of a key event doesn't include the effect of the event itself,
+                    // so we apply it ourselves when the key is a modifier.
+                    let mut new_modifiers = modifiers;
+                    match keysym as c_uint {
+                        ffi::XK_Shift_L | ffi::XK_Shift_R => new_modifiers.shift = state == Pressed,
+                        ffi::XK_Control_L | ffi::XK_Control_R => {
+                            new_modifiers.ctrl = state == Pressed
+                        },
+                        ffi::XK_Alt_L | ffi::XK_Alt_R | ffi::XK_Meta_L | ffi::XK_Meta_R => {
+                            new_modifiers.alt = state == Pressed
+                        },
+                        ffi::XK_Super_L | ffi::XK_Super_R => new_modifiers.logo = state == Pressed,
+                        _ => (),
+                    }
+                    if self.modifiers.replace(new_modifiers) != new_modifiers {
+                        callback(Event::WindowEvent {
+                            window_id,
+                            event: WindowEvent::ModifiersChanged(new_modifiers),
+                        });
+                    }
                 }
 
                 if state == Pressed {
@@ -546,12 +718,28 @@ impl<T: 'static> EventProcessor<T> {
                         return;
                     };
 
-                    for chr in written.chars() {
-                        let event = Event::WindowEvent {
-                            window_id,
-                            event: WindowEvent::ReceivedCharacter(chr),
-                        };
-                        callback(event);
+                    // The text of a composition is committed with a key code of 0, like the result
+                    // of a compose sequence. Any other key press means the composition is over.
+                    let committed = xkev.keycode == 0 && self.ime_window == Some(window);
+                    if xkev.keycode != 0 || committed {
+                        self.ime_window = None;
+                    }
+
+                    if committed {
+                        if !written.is_empty() {
+                            callback(Event::WindowEvent {
+                                window_id,
+                                event: WindowEvent::Ime(ImeEvent::Commit(written)),
+                            });
+                        }
+                    } else {
+                        for chr in written.chars() {
+                            let event = Event::WindowEvent {
+                                window_id,
+                                event: WindowEvent::ReceivedCharacter(chr),
+                            };
+                            callback(event);
+                        }
                     }
                 }
             },
@@ -581,6 +769,9 @@ impl<T: 'static> EventProcessor<T> {
                 match xev.evtype {
                     ffi::XI_ButtonPress | ffi::XI_ButtonRelease => {
                         let xev: &ffi::XIDeviceEvent = unsafe { &*(xev.data as *const _) };
+                        if !self.input_enabled(xev.event) {
+                            return;
+                        }
                         let window_id = mkwid(xev.event);
                         let device_id = mkdid(xev.deviceid);
                         if (xev.flags & ffi::XIPointerEmulated) != 0 {
@@ -668,6 +859,9 @@ impl<T: 'static> EventProcessor<T> {
                     },
                     ffi::XI_Motion => {
                         let xev: &ffi::XIDeviceEvent = unsafe { &*(xev.data as *const _) };
+                        if !self.input_enabled(xev.event) {
+                            return;
+                        }
                         let device_id = mkdid(xev.deviceid);
                         let window_id = mkwid(xev.event);
                         let new_cursor_pos = (xev.event_x, xev.event_y);
@@ -786,10 +980,14 @@ impl<T: 'static> EventProcessor<T> {
                                 }
                             }
                         }
-                        callback(Event::WindowEvent {
-                            window_id,
-                            event: CursorEntered { device_id },
-                        });
+                        // An `XI_Enter` is also sent when a grab ends while the cursor is inside,
+                        // even though the matching `XI_Leave` was ignored.
+                        if self.cursor_window.replace(Some(xev.event)) != Some(xev.event) {
+                            callback(Event::WindowEvent {
+                                window_id,
+                                event: CursorEntered { device_id },
+                            });
+                        }
 
                         if let Some(dpi_factor) =
                             self.with_window(xev.event, |window| window.hidpi_factor())
@@ -824,6 +1022,15 @@ impl<T: 'static> EventProcessor<T> {
                     },
                     ffi::XI_Leave => {
                         let xev: &ffi::XILeaveEvent = unsafe { &*(xev.data as *const _) };
+                        // The cursor doesn't actually leave the window when a grab starts, such as
+                        // when the window manager starts dragging the window.
+                        if xev.mode == ffi::XINotifyGrab {
+                            return;
+                        }
+                        if self.cursor_window.get() != Some(xev.event) {
+                            return;
+                        }
+                        self.cursor_window.set(None);
 
                         // Leave, FocusIn, and FocusOut can be received by a window that's already
                         // been destroyed, which the user presumably doesn't want to deal with.
@@ -839,6 +1046,21 @@ impl<T: 'static> EventProcessor<T> {
                     },
                     ffi::XI_FocusIn => {
                         let xev: &ffi::XIFocusInEvent = unsafe { &*(xev.data as *const _) };
+                        // Keyboard grabs (by the WM while switching windows or dragging, or by
+                        // `set_keyboard_grab`) move the focus away and back without the user
+                        // actually changing windows.
+                        if xev.mode == ffi::XINotifyGrab {
+                            return;
+                        }
+                        if xev.mode == ffi::XINotifyUngrab {
+                            // The WM let go of the keyboard, so our own grab can be taken back.
+                            self.with_window(xev.event, |window| window.regrab_keyboard());
+                            return;
+                        }
+                        // `Focused(true)` has already been sent for this window.
+                        if self.focused_window.get() == Some(xev.event) {
+                            return;
+                        }
 
                         let dpi_factor =
                             match self.with_window(xev.event, |window| window.hidpi_factor()) {
@@ -846,17 +1068,30 @@ impl<T: 'static> EventProcessor<T> {
                                 None => return,
                             };
                         let window_id = mkwid(xev.event);
+                        self.focused_window.set(Some(xev.event));
 
                         wt.ime
                             .borrow_mut()
                             .focus(xev.event)
                             .expect("Failed to focus input context");
 
+                        self.with_window(xev.event, |window| window.regrab_keyboard());
+
                         callback(Event::WindowEvent {
                             window_id,
                             event: Focused(true),
                         });
 
+                        // Modifiers may have been pressed or released while another window had
+                        // focus, so we start over from the real state.
+                        let modifiers = ModifiersState::from(xev.mods);
+                        if self.modifiers.replace(modifiers) != modifiers {
+                            callback(Event::WindowEvent {
+                                window_id,
+                                event: WindowEvent::ModifiersChanged(modifiers),
+                            });
+                        }
+
                         // The deviceid for this event is for a keyboard instead of a pointer,
                         // so we have to do a little extra work.
                         let pointer_id = self
@@ -881,6 +1116,14 @@ impl<T: 'static> EventProcessor<T> {
                     },
                     ffi::XI_FocusOut => {
                         let xev: &ffi::XIFocusOutEvent = unsafe { &*(xev.data as *const _) };
+                        if xev.mode == ffi::XINotifyGrab || xev.mode == ffi::XINotifyUngrab {
+                            return;
+                        }
+                        // Only report losing focus if gaining it was reported.
+                        if self.focused_window.get() != Some(xev.event) {
+                            return;
+                        }
+                        self.focused_window.set(None);
                         if !self.window_exists(xev.event) {
                             return;
                         }
@@ -888,6 +1131,14 @@ impl<T: 'static> EventProcessor<T> {
                             .borrow_mut()
                             .unfocus(xev.event)
                             .expect("Failed to unfocus input context");
+                        if self.modifiers.replace(ModifiersState::default())
+                            != ModifiersState::default()
+                        {
+                            callback(Event::WindowEvent {
+                                window_id: mkwid(xev.event),
+                                event: WindowEvent::ModifiersChanged(ModifiersState::default()),
+                            });
+                        }
                         callback(Event::WindowEvent {
                             window_id: mkwid(xev.event),
                             event: Focused(false),
@@ -896,6 +1147,9 @@ impl<T: 'static> EventProcessor<T> {
 
                     ffi::XI_TouchBegin | ffi::XI_TouchUpdate | ffi::XI_TouchEnd => {
                         let xev: &ffi::XIDeviceEvent = unsafe { &*(xev.data as *const _) };
+                        if !self.input_enabled(xev.event) {
+                            return;
+                        }
                         let window_id = mkwid(xev.event);
                         let phase = match xev.evtype {
                             ffi::XI_TouchBegin => TouchPhase::Started,
@@ -916,12 +1170,20 @@ impl<T: 'static> EventProcessor<T> {
                                     device_id: mkdid(xev.deviceid),
                                     phase,
                                     location,
+                                    force: None,
                                     id: xev.detail as u64,
                                 }),
                             })
                         }
                     },
 
+                    ffi::XI_RawButtonPress
+                    | ffi::XI_RawButtonRelease
+                    | ffi::XI_RawMotion
+                    | ffi::XI_RawKeyPress
+                    | ffi::XI_RawKeyRelease
+                        if !self.device_events_enabled() => {},
+
                     ffi::XI_RawButtonPress | ffi::XI_RawButtonRelease => {
                         let xev: &ffi::XIRawEvent = unsafe { &*(xev.data as *const _) };
                         if xev.flags & ffi::XIPointerEmulated == 0 {
@@ -942,6 +1204,12 @@ impl<T: 'static> EventProcessor<T> {
                     ffi::XI_RawMotion => {
                         let xev: &ffi::XIRawEvent = unsafe { &*(xev.data as *const _) };
                         let did = mkdid(xev.deviceid);
+                        let absolute_axes = self
+                            .devices
+                            .borrow()
+                            .get(&DeviceId(xev.deviceid))
+                            .map(|device| device.absolute_axes.clone())
+                            .unwrap_or_default();
 
                         let mask = unsafe {
                             slice::from_raw_parts(
@@ -955,9 +1223,11 @@ impl<T: 'static> EventProcessor<T> {
                         for i in 0..xev.valuators.mask_len * 8 {
                             if ffi::XIMaskIsSet(mask, i) {
                                 let x = unsafe { *value };
-                                // We assume that every XInput2 device with analog axes is a pointing device emitting
-                                // relative coordinates.
+                                // We assume that every XInput2 device with relative analog axes is a pointing
+                                // device. Absolute axes, like those of tablets and joysticks, report a position
+                                // instead, so they're only available through `DeviceEvent::Motion`.
                                 match i {
+                                    _ if absolute_axes.contains(&i) => {},
                                     0 => mouse_delta.0 = x,
                                     1 => mouse_delta.1 = x,
                                     2 => scroll_delta.0 = x as f32,
@@ -1078,21 +1348,25 @@ impl<T: 'static> EventProcessor<T> {
                                                 // Check if the window is on this monitor
                                                 let monitor = window.current_monitor();
                                                 if monitor.name == new_monitor.name {
-                                                    callback(Event::WindowEvent {
-                                                        window_id: mkwid(window_id.0),
-                                                        event: WindowEvent::HiDpiFactorChanged(
-                                                            new_monitor.hidpi_factor,
-                                                        ),
-                                                    });
                                                     let (width, height) =
                                                         window.inner_size_physical();
-                                                    let (_, _, flusher) = window.adjust_for_dpi(
-                                                        prev_monitor.hidpi_factor,
-                                                        new_monitor.hidpi_factor,
-                                                        width as f64,
-                                                        height as f64,
-                                                    );
+                                                    let (new_width, new_height, flusher) = window
+                                                        .adjust_for_dpi(
+                                                            prev_monitor.hidpi_factor,
+                                                            new_monitor.hidpi_factor,
+                                                            width as f64,
+                                                            height as f64,
+                                                        );
                                                     flusher.queue();
+                                                    callback(Event::WindowEvent {
+                                                        window_id: mkwid(window_id.0),
+                                                        event: WindowEvent::ScaleFactorChanged {
+                                                            scale_factor: new_monitor.hidpi_factor,
+                                                            new_inner_size: PhysicalSize::new(
+                                                                new_width, new_height,
+                                                            ),
+                                                        },
+                                                    });
                                                 }
                                             }
                                         }
@@ -1103,12 +1377,5 @@ impl<T: 'static> EventProcessor<T> {
                 }
             },
         }
-
-        match self.ime_receiver.try_recv() {
-            Ok((window_id, x, y)) => {
-                wt.ime.borrow_mut().send_xim_spot(window_id, x, y);
-            },
-            Err(_) => (),
-        }
     }
 }
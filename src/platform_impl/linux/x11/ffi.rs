@@ -1,4 +1,13 @@
+use std::os::raw::c_int;
+
 pub use x11_dl::{
     error::OpenError, keysym::*, xcursor::*, xinput::*, xinput2::*, xlib::*, xlib_xcb::*,
     xrandr::*, xrender::*,
 };
+// `x11-dl` names the XFixes bindings after Xlib by mistake.
+pub use x11_dl::xfixes::Xlib as Xfixes;
+
+// `ShapeBounding` and `ShapeInput` from `X11/extensions/shape.h`, for use with
+// `XFixesSetWindowShapeRegion`.
+pub const SHAPE_BOUNDING: c_int = 0;
+pub const SHAPE_INPUT: c_int = 2;
@@ -107,8 +107,19 @@ unsafe fn replace_im(inner: *mut ImeInner) -> Result<(), ReplaceImError> {
     let mut new_contexts = HashMap::new();
     for (window, old_context) in (*inner).contexts.iter() {
         let spot = old_context.as_ref().map(|old_context| old_context.ic_spot);
+        let allowed = old_context
+            .as_ref()
+            .map(|old_context| old_context.allowed)
+            .unwrap_or(true);
         let new_context = {
-            let result = ImeContext::new(xconn, new_im.im, *window, spot);
+            let result = ImeContext::new(
+                xconn,
+                new_im.im,
+                *window,
+                spot,
+                allowed,
+                &(*inner).event_sender,
+            );
             if result.is_err() {
                 let _ = close_im(xconn, new_im.im);
             }
@@ -1,10 +1,11 @@
 use std::{
-    os::raw::{c_short, c_void},
+    ffi::CStr,
+    os::raw::{c_int, c_short, c_void},
     ptr,
     sync::Arc,
 };
 
-use super::{ffi, util, XConnection, XError};
+use super::{ffi, util, ImeEvent, ImeEventSender, XConnection, XError};
 
 #[derive(Debug)]
 pub enum ImeContextCreationError {
@@ -28,6 +29,115 @@ unsafe fn create_pre_edit_attr<'a>(
     .expect("XVaCreateNestedList returned NULL")
 }
 
+// The text being composed, which the preedit callbacks of a context keep up to date.
+#[derive(Debug)]
+struct PreeditState {
+    window: ffi::Window,
+    event_sender: ImeEventSender,
+    text: Vec<char>,
+    // In characters, like the positions the input method reports.
+    cursor_pos: usize,
+}
+
+impl PreeditState {
+    fn send(&self, event: ImeEvent) {
+        let _ = self.event_sender.send((self.window, event));
+    }
+
+    fn send_preedit(&self) {
+        let cursor_byte_pos = self.text[..self.cursor_pos]
+            .iter()
+            .map(|c| c.len_utf8())
+            .sum();
+        self.send(ImeEvent::Preedit(
+            self.text.iter().collect(),
+            Some((cursor_byte_pos, cursor_byte_pos)),
+        ));
+    }
+}
+
+unsafe fn xim_text_chars(text: *const ffi::XIMText) -> Vec<char> {
+    // Wide characters aren't used in UTF-8 locales.
+    if text.is_null() || (*text).encoding_is_wchar != ffi::False {
+        return Vec::new();
+    }
+    let string = (*text).string.multi_byte;
+    if string.is_null() {
+        return Vec::new();
+    }
+    CStr::from_ptr(string).to_string_lossy().chars().collect()
+}
+
+unsafe extern "C" fn preedit_start_callback(
+    _ic: ffi::XIC,
+    client_data: ffi::XPointer,
+    _call_data: ffi::XPointer,
+) -> ffi::Bool {
+    let state = &mut *(client_data as *mut PreeditState);
+    state.text.clear();
+    state.cursor_pos = 0;
+    state.send(ImeEvent::Enabled);
+    // The text being composed has no length limit.
+    -1
+}
+
+unsafe extern "C" fn preedit_done_callback(
+    _ic: ffi::XIC,
+    client_data: ffi::XPointer,
+    _call_data: ffi::XPointer,
+) -> ffi::Bool {
+    let state = &mut *(client_data as *mut PreeditState);
+    state.text.clear();
+    state.cursor_pos = 0;
+    state.send(ImeEvent::Disabled);
+    ffi::False
+}
+
+unsafe extern "C" fn preedit_draw_callback(
+    _ic: ffi::XIC,
+    client_data: ffi::XPointer,
+    call_data: ffi::XPointer,
+) -> ffi::Bool {
+    let state = &mut *(client_data as *mut PreeditState);
+    let call_data = &*(call_data as *const ffi::XIMPreeditDrawCallbackStruct);
+
+    // `chg_length` characters starting at `chg_first` are replaced with the new text.
+    let len = state.text.len();
+    let chg_first = (call_data.chg_first.max(0) as usize).min(len);
+    let chg_end = (chg_first + call_data.chg_length.max(0) as usize).min(len);
+    state
+        .text
+        .splice(chg_first..chg_end, xim_text_chars(call_data.text));
+    state.cursor_pos = (call_data.caret.max(0) as usize).min(state.text.len());
+    state.send_preedit();
+    ffi::False
+}
+
+unsafe extern "C" fn preedit_caret_callback(
+    _ic: ffi::XIC,
+    client_data: ffi::XPointer,
+    call_data: ffi::XPointer,
+) -> ffi::Bool {
+    let state = &mut *(client_data as *mut PreeditState);
+    let call_data = &mut *(call_data as *mut ffi::XIMPreeditCaretCallbackStruct);
+
+    let len = state.text.len();
+    state.cursor_pos = match call_data.direction {
+        ffi::XIMCaretDirection::XIMForwardChar => (state.cursor_pos + 1).min(len),
+        ffi::XIMCaretDirection::XIMBackwardChar => state.cursor_pos.saturating_sub(1),
+        ffi::XIMCaretDirection::XIMLineStart => 0,
+        ffi::XIMCaretDirection::XIMLineEnd => len,
+        ffi::XIMCaretDirection::XIMAbsolutePosition => {
+            (call_data.position.max(0) as usize).min(len)
+        },
+        _ => state.cursor_pos,
+    };
+    // The input method reads the resulting position back.
+    call_data.position = state.cursor_pos as c_int;
+    state.send_preedit();
+    ffi::False
+}
+
 // WARNING: this struct doesn't destroy its XIC resource when dropped.
 // This is intentional, as it doesn't have enough information to know whether or not the context
 // still exists on the server. Since `ImeInner` has that awareness, destruction must be handled
@@ -36,6 +146,10 @@ unsafe fn create_pre_edit_attr<'a>(
 pub struct ImeContext {
     pub ic: ffi::XIC,
     pub ic_spot: ffi::XPoint,
+    pub allowed: bool,
+    // Only set when the preedit callbacks are used, which receive a pointer to it. It's never read
+    // here, but has to live as long as the input context.
+    _preedit_state: Option<Box<PreeditState>>,
 }
 
 impl ImeContext {
@@ -44,11 +158,42 @@ impl ImeContext {
         im: ffi::XIM,
         window: ffi::Window,
         ic_spot: Option<ffi::XPoint>,
+        allowed: bool,
+        event_sender: &ImeEventSender,
     ) -> Result<Self, ImeContextCreationError> {
-        let ic = if let Some(ic_spot) = ic_spot {
-            ImeContext::create_ic_with_spot(xconn, im, window, ic_spot)
+        let mut preedit_state = None;
+        let ic = if allowed {
+            // We'd rather draw the text being composed ourselves, so that it appears in place,
+            // but not every input method lets us.
+            let mut state = Box::new(PreeditState {
+                window,
+                event_sender: event_sender.clone(),
+                text: Vec::new(),
+                cursor_pos: 0,
+            });
+            let spot = ic_spot.unwrap_or(ffi::XPoint { x: 0, y: 0 });
+            match ImeContext::create_ic_with_callbacks(xconn, im, window, spot, &mut state) {
+                Some(ic) => {
+                    preedit_state = Some(state);
+                    Some(ic)
+                },
+                None => {
+                    // Throw away the error from the failed attempt.
+                    let _ = xconn.check_errors();
+                    let style = ffi::XIMPreeditNothing | ffi::XIMStatusNothing;
+                    match ic_spot {
+                        Some(ic_spot) => {
+                            ImeContext::create_ic_with_spot(xconn, im, window, style, ic_spot)
+                        },
+                        None => ImeContext::create_ic(xconn, im, window, style),
+                    }
+                },
+            }
         } else {
-            ImeContext::create_ic(xconn, im, window)
+            // When IME is disallowed, we still need an input context to look up the characters
+            // produced by key presses, but the input method mustn't perform any pre-editing.
+            let style = ffi::XIMPreeditNone | ffi::XIMStatusNone;
+            ImeContext::create_ic(xconn, im, window, style)
         };
 
         let ic = ic.ok_or(ImeContextCreationError::Null)?;
@@ -59,6 +204,8 @@ impl ImeContext {
         Ok(ImeContext {
             ic,
             ic_spot: ic_spot.unwrap_or_else(|| ffi::XPoint { x: 0, y: 0 }),
+            allowed,
+            _preedit_state: preedit_state,
         })
     }
 
@@ -66,11 +213,12 @@ impl ImeContext {
         xconn: &Arc<XConnection>,
         im: ffi::XIM,
         window: ffi::Window,
+        style: c_int,
     ) -> Option<ffi::XIC> {
         let ic = (xconn.xlib.XCreateIC)(
             im,
             ffi::XNInputStyle_0.as_ptr() as *const _,
-            ffi::XIMPreeditNothing | ffi::XIMStatusNothing,
+            style,
             ffi::XNClientWindow_0.as_ptr() as *const _,
             window,
             ptr::null_mut::<()>(),
@@ -86,13 +234,73 @@ impl ImeContext {
         xconn: &Arc<XConnection>,
         im: ffi::XIM,
         window: ffi::Window,
+        style: c_int,
         ic_spot: ffi::XPoint,
     ) -> Option<ffi::XIC> {
         let pre_edit_attr = create_pre_edit_attr(xconn, &ic_spot);
         let ic = (xconn.xlib.XCreateIC)(
             im,
             ffi::XNInputStyle_0.as_ptr() as *const _,
-            ffi::XIMPreeditNothing | ffi::XIMStatusNothing,
+            style,
+            ffi::XNClientWindow_0.as_ptr() as *const _,
+            window,
+            ffi::XNPreeditAttributes_0.as_ptr() as *const _,
+            pre_edit_attr.ptr,
+            ptr::null_mut::<()>(),
+        );
+        if ic.is_null() {
+            None
+        } else {
+            Some(ic)
+        }
+    }
+
+    unsafe fn create_ic_with_callbacks(
+        xconn: &Arc<XConnection>,
+        im: ffi::XIM,
+        window: ffi::Window,
+        ic_spot: ffi::XPoint,
+        state: &mut PreeditState,
+    ) -> Option<ffi::XIC> {
+        let client_data = state as *mut PreeditState as ffi::XPointer;
+        let start_callback = ffi::XICCallback {
+            client_data,
+            callback: Some(preedit_start_callback),
+        };
+        let done_callback = ffi::XICCallback {
+            client_data,
+            callback: Some(preedit_done_callback),
+        };
+        let draw_callback = ffi::XICCallback {
+            client_data,
+            callback: Some(preedit_draw_callback),
+        };
+        let caret_callback = ffi::XICCallback {
+            client_data,
+            callback: Some(preedit_caret_callback),
+        };
+        let pre_edit_attr = util::XSmartPointer::new(
+            xconn,
+            (xconn.xlib.XVaCreateNestedList)(
+                0,
+                ffi::XNSpotLocation_0.as_ptr() as *const _,
+                &ic_spot,
+                ffi::XNPreeditStartCallback_0.as_ptr() as *const _,
+                &start_callback,
+                ffi::XNPreeditDoneCallback_0.as_ptr() as *const _,
+                &done_callback,
+                ffi::XNPreeditDrawCallback_0.as_ptr() as *const _,
+                &draw_callback,
+                ffi::XNPreeditCaretCallback_0.as_ptr() as *const _,
+                &caret_callback,
+                ptr::null_mut::<()>(),
+            ),
+        )
+        .expect("XVaCreateNestedList returned NULL");
+        let ic = (xconn.xlib.XCreateIC)(
+            im,
+            ffi::XNInputStyle_0.as_ptr() as *const _,
+            ffi::XIMPreeditCallbacks | ffi::XIMStatusNothing,
             ffi::XNClientWindow_0.as_ptr() as *const _,
             window,
             ffi::XNPreeditAttributes_0.as_ptr() as *const _,
@@ -120,12 +328,28 @@ impl ImeContext {
         xconn.check_errors()
     }
 
+    pub fn reset(&self, xconn: &Arc<XConnection>) -> Result<(), XError> {
+        unsafe {
+            // This returns whatever was still being composed, which we throw away.
+            let composed = (xconn.xlib.Xutf8ResetIC)(self.ic);
+            if !composed.is_null() {
+                (xconn.xlib.XFree)(composed as *mut _);
+            }
+        }
+        xconn.check_errors()
+    }
+
     pub fn set_spot(&mut self, xconn: &Arc<XConnection>, x: c_short, y: c_short) {
         if self.ic_spot.x == x && self.ic_spot.y == y {
             return;
         }
         self.ic_spot = ffi::XPoint { x, y };
 
+        // Without pre-editing, there's no candidate window to position.
+        if !self.allowed {
+            return;
+        }
+
         unsafe {
             let pre_edit_attr = create_pre_edit_attr(xconn, &self.ic_spot);
             (xconn.xlib.XSetICValues)(
@@ -11,6 +11,7 @@ use std::sync::{
 };
 
 use super::{ffi, util, XConnection, XError};
+use crate::event::Ime as ImeEvent;
 
 pub use self::context::ImeContextCreationError;
 use self::{
@@ -20,8 +21,21 @@ use self::{
     input_method::PotentialInputMethods,
 };
 
-pub type ImeReceiver = Receiver<(ffi::Window, i16, i16)>;
-pub type ImeSender = Sender<(ffi::Window, i16, i16)>;
+pub type ImeReceiver = Receiver<ImeRequest>;
+pub type ImeSender = Sender<ImeRequest>;
+pub type ImeEventReceiver = Receiver<(ffi::Window, ImeEvent)>;
+pub type ImeEventSender = Sender<(ffi::Window, ImeEvent)>;
+
+/// Request sent from a window to the thread owning the input method.
+#[derive(Debug)]
+pub enum ImeRequest {
+    /// Set the position of the candidate window.
+    Position(ffi::Window, i16, i16),
+    /// Allow or disallow IME input.
+    Allow(ffi::Window, bool),
+    /// Throw away any pending dead keys or composition.
+    Reset(ffi::Window),
+}
 
 #[derive(Debug)]
 pub enum ImeCreationError {
@@ -37,11 +51,14 @@ pub struct Ime {
 }
 
 impl Ime {
-    pub fn new(xconn: Arc<XConnection>) -> Result<Self, ImeCreationError> {
+    pub fn new(
+        xconn: Arc<XConnection>,
+        event_sender: ImeEventSender,
+    ) -> Result<Self, ImeCreationError> {
         let potential_input_methods = PotentialInputMethods::new(&xconn);
 
         let (mut inner, client_data) = {
-            let mut inner = Box::new(ImeInner::new(xconn, potential_input_methods));
+            let mut inner = Box::new(ImeInner::new(xconn, potential_input_methods, event_sender));
             let inner_ptr = Box::into_raw(inner);
             let client_data = inner_ptr as _;
             let destroy_callback = ffi::XIMCallback {
@@ -93,7 +110,16 @@ impl Ime {
             // Create empty entry in map, so that when IME is rebuilt, this window has a context.
             None
         } else {
-            Some(unsafe { ImeContext::new(&self.inner.xconn, self.inner.im, window, None) }?)
+            Some(unsafe {
+                ImeContext::new(
+                    &self.inner.xconn,
+                    self.inner.im,
+                    window,
+                    None,
+                    true,
+                    &self.inner.event_sender,
+                )
+            }?)
         };
         self.inner.contexts.insert(window, context);
         Ok(!self.is_destroyed())
@@ -143,6 +169,57 @@ impl Ime {
         }
     }
 
+    pub fn reset(&mut self, window: ffi::Window) -> Result<bool, XError> {
+        if self.is_destroyed() {
+            return Ok(false);
+        }
+        if let Some(&mut Some(ref mut context)) = self.inner.contexts.get_mut(&window) {
+            context.reset(&self.xconn).map(|_| true)
+        } else {
+            Ok(false)
+        }
+    }
+
+    pub fn set_allowed(
+        &mut self,
+        window: ffi::Window,
+        allowed: bool,
+    ) -> Result<bool, ImeContextCreationError> {
+        if self.is_destroyed() {
+            return Ok(false);
+        }
+        let ic_spot = match self.inner.contexts.get(&window) {
+            Some(Some(context)) if context.allowed != allowed => context.ic_spot,
+            _ => return Ok(false),
+        };
+
+        // The input style can't be changed on an existing context, so we replace it. The old one
+        // is only destroyed once the new one exists, so that it's kept if that fails.
+        let context = unsafe {
+            ImeContext::new(
+                &self.inner.xconn,
+                self.inner.im,
+                window,
+                Some(ic_spot),
+                allowed,
+                &self.inner.event_sender,
+            )
+        }?;
+        if let Some(Some(old_context)) = self.inner.contexts.insert(window, Some(context)) {
+            unsafe { self.inner.destroy_ic_if_necessary(old_context.ic) }
+                .map_err(ImeContextCreationError::XError)?;
+        }
+        let focused_window = self
+            .xconn
+            .get_input_focus()
+            .map_err(ImeContextCreationError::XError)?;
+        if focused_window == window {
+            self.focus(window)
+                .map_err(ImeContextCreationError::XError)?;
+        }
+        Ok(true)
+    }
+
     pub fn send_xim_spot(&mut self, window: ffi::Window, x: i16, y: i16) {
         if self.is_destroyed() {
             return;
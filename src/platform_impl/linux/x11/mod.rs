@@ -12,12 +12,13 @@ mod xdisplay;
 
 pub use self::{
     monitor::MonitorHandle,
+    util::CustomCursor,
     window::UnownedWindow,
     xdisplay::{XConnection, XError, XNotSupported},
 };
 
 use std::{
-    cell::RefCell,
+    cell::{Cell, RefCell},
     collections::{HashMap, HashSet, VecDeque},
     ffi::CStr,
     mem,
@@ -33,12 +34,14 @@ use libc::{self, setlocale, LC_CTYPE};
 use self::{
     dnd::{Dnd, DndState},
     event_processor::EventProcessor,
-    ime::{Ime, ImeCreationError, ImeReceiver, ImeSender},
+    ime::{Ime, ImeCreationError, ImeEventReceiver, ImeReceiver, ImeRequest, ImeSender},
 };
 use crate::{
     error::OsError as RootOsError,
-    event::{Event, WindowEvent},
-    event_loop::{ControlFlow, EventLoopClosed, EventLoopWindowTarget as RootELW},
+    event::{AxisId, Event, WindowEvent},
+    event_loop::{
+        ControlFlow, DeviceEventFilter, EventLoopClosed, EventLoopWindowTarget as RootELW,
+    },
     platform_impl::{platform::sticky_exit_callback, PlatformSpecificWindowBuilderAttributes},
     window::WindowAttributes,
 };
@@ -51,6 +54,7 @@ pub struct EventLoopWindowTarget<T> {
     ime: RefCell<Ime>,
     windows: RefCell<HashMap<WindowId, Weak<UnownedWindow>>>,
     pending_redraws: Arc<Mutex<HashSet<WindowId>>>,
+    device_event_filter: Cell<DeviceEventFilter>,
     _marker: ::std::marker::PhantomData<T>,
 }
 
@@ -79,13 +83,14 @@ impl<T: 'static> EventLoop<T> {
             .expect("Failed to call XInternAtoms when initializing drag and drop");
 
         let (ime_sender, ime_receiver) = mpsc::channel();
+        let (ime_event_sender, ime_event_receiver) = mpsc::channel();
         // Input methods will open successfully without setting the locale, but it won't be
         // possible to actually commit pre-edit sequences.
         unsafe {
             setlocale(LC_CTYPE, b"\0".as_ptr() as *const _);
         }
         let ime = RefCell::new({
-            let result = Ime::new(Arc::clone(&xconn));
+            let result = Ime::new(Arc::clone(&xconn), ime_event_sender);
             if let Err(ImeCreationError::OpenFailure(ref state)) = result {
                 panic!(format!("Failed to open input method: {:#?}", state));
             }
@@ -143,6 +148,7 @@ impl<T: 'static> EventLoop<T> {
                 xconn,
                 wm_delete_window,
                 pending_redraws: Default::default(),
+                device_event_filter: Default::default(),
             }),
             _marker: ::std::marker::PhantomData,
         });
@@ -174,7 +180,12 @@ impl<T: 'static> EventLoop<T> {
             devices: Default::default(),
             randr_event_offset,
             ime_receiver,
+            ime_event_receiver,
+            ime_window: None,
             xi2ext,
+            modifiers: Default::default(),
+            focused_window: Default::default(),
+            cursor_window: Default::default(),
         };
 
         // Register for device hotplug events
@@ -268,8 +279,14 @@ impl<T: 'static> EventLoop<T> {
             }
             // Empty the redraw requests
             {
-                let mut guard = wt.pending_redraws.lock().unwrap();
-                for wid in guard.drain() {
+                // The lock mustn't be held while calling back, since the callback may request
+                // another redraw.
+                let pending_redraws = mem::take(&mut *wt.pending_redraws.lock().unwrap());
+                for wid in pending_redraws {
+                    // `Destroyed` has to be the last event of a window.
+                    if !wt.windows.borrow().contains_key(&wid) {
+                        continue;
+                    }
                     sticky_exit_callback(
                         Event::WindowEvent {
                             window_id: crate::window::WindowId(super::WindowId::X(wid)),
@@ -373,6 +390,16 @@ impl<T: 'static> EventLoop<T> {
     }
 }
 
+impl<T> EventLoopWindowTarget<T> {
+    pub fn set_device_event_filter(&self, filter: DeviceEventFilter) {
+        self.device_event_filter.set(filter);
+    }
+
+    pub fn primary_monitor(&self) -> MonitorHandle {
+        self.xconn.primary_monitor()
+    }
+}
+
 fn get_xtarget<T>(rt: &RootELW<T>) -> &EventLoopWindowTarget<T> {
     if let super::EventLoopWindowTarget::X(ref target) = rt.p {
         target
@@ -443,6 +470,39 @@ impl DeviceId {
     pub unsafe fn dummy() -> Self {
         DeviceId(0)
     }
+
+    /// Looks up the label of the valuator numbered `axis`, which is how `AxisMotion` events number
+    /// the axes of the device.
+    pub fn axis_label(&self, xconn: &XConnection, axis: AxisId) -> Option<String> {
+        let device_info = DeviceInfo::get(xconn, self.0)?;
+        let label = device_info
+            .iter()
+            .flat_map(Device::classes)
+            .filter_map(|class_ptr| {
+                let class = unsafe { &**class_ptr };
+                if class._type != ffi::XIValuatorClass {
+                    return None;
+                }
+                let info = unsafe {
+                    mem::transmute::<&ffi::XIAnyClassInfo, &ffi::XIValuatorClassInfo>(class)
+                };
+                Some(info)
+            })
+            .find(|info| info.number as AxisId == axis)?
+            .label;
+        if label == 0 {
+            return None;
+        }
+        unsafe {
+            let name = (xconn.xlib.XGetAtomName)(xconn.display, label);
+            if name.is_null() {
+                return None;
+            }
+            let label = CStr::from_ptr(name).to_string_lossy().into_owned();
+            (xconn.xlib.XFree)(name as _);
+            Some(label)
+        }
+    }
 }
 
 pub struct Window(Arc<UnownedWindow>);
@@ -472,13 +532,7 @@ impl Window {
 
 impl Drop for Window {
     fn drop(&mut self) {
-        let window = self.deref();
-        let xconn = &window.xconn;
-        unsafe {
-            (xconn.xlib.XDestroyWindow)(xconn.display, window.id().0);
-            // If the window was somehow already destroyed, we'll get a `BadWindow` error, which we don't care about.
-            let _ = xconn.check_errors();
-        }
+        self.close();
     }
 }
 
@@ -531,6 +585,8 @@ fn mkdid(w: c_int) -> crate::event::DeviceId {
 struct Device {
     name: String,
     scroll_axes: Vec<(i32, ScrollAxis)>,
+    // Axes reporting a position rather than motion, like those of tablets and joysticks.
+    absolute_axes: Vec<i32>,
     // For master devices, this is the paired device (pointer <-> keyboard).
     // For slave devices, this is the master.
     attachment: c_int,
@@ -553,6 +609,7 @@ impl Device {
     fn new<T: 'static>(el: &EventProcessor<T>, info: &ffi::XIDeviceInfo) -> Self {
         let name = unsafe { CStr::from_ptr(info.name).to_string_lossy() };
         let mut scroll_axes = Vec::new();
+        let mut absolute_axes = Vec::new();
 
         let wt = get_xtarget(&el.target);
 
@@ -589,6 +646,14 @@ impl Device {
                             },
                         ));
                     },
+                    ffi::XIValuatorClass => {
+                        let info = unsafe {
+                            mem::transmute::<&ffi::XIAnyClassInfo, &ffi::XIValuatorClassInfo>(class)
+                        };
+                        if info.mode == ffi::XIModeAbsolute {
+                            absolute_axes.push(info.number);
+                        }
+                    },
                     _ => {},
                 }
             }
@@ -597,6 +662,7 @@ impl Device {
         let mut device = Device {
             name: name.into_owned(),
             scroll_axes,
+            absolute_axes,
             attachment: info.attachment,
         };
         device.reset_scroll_position(info);
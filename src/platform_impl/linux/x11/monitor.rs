@@ -1,4 +1,7 @@
-use std::os::raw::*;
+use std::{
+    hash::{Hash, Hasher},
+    os::raw::*,
+};
 
 use parking_lot::Mutex;
 
@@ -61,6 +64,20 @@ pub struct MonitorHandle {
     video_modes: Vec<VideoMode>,
 }
 
+impl PartialEq for MonitorHandle {
+    fn eq(&self, other: &Self) -> bool {
+        self.id == other.id
+    }
+}
+
+impl Eq for MonitorHandle {}
+
+impl Hash for MonitorHandle {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.id.hash(state);
+    }
+}
+
 impl MonitorHandle {
     fn from_repr(
         xconn: &XConnection,
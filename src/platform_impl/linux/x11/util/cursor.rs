@@ -0,0 +1,81 @@
+use std::{fmt, slice, sync::Arc};
+
+use super::*;
+use crate::window::BadCursor;
+
+/// A cursor created from RGBA data, which stays alive for as long as any clone of it does.
+#[derive(Clone)]
+pub struct CustomCursor {
+    inner: Arc<CursorHandle>,
+}
+
+struct CursorHandle {
+    xconn: Arc<XConnection>,
+    cursor: ffi::Cursor,
+}
+
+impl CustomCursor {
+    pub fn from_rgba(
+        xconn: &Arc<XConnection>,
+        rgba: &[u8],
+        width: u32,
+        height: u32,
+        hotspot_x: u32,
+        hotspot_y: u32,
+    ) -> Result<Self, BadCursor> {
+        let cursor = unsafe {
+            let image = (xconn.xcursor.XcursorImageCreate)(width as c_int, height as c_int);
+            if image.is_null() {
+                return Err(BadCursor::CreationFailed);
+            }
+            (*image).xhot = hotspot_x;
+            (*image).yhot = hotspot_y;
+            (*image).delay = 0;
+
+            // Xcursor expects premultiplied ARGB pixels.
+            let pixels = slice::from_raw_parts_mut((*image).pixels, (width * height) as usize);
+            for (pixel, rgba) in pixels.iter_mut().zip(rgba.chunks(4)) {
+                let alpha = rgba[3] as u32;
+                let premultiply = |channel: u8| channel as u32 * alpha / 255;
+                *pixel = (alpha << 24)
+                    | (premultiply(rgba[0]) << 16)
+                    | (premultiply(rgba[1]) << 8)
+                    | premultiply(rgba[2]);
+            }
+
+            let cursor = (xconn.xcursor.XcursorImageLoadCursor)(xconn.display, image);
+            (xconn.xcursor.XcursorImageDestroy)(image);
+            cursor
+        };
+        if cursor == 0 {
+            return Err(BadCursor::CreationFailed);
+        }
+        Ok(CustomCursor {
+            inner: Arc::new(CursorHandle {
+                xconn: Arc::clone(xconn),
+                cursor,
+            }),
+        })
+    }
+
+    pub fn cursor(&self) -> ffi::Cursor {
+        self.inner.cursor
+    }
+}
+
+impl fmt::Debug for CustomCursor {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("CustomCursor")
+            .field("cursor", &self.inner.cursor)
+            .finish()
+    }
+}
+
+impl Drop for CursorHandle {
+    fn drop(&mut self) {
+        unsafe {
+            (self.xconn.xlib.XFreeCursor)(self.xconn.display, self.cursor);
+        }
+        let _ = self.xconn.flush_requests();
+    }
+}
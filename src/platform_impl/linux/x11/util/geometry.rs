@@ -39,6 +39,31 @@ impl AaRect {
         );
         x_overlap * y_overlap
     }
+
+    pub fn intersection(&self, other: &Self) -> Option<Self> {
+        let x = cmp::max(self.x, other.x);
+        let y = cmp::max(self.y, other.y);
+        let width = cmp::min(self.x + self.width, other.x + other.width) - x;
+        let height = cmp::min(self.y + self.height, other.y + other.height) - y;
+        if width > 0 && height > 0 {
+            Some(AaRect {
+                x,
+                y,
+                width,
+                height,
+            })
+        } else {
+            None
+        }
+    }
+
+    pub fn position(&self) -> (i32, i32) {
+        (self.x as i32, self.y as i32)
+    }
+
+    pub fn size(&self) -> (u32, u32) {
+        (self.width as u32, self.height as u32)
+    }
 }
 
 #[derive(Debug)]
@@ -248,6 +273,31 @@ impl XConnection {
         })
     }
 
+    // The EWMH only gives a single work area per desktop, spanning all the monitors, so it has to be
+    // intersected with a monitor to get the part of that monitor that isn't covered by panels.
+    pub fn get_work_area(&self, root: ffi::Window) -> Option<AaRect> {
+        let work_area_atom = unsafe { self.get_atom_unchecked(b"_NET_WORKAREA\0") };
+        let current_desktop_atom = unsafe { self.get_atom_unchecked(b"_NET_CURRENT_DESKTOP\0") };
+
+        if !hint_is_supported(work_area_atom) {
+            return None;
+        }
+
+        let desktop = self
+            .get_property::<c_ulong>(root, current_desktop_atom, ffi::XA_CARDINAL)
+            .ok()
+            .and_then(|desktop| desktop.first().cloned())
+            .unwrap_or(0) as usize;
+        let work_areas: Vec<c_ulong> = self
+            .get_property(root, work_area_atom, ffi::XA_CARDINAL)
+            .ok()?;
+        let work_area = work_areas.get(desktop * 4..desktop * 4 + 4)?;
+        Some(AaRect::new(
+            (work_area[0] as i32, work_area[1] as i32),
+            (work_area[2] as u32, work_area[3] as u32),
+        ))
+    }
+
     pub fn is_top_level(&self, window: ffi::Window, root: ffi::Window) -> Option<bool> {
         let client_list_atom = unsafe { self.get_atom_unchecked(b"_NET_CLIENT_LIST\0") };
 
@@ -316,11 +366,11 @@ impl XConnection {
         // With rare exceptions, this is the position of a nested window. Cases where the window
         // isn't nested are outlined in the comments throghout this function, but in addition to
         // that, fullscreen windows often aren't nested.
-        let (inner_y_rel_root, child) = {
+        let (inner_x_rel_root, inner_y_rel_root, child) = {
             let coords = self
                 .translate_coords(window, root)
                 .expect("Failed to translate window coordinates");
-            (coords.y_rel_root, coords.child)
+            (coords.x_rel_root, coords.y_rel_root, coords.child)
         };
 
         let (width, height, border) = {
@@ -380,11 +430,12 @@ impl XConnection {
             let outer_window = self
                 .climb_hierarchy(window, root)
                 .expect("Failed to climb window hierarchy");
-            let (outer_y, outer_width, outer_height) = {
+            let (outer_x, outer_y, outer_width, outer_height) = {
                 let outer_geometry = self
                     .get_geometry(outer_window)
                     .expect("Failed to get outer window geometry");
                 (
+                    outer_geometry.x_rel_parent,
                     outer_geometry.y_rel_parent,
                     outer_geometry.width,
                     outer_geometry.height,
@@ -392,13 +443,15 @@ impl XConnection {
             };
 
             // Since we have the geometry of the outermost window and the geometry of the client
-            // area, we can figure out what's in between.
+            // area, we can figure out what's in between. The frame isn't necessarily as wide on
+            // the left as on the right, so both offsets are taken from the positions.
             let diff_x = outer_width.saturating_sub(width);
             let diff_y = outer_height.saturating_sub(height);
+            let offset_x = inner_x_rel_root.saturating_sub(outer_x) as c_uint;
             let offset_y = inner_y_rel_root.saturating_sub(outer_y) as c_uint;
 
-            let left = diff_x / 2;
-            let right = left;
+            let left = offset_x;
+            let right = diff_x.saturating_sub(offset_x);
             let top = offset_y;
             let bottom = diff_y.saturating_sub(offset_y);
 
@@ -41,6 +41,16 @@ impl<'a> PointerState<'a> {
     pub fn get_modifier_state(&self) -> ModifiersState {
         self.modifiers.into()
     }
+
+    /// Whether the given core button (e.g. `ffi::Button1`) is currently held down.
+    pub fn is_button_down(&self, button: c_uint) -> bool {
+        let byte = (button / 8) as c_int;
+        if self.buttons.mask.is_null() || byte >= self.buttons.mask_len {
+            return false;
+        }
+        let mask = unsafe { *self.buttons.mask.offset(byte as isize) };
+        mask & (1 << (button % 8)) != 0
+    }
 }
 
 impl<'a> Drop for PointerState<'a> {
@@ -119,6 +129,15 @@ impl XConnection {
         }
     }
 
+    pub fn get_input_focus(&self) -> Result<ffi::Window, XError> {
+        let mut focus: ffi::Window = 0;
+        let mut revert_to: c_int = 0;
+        unsafe {
+            (self.xlib.XGetInputFocus)(self.display, &mut focus, &mut revert_to);
+        }
+        self.check_errors().map(|_| focus)
+    }
+
     fn lookup_utf8_inner(
         &self,
         ic: ffi::XIC,
@@ -3,6 +3,7 @@
 
 mod atom;
 mod client_msg;
+mod cursor;
 mod format;
 mod geometry;
 mod hint;
@@ -14,8 +15,8 @@ mod window_property;
 mod wm;
 
 pub use self::{
-    atom::*, client_msg::*, format::*, geometry::*, hint::*, icon::*, input::*, memory::*,
-    randr::*, window_property::*, wm::*,
+    atom::*, client_msg::*, cursor::*, format::*, geometry::*, hint::*, icon::*, input::*,
+    memory::*, randr::*, window_property::*, wm::*,
 };
 
 use std::{mem, ops::BitAnd, os::raw::*, ptr};
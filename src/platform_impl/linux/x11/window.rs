@@ -1,4 +1,14 @@
-use std::{cmp, collections::HashSet, env, ffi::CString, mem, os::raw::*, path::Path, sync::Arc};
+use std::{
+    cmp,
+    collections::HashSet,
+    env,
+    ffi::{CStr, CString},
+    mem,
+    os::raw::*,
+    path::Path,
+    ptr,
+    sync::Arc,
+};
 
 use libc;
 use parking_lot::Mutex;
@@ -11,10 +21,15 @@ use crate::{
         x11::{ime::ImeContextCreationError, MonitorHandle as X11MonitorHandle},
         MonitorHandle as PlatformMonitorHandle, OsError, PlatformSpecificWindowBuilderAttributes,
     },
-    window::{CursorIcon, Icon, WindowAttributes},
+    window::{
+        clamp_inner_size, fit_placement, BadCursor, CursorIcon, FullscreenType, Icon,
+        PlacementArea, Rect, ResizeDirection, UserAttentionType, WindowAttributes, WindowPlacement,
+    },
 };
 
-use super::{ffi, util, EventLoopWindowTarget, ImeSender, WindowId, XConnection, XError};
+use super::{
+    ffi, util, EventLoopWindowTarget, ImeRequest, ImeSender, WindowId, XConnection, XError,
+};
 
 unsafe extern "C" fn visibility_predicate(
     _display: *mut ffi::Display,
@@ -36,18 +51,40 @@ pub struct SharedState {
     pub guessed_dpi: Option<f64>,
     pub last_monitor: Option<X11MonitorHandle>,
     pub dpi_adjusted: Option<(f64, f64)>,
-    pub fullscreen: Option<RootMonitorHandle>,
+    // `Borderless(None)` is resolved to the monitor the window was on when entering fullscreen.
+    pub fullscreen: Option<FullscreenType>,
     // Used to restore position after exiting fullscreen.
     pub restore_position: Option<(i32, i32)>,
+    // Whether `_NET_WM_STATE` contained `_NET_WM_STATE_FULLSCREEN` when we last looked.
+    pub wm_fullscreen: bool,
     pub frame_extents: Option<util::FrameExtentsHeuristic>,
     pub min_inner_size: Option<LogicalSize>,
     pub max_inner_size: Option<LogicalSize>,
+    // While this is `false`, the size hints pin the window to its current size.
+    pub resizable: bool,
+    // What was last asked for, since window managers don't report whether they comply.
+    pub decorations: bool,
+    pub visibility: Visibility,
+    // The state last reported through `Occluded`.
+    pub occluded: bool,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum Visibility {
+    #[default]
+    No,
+    Yes,
+    // Mapped, but the `MapNotify` hasn't been processed yet.
+    YesWait,
+    // Destroyed by another client.
+    Destroyed,
 }
 
 impl SharedState {
     fn new(dpi_factor: f64) -> Mutex<Self> {
         let mut shared_state = SharedState::default();
         shared_state.guessed_dpi = Some(dpi_factor);
+        shared_state.resizable = true;
         Mutex::new(shared_state)
     }
 }
@@ -61,8 +98,13 @@ pub struct UnownedWindow {
     root: ffi::Window,           // never changes
     screen_id: i32,              // never changes
     cursor: Mutex<CursorIcon>,
+    custom_cursor: Mutex<Option<util::CustomCursor>>,
     cursor_grabbed: Mutex<bool>,
+    /// Whether `set_keyboard_grab(true)` is in effect. This stays set while the window manager
+    /// temporarily holds the keyboard, so that the grab can be taken back on `FocusIn`.
+    keyboard_grabbed: Mutex<bool>,
     cursor_visible: Mutex<bool>,
+    enabled: Mutex<bool>,
     ime_sender: Mutex<ImeSender>,
     pub shared_state: Mutex<SharedState>,
     pending_redraws: Arc<::std::sync::Mutex<HashSet<WindowId>>>,
@@ -163,7 +205,8 @@ impl UnownedWindow {
                 | ffi::KeymapStateMask
                 | ffi::ButtonPressMask
                 | ffi::ButtonReleaseMask
-                | ffi::PointerMotionMask;
+                | ffi::PointerMotionMask
+                | ffi::PropertyChangeMask;
             swa.border_pixel = 0;
             swa.override_redirect = pl_attribs.override_redirect as c_int;
             swa
@@ -213,8 +256,11 @@ impl UnownedWindow {
             root,
             screen_id,
             cursor: Default::default(),
+            custom_cursor: Mutex::new(None),
             cursor_grabbed: Mutex::new(false),
+            keyboard_grabbed: Mutex::new(false),
             cursor_visible: Mutex::new(true),
+            enabled: Mutex::new(true),
             ime_sender: Mutex::new(event_loop.ime_sender.clone()),
             shared_state: SharedState::new(dpi_factor),
             pending_redraws: event_loop.pending_redraws.clone(),
@@ -227,6 +273,7 @@ impl UnownedWindow {
         window
             .set_decorations_inner(window_attrs.decorations)
             .queue();
+        window.shared_state.lock().decorations = window_attrs.decorations;
 
         {
             // Enable drag and drop (TODO: extend API to make this toggleable)
@@ -308,6 +355,7 @@ impl UnownedWindow {
                         let mut shared_state_lock = window.shared_state.lock();
                         shared_state_lock.min_inner_size = window_attrs.min_inner_size;
                         shared_state_lock.max_inner_size = window_attrs.max_inner_size;
+                        shared_state_lock.resizable = false;
                     }
                 }
 
@@ -335,6 +383,10 @@ impl UnownedWindow {
                 );
             } //.queue();
 
+            if !window_attrs.active {
+                window.set_inactive_hints().queue();
+            }
+
             // Set visibility (map window)
             if window_attrs.visible {
                 unsafe {
@@ -395,17 +447,18 @@ impl UnownedWindow {
                 window.set_maximized_inner(window_attrs.maximized).queue();
             }
             if window_attrs.fullscreen.is_some() {
-                window
-                    .set_fullscreen_inner(window_attrs.fullscreen.clone())
-                    .queue();
+                window.set_fullscreen(window_attrs.fullscreen.clone());
             }
             if window_attrs.always_on_top {
                 window
                     .set_always_on_top_inner(window_attrs.always_on_top)
                     .queue();
             }
+            if window_attrs.skip_taskbar {
+                window.set_skip_taskbar_inner(true).queue();
+            }
 
-            if window_attrs.visible {
+            if window_attrs.visible && window_attrs.active {
                 unsafe {
                     // XSetInputFocus generates an error if the window is not visible, so we wait
                     // until we receive VisibilityNotify.
@@ -425,6 +478,9 @@ impl UnownedWindow {
                     );
                 }
             }
+            if window_attrs.visible {
+                window.shared_state.lock().visibility = Visibility::Yes;
+            }
         }
 
         // We never want to give the user a broken window, since by then, it's too late to handle.
@@ -493,6 +549,27 @@ impl UnownedWindow {
         )
     }
 
+    // Without the input hint, the window manager doesn't give the window keyboard focus. A user
+    // time of 0 additionally asks it not to activate the window when it's mapped.
+    fn set_inactive_hints(&self) -> util::Flusher<'_> {
+        let mut wm_hints = self
+            .xconn
+            .get_wm_hints(self.xwindow)
+            .expect("`XGetWMHints` failed");
+        wm_hints.flags |= ffi::InputHint;
+        wm_hints.input = ffi::False;
+        self.xconn.set_wm_hints(self.xwindow, wm_hints).queue();
+
+        let user_time_atom = unsafe { self.xconn.get_atom_unchecked(b"_NET_WM_USER_TIME\0") };
+        self.xconn.change_property(
+            self.xwindow,
+            user_time_atom,
+            ffi::XA_CARDINAL,
+            util::PropMode::Replace,
+            &[0 as util::Cardinal],
+        )
+    }
+
     fn set_gtk_theme_variant(&self, variant: String) -> util::Flusher<'_> {
         let hint_atom = unsafe { self.xconn.get_atom_unchecked(b"_GTK_THEME_VARIANT\0") };
         let utf8_atom = unsafe { self.xconn.get_atom_unchecked(b"UTF8_STRING\0") };
@@ -508,6 +585,9 @@ impl UnownedWindow {
 
     #[inline]
     pub fn set_urgent(&self, is_urgent: bool) {
+        if self.is_destroyed() {
+            return;
+        }
         let mut wm_hints = self
             .xconn
             .get_wm_hints(self.xwindow)
@@ -523,6 +603,23 @@ impl UnownedWindow {
             .expect("Failed to set urgency hint");
     }
 
+    #[inline]
+    pub fn request_user_attention(&self, request_type: Option<UserAttentionType>) {
+        if self.is_destroyed() {
+            return;
+        }
+        let demands_attention_atom = unsafe {
+            self.xconn
+                .get_atom_unchecked(b"_NET_WM_STATE_DEMANDS_ATTENTION\0")
+        };
+        self.set_netwm(
+            request_type.is_some().into(),
+            (demands_attention_atom as c_long, 0, 0, 0),
+        )
+        .flush()
+        .expect("Failed to set demands-attention state");
+    }
+
     fn set_netwm(
         &self,
         operation: util::StateOperation,
@@ -550,8 +647,10 @@ impl UnownedWindow {
         self.set_netwm(fullscreen.into(), (fullscreen_atom as c_long, 0, 0, 0))
     }
 
-    fn set_fullscreen_inner(&self, monitor: Option<RootMonitorHandle>) -> util::Flusher<'_> {
-        match monitor {
+    fn set_fullscreen_inner(&self, fullscreen: Option<FullscreenType>) -> util::Flusher<'_> {
+        // There's no portable way to take exclusive control of a monitor, so both kinds of
+        // fullscreen are requested from the window manager the same way.
+        match fullscreen.as_ref().and_then(|f| f.monitor()) {
             None => {
                 let flusher = self.set_fullscreen_hint(false);
                 if let Some(position) = self.shared_state.lock().restore_position.take() {
@@ -559,8 +658,8 @@ impl UnownedWindow {
                 }
                 flusher
             },
-            Some(RootMonitorHandle {
-                inner: PlatformMonitorHandle::X(monitor),
+            Some(&RootMonitorHandle {
+                inner: PlatformMonitorHandle::X(ref monitor),
             }) => {
                 let window_position = self.outer_position_physical();
                 self.shared_state.lock().restore_position = Some(window_position);
@@ -574,14 +673,61 @@ impl UnownedWindow {
     }
 
     #[inline]
-    pub fn fullscreen(&self) -> Option<RootMonitorHandle> {
+    pub fn fullscreen(&self) -> Option<FullscreenType> {
         self.shared_state.lock().fullscreen.clone()
     }
 
+    /// Updates the tracked fullscreen state when the window manager toggles fullscreen on its own,
+    /// e.g. through a keyboard shortcut.
+    pub(crate) fn refresh_fullscreen_state(&self) {
+        let state_atom = unsafe { self.xconn.get_atom_unchecked(b"_NET_WM_STATE\0") };
+        let fullscreen_atom =
+            unsafe { self.xconn.get_atom_unchecked(b"_NET_WM_STATE_FULLSCREEN\0") };
+        let is_fullscreen = self
+            .xconn
+            .get_property::<ffi::Atom>(self.xwindow, state_atom, ffi::XA_ATOM)
+            .map(|atoms| atoms.contains(&fullscreen_atom))
+            .unwrap_or(false);
+
+        // `_NET_WM_STATE` also changes for unrelated reasons, possibly before the window manager
+        // has gotten around to one of our own requests, so we only act on actual transitions.
+        let mut shared_state_lock = self.shared_state.lock();
+        if is_fullscreen == shared_state_lock.wm_fullscreen {
+            return;
+        }
+        shared_state_lock.wm_fullscreen = is_fullscreen;
+        if is_fullscreen == shared_state_lock.fullscreen.is_some() {
+            return;
+        }
+        if is_fullscreen {
+            drop(shared_state_lock);
+            let monitor = RootMonitorHandle {
+                inner: PlatformMonitorHandle::X(self.current_monitor()),
+            };
+            self.shared_state.lock().fullscreen = Some(FullscreenType::Borderless(Some(monitor)));
+        } else {
+            shared_state_lock.fullscreen = None;
+            shared_state_lock.restore_position = None;
+        }
+    }
+
     #[inline]
-    pub fn set_fullscreen(&self, monitor: Option<RootMonitorHandle>) {
-        self.shared_state.lock().fullscreen = monitor.clone();
-        self.set_fullscreen_inner(monitor)
+    pub fn set_fullscreen(&self, fullscreen: Option<FullscreenType>) {
+        if self.is_destroyed() {
+            return;
+        }
+        let fullscreen = fullscreen.map(|fullscreen| {
+            match fullscreen {
+                FullscreenType::Borderless(None) => {
+                    FullscreenType::Borderless(Some(RootMonitorHandle {
+                        inner: PlatformMonitorHandle::X(self.current_monitor()),
+                    }))
+                },
+                fullscreen => fullscreen,
+            }
+        });
+        self.shared_state.lock().fullscreen = fullscreen.clone();
+        self.set_fullscreen_inner(fullscreen)
             .flush()
             .expect("Failed to change window fullscreen state");
         self.invalidate_cached_frame_extents();
@@ -607,6 +753,16 @@ impl UnownedWindow {
         })
     }
 
+    /// Like `current_monitor`, but `None` if the window doesn't overlap any monitor.
+    pub fn visible_monitor(&self) -> Option<X11MonitorHandle> {
+        let monitor = self.current_monitor();
+        if self.get_rect().get_overlapping_area(&monitor.rect) > 0 {
+            Some(monitor)
+        } else {
+            None
+        }
+    }
+
     pub fn available_monitors(&self) -> Vec<X11MonitorHandle> {
         self.xconn.available_monitors()
     }
@@ -632,6 +788,9 @@ impl UnownedWindow {
 
     #[inline]
     pub fn set_maximized(&self, maximized: bool) {
+        if self.is_destroyed() {
+            return;
+        }
         self.set_maximized_inner(maximized)
             .flush()
             .expect("Failed to change window maximization");
@@ -660,11 +819,40 @@ impl UnownedWindow {
 
     #[inline]
     pub fn set_title(&self, title: &str) {
+        if self.is_destroyed() {
+            return;
+        }
         self.set_title_inner(title)
             .flush()
             .expect("Failed to set window title");
     }
 
+    pub fn get_title(&self) -> String {
+        if self.is_destroyed() {
+            return String::new();
+        }
+        let wm_name_atom = unsafe { self.xconn.get_atom_unchecked(b"_NET_WM_NAME\0") };
+        let utf8_atom = unsafe { self.xconn.get_atom_unchecked(b"UTF8_STRING\0") };
+        let title: Result<Vec<c_uchar>, _> =
+            self.xconn
+                .get_property(self.xwindow, wm_name_atom, utf8_atom);
+        match title {
+            Ok(ref title) if !title.is_empty() => String::from_utf8_lossy(title).into_owned(),
+            // The title may have been changed by someone who only set `WM_NAME`
+            _ => unsafe {
+                let mut name: *mut c_char = ptr::null_mut();
+                (self.xconn.xlib.XFetchName)(self.xconn.display, self.xwindow, &mut name);
+                if name.is_null() {
+                    String::new()
+                } else {
+                    let title = CStr::from_ptr(name).to_string_lossy().into_owned();
+                    (self.xconn.xlib.XFree)(name as *mut _);
+                    title
+                }
+            },
+        }
+    }
+
     fn set_decorations_inner(&self, decorations: bool) -> util::Flusher<'_> {
         let wm_hints = unsafe { self.xconn.get_atom_unchecked(b"_MOTIF_WM_HINTS\0") };
         self.xconn.change_property(
@@ -684,12 +872,26 @@ impl UnownedWindow {
 
     #[inline]
     pub fn set_decorations(&self, decorations: bool) {
+        if self.is_destroyed() {
+            return;
+        }
         self.set_decorations_inner(decorations)
             .flush()
             .expect("Failed to set decoration state");
+        self.shared_state.lock().decorations = decorations;
         self.invalidate_cached_frame_extents();
     }
 
+    #[inline]
+    pub fn get_resizable(&self) -> bool {
+        self.shared_state.lock().resizable
+    }
+
+    #[inline]
+    pub fn get_decorations(&self) -> bool {
+        self.shared_state.lock().decorations
+    }
+
     fn set_always_on_top_inner(&self, always_on_top: bool) -> util::Flusher<'_> {
         let above_atom = unsafe { self.xconn.get_atom_unchecked(b"_NET_WM_STATE_ABOVE\0") };
         self.set_netwm(always_on_top.into(), (above_atom as c_long, 0, 0, 0))
@@ -697,11 +899,139 @@ impl UnownedWindow {
 
     #[inline]
     pub fn set_always_on_top(&self, always_on_top: bool) {
+        if self.is_destroyed() {
+            return;
+        }
         self.set_always_on_top_inner(always_on_top)
             .flush()
             .expect("Failed to set always-on-top state");
     }
 
+    fn set_skip_taskbar_inner(&self, skip: bool) -> util::Flusher<'_> {
+        let skip_taskbar_atom = unsafe {
+            self.xconn
+                .get_atom_unchecked(b"_NET_WM_STATE_SKIP_TASKBAR\0")
+        };
+        let skip_pager_atom =
+            unsafe { self.xconn.get_atom_unchecked(b"_NET_WM_STATE_SKIP_PAGER\0") };
+        self.set_netwm(
+            skip.into(),
+            (skip_taskbar_atom as c_long, skip_pager_atom as c_long, 0, 0),
+        )
+    }
+
+    #[inline]
+    pub fn set_skip_taskbar(&self, skip: bool) {
+        if self.is_destroyed() {
+            return;
+        }
+        self.set_skip_taskbar_inner(skip)
+            .flush()
+            .expect("Failed to set skip-taskbar state");
+    }
+
+    #[inline]
+    pub fn set_opacity(&self, opacity: f32) {
+        if self.is_destroyed() {
+            return;
+        }
+        let opacity_atom = unsafe { self.xconn.get_atom_unchecked(b"_NET_WM_WINDOW_OPACITY\0") };
+        // The opacity is a fraction of 0xFFFFFFFF.
+        let opacity = (f64::from(opacity) * f64::from(0xFFFF_FFFFu32)) as u32;
+        self.xconn
+            .change_property(
+                self.xwindow,
+                opacity_atom,
+                ffi::XA_CARDINAL,
+                util::PropMode::Replace,
+                &[opacity as util::Cardinal],
+            )
+            .flush()
+            .expect("Failed to set window opacity");
+    }
+
+    pub fn set_shape(&self, shape: Option<&[Rect]>) -> Result<(), ExternalError> {
+        let xfixes = match self.xconn.xfixes {
+            Some(ref xfixes) => xfixes,
+            None => return Err(ExternalError::NotSupported(NotSupportedError::new())),
+        };
+        let region = match shape {
+            Some(shape) => {
+                let dpi_factor = self.hidpi_factor();
+                let mut rectangles: Vec<_> = shape
+                    .iter()
+                    .map(|rect| {
+                        let position = rect.position.to_physical(dpi_factor);
+                        let size = rect.size.to_physical(dpi_factor);
+                        let (x, y): (i32, i32) = position.into();
+                        let (width, height): (u32, u32) = size.into();
+                        ffi::XRectangle {
+                            x: x as c_short,
+                            y: y as c_short,
+                            width: width as c_ushort,
+                            height: height as c_ushort,
+                        }
+                    })
+                    .collect();
+                unsafe {
+                    (xfixes.XFixesCreateRegion)(
+                        self.xconn.display,
+                        rectangles.as_mut_ptr(),
+                        rectangles.len() as c_int,
+                    )
+                }
+            },
+            // Shaping a window with no region resets it to its full rectangle.
+            None => 0,
+        };
+        unsafe {
+            // The bounding shape clips input as well as drawing.
+            (xfixes.XFixesSetWindowShapeRegion)(
+                self.xconn.display,
+                self.xwindow,
+                ffi::SHAPE_BOUNDING,
+                0,
+                0,
+                region,
+            );
+            if region != 0 {
+                (xfixes.XFixesDestroyRegion)(self.xconn.display, region);
+            }
+        }
+        self.xconn
+            .flush_requests()
+            .map_err(|err| ExternalError::Os(os_error!(OsError::XError(err))))
+    }
+
+    pub fn set_cursor_hittest(&self, hittest: bool) -> Result<(), ExternalError> {
+        let xfixes = match self.xconn.xfixes {
+            Some(ref xfixes) => xfixes,
+            None => return Err(ExternalError::NotSupported(NotSupportedError::new())),
+        };
+        unsafe {
+            // An empty input shape lets all pointer events through, while no input shape at all
+            // makes it follow the bounding shape again.
+            let region = match hittest {
+                true => 0,
+                false => (xfixes.XFixesCreateRegion)(self.xconn.display, ptr::null_mut(), 0),
+            };
+            (xfixes.XFixesSetWindowShapeRegion)(
+                self.xconn.display,
+                self.xwindow,
+                ffi::SHAPE_INPUT,
+                0,
+                0,
+                region,
+            );
+            if region != 0 {
+                (xfixes.XFixesDestroyRegion)(self.xconn.display, region);
+            }
+        }
+        self.xconn
+            .flush_requests()
+            .map_err(|err| ExternalError::Os(os_error!(OsError::XError(err))))
+    }
+
     fn set_icon_inner(&self, icon: Icon) -> util::Flusher<'_> {
         let icon_atom = unsafe { self.xconn.get_atom_unchecked(b"_NET_WM_ICON\0") };
         let data = icon.to_cardinals();
@@ -728,6 +1058,9 @@ impl UnownedWindow {
 
     #[inline]
     pub fn set_window_icon(&self, icon: Option<Icon>) {
+        if self.is_destroyed() {
+            return;
+        }
         match icon {
             Some(icon) => self.set_icon_inner(icon),
             None => self.unset_icon_inner(),
@@ -738,22 +1071,90 @@ impl UnownedWindow {
 
     #[inline]
     pub fn set_visible(&self, visible: bool) {
+        let mut shared_state = self.shared_state.lock();
+        match (visible, shared_state.visibility) {
+            (_, Visibility::Destroyed)
+            | (true, Visibility::Yes)
+            | (true, Visibility::YesWait)
+            | (false, Visibility::No) => return,
+            _ => (),
+        }
+
         match visible {
             true => unsafe {
                 (self.xconn.xlib.XMapRaised)(self.xconn.display, self.xwindow);
                 self.xconn
                     .flush_requests()
                     .expect("Failed to call XMapRaised");
+                shared_state.visibility = Visibility::YesWait;
             },
             false => unsafe {
+                // The server would drop the grab once the window is unmapped anyway, but we
+                // don't want to take it back when the window is shown again.
+                self.release_keyboard_grab();
                 (self.xconn.xlib.XUnmapWindow)(self.xconn.display, self.xwindow);
                 self.xconn
                     .flush_requests()
                     .expect("Failed to call XUnmapWindow");
+                shared_state.visibility = Visibility::No;
             },
         }
     }
 
+    pub fn is_focused(&self) -> bool {
+        if self.is_destroyed() {
+            return false;
+        }
+        self.xconn
+            .get_input_focus()
+            .map(|focus| focus == self.xwindow)
+            .unwrap_or(false)
+    }
+
+    #[inline]
+    pub fn is_visible(&self) -> Option<bool> {
+        match self.shared_state.lock().visibility {
+            Visibility::Destroyed => None,
+            visibility => Some(visibility == Visibility::Yes),
+        }
+    }
+
+    #[inline]
+    pub fn close(&self) {
+        let mut shared_state = self.shared_state.lock();
+        if shared_state.visibility == Visibility::Destroyed {
+            return;
+        }
+        // Cache the geometry while the window can still be queried, so that it stays available
+        // afterwards.
+        if let Ok(coords) = self.xconn.translate_coords(self.xwindow, self.root) {
+            shared_state.inner_position = Some((coords.x_rel_root, coords.y_rel_root));
+        }
+        if let Ok(geo) = self.xconn.get_geometry(self.xwindow) {
+            shared_state.size = Some((geo.width, geo.height));
+        }
+        if shared_state.frame_extents.is_none() {
+            shared_state.frame_extents = Some(
+                self.xconn
+                    .get_frame_extents_heuristic(self.xwindow, self.root),
+            );
+        }
+        self.release_keyboard_grab();
+        unsafe {
+            (self.xconn.xlib.XDestroyWindow)(self.xconn.display, self.xwindow);
+            // If the window was somehow already destroyed, we'll get a `BadWindow` error, which we don't care about.
+            let _ = self.xconn.check_errors();
+        }
+        shared_state.visibility = Visibility::Destroyed;
+    }
+
+    // Any request made against a destroyed window fails with `BadWindow`, so setters turn into
+    // no-ops and getters fall back to the last known state.
+    #[inline]
+    fn is_destroyed(&self) -> bool {
+        self.shared_state.lock().visibility == Visibility::Destroyed
+    }
+
     fn update_cached_frame_extents(&self) {
         let extents = self
             .xconn
@@ -762,7 +1163,10 @@ impl UnownedWindow {
     }
 
     pub(crate) fn invalidate_cached_frame_extents(&self) {
-        (*self.shared_state.lock()).frame_extents.take();
+        let mut shared_state = self.shared_state.lock();
+        if shared_state.visibility != Visibility::Destroyed {
+            shared_state.frame_extents.take();
+        }
     }
 
     pub(crate) fn outer_position_physical(&self) -> (i32, i32) {
@@ -789,6 +1193,9 @@ impl UnownedWindow {
     }
 
     pub(crate) fn inner_position_physical(&self) -> (i32, i32) {
+        if self.is_destroyed() {
+            return self.shared_state.lock().inner_position.unwrap_or_default();
+        }
         // This should be okay to unwrap since the only error XTranslateCoordinates can return
         // is BadWindow, and if the window handle is bad we have bigger problems.
         self.xconn
@@ -829,11 +1236,17 @@ impl UnownedWindow {
 
     #[inline]
     pub fn set_outer_position(&self, logical_position: LogicalPosition) {
+        if self.is_destroyed() {
+            return;
+        }
         let (x, y) = logical_position.to_physical(self.hidpi_factor()).into();
         self.set_position_physical(x, y);
     }
 
     pub(crate) fn inner_size_physical(&self) -> (u32, u32) {
+        if self.is_destroyed() {
+            return self.shared_state.lock().size.unwrap_or_default();
+        }
         // This should be okay to unwrap since the only error XGetGeometry can return
         // is BadWindow, and if the window handle is bad we have bigger problems.
         self.xconn
@@ -884,10 +1297,42 @@ impl UnownedWindow {
     }
 
     #[inline]
-    pub fn set_inner_size(&self, logical_size: LogicalSize) {
+    pub fn set_inner_size(&self, logical_size: LogicalSize) -> Option<LogicalSize> {
+        if self.is_destroyed() {
+            return None;
+        }
         let dpi_factor = self.hidpi_factor();
+        let inner_size = self.inner_size_physical();
+        let available = {
+            let (outer_width, outer_height) = self.outer_size_physical();
+            let (monitor_width, monitor_height): (u32, u32) = self.current_monitor().size().into();
+            let width = monitor_width.saturating_sub(outer_width - inner_size.0);
+            let height = monitor_height.saturating_sub(outer_height - inner_size.1);
+            LogicalSize::from_physical((width, height), dpi_factor)
+        };
+        let (min_size, max_size) = {
+            let shared_state_lock = self.shared_state.lock();
+            (
+                shared_state_lock.min_inner_size,
+                shared_state_lock.max_inner_size,
+            )
+        };
+        let logical_size = clamp_inner_size(logical_size, min_size, max_size, Some(available));
         let (width, height) = logical_size.to_physical(dpi_factor).into();
+        if (width, height) == inner_size {
+            return Some(logical_size);
+        }
+        if !self.shared_state.lock().resizable {
+            // Otherwise the window manager would keep the window at the size it was pinned to.
+            self.update_normal_hints(|normal_hints| {
+                normal_hints.set_min_size(Some((width, height)));
+                normal_hints.set_max_size(Some((width, height)));
+            })
+            .expect("Failed to call `XSetWMNormalHints`");
+        }
+        // The `ConfigureNotify` for the new size is what produces the `Resized` event.
         self.set_inner_size_physical(width, height);
+        Some(logical_size)
     }
 
     fn update_normal_hints<F>(&self, callback: F) -> Result<(), XError>
@@ -908,10 +1353,21 @@ impl UnownedWindow {
 
     #[inline]
     pub fn set_min_inner_size(&self, logical_dimensions: Option<LogicalSize>) {
-        self.shared_state.lock().min_inner_size = logical_dimensions;
-        let physical_dimensions = logical_dimensions
-            .map(|logical_dimensions| logical_dimensions.to_physical(self.hidpi_factor()).into());
-        self.set_min_inner_size_physical(physical_dimensions);
+        if self.is_destroyed() {
+            return;
+        }
+        let resizable = {
+            let mut shared_state_lock = self.shared_state.lock();
+            shared_state_lock.min_inner_size = logical_dimensions;
+            shared_state_lock.resizable
+        };
+        // The limits of a window that isn't resizable are applied once it's resizable again.
+        if resizable {
+            let physical_dimensions = logical_dimensions.map(|logical_dimensions| {
+                logical_dimensions.to_physical(self.hidpi_factor()).into()
+            });
+            self.set_min_inner_size_physical(physical_dimensions);
+        }
     }
 
     pub(crate) fn set_max_inner_size_physical(&self, dimensions: Option<(u32, u32)>) {
@@ -921,10 +1377,89 @@ impl UnownedWindow {
 
     #[inline]
     pub fn set_max_inner_size(&self, logical_dimensions: Option<LogicalSize>) {
-        self.shared_state.lock().max_inner_size = logical_dimensions;
-        let physical_dimensions = logical_dimensions
-            .map(|logical_dimensions| logical_dimensions.to_physical(self.hidpi_factor()).into());
-        self.set_max_inner_size_physical(physical_dimensions);
+        if self.is_destroyed() {
+            return;
+        }
+        let resizable = {
+            let mut shared_state_lock = self.shared_state.lock();
+            shared_state_lock.max_inner_size = logical_dimensions;
+            shared_state_lock.resizable
+        };
+        if resizable {
+            let physical_dimensions = logical_dimensions.map(|logical_dimensions| {
+                logical_dimensions.to_physical(self.hidpi_factor()).into()
+            });
+            self.set_max_inner_size_physical(physical_dimensions);
+        }
+    }
+
+    pub fn get_maximized(&self) -> bool {
+        if self.is_destroyed() {
+            return false;
+        }
+        let state_atom = unsafe { self.xconn.get_atom_unchecked(b"_NET_WM_STATE\0") };
+        let horz_atom = unsafe {
+            self.xconn
+                .get_atom_unchecked(b"_NET_WM_STATE_MAXIMIZED_HORZ\0")
+        };
+        let vert_atom = unsafe {
+            self.xconn
+                .get_atom_unchecked(b"_NET_WM_STATE_MAXIMIZED_VERT\0")
+        };
+        self.xconn
+            .get_property::<ffi::Atom>(self.xwindow, state_atom, ffi::XA_ATOM)
+            .map(|atoms| atoms.contains(&horz_atom) && atoms.contains(&vert_atom))
+            .unwrap_or(false)
+    }
+
+    pub fn get_placement(&self) -> Option<WindowPlacement> {
+        if self.is_destroyed() {
+            return None;
+        }
+        Some(WindowPlacement {
+            position: self.outer_position().ok()?,
+            inner_size: self.inner_size(),
+            maximized: self.get_maximized(),
+            monitor_native_id: Some(self.current_monitor().native_identifier().to_string()),
+        })
+    }
+
+    pub fn set_placement(&self, placement: &WindowPlacement) {
+        if self.is_destroyed() {
+            return;
+        }
+        let work_area = self.xconn.get_work_area(self.root);
+        let areas: Vec<_> = self
+            .available_monitors()
+            .iter()
+            .map(|monitor| {
+                let monitor_rect =
+                    util::AaRect::new(monitor.position().into(), monitor.size().into());
+                let rect = work_area
+                    .as_ref()
+                    .and_then(|work_area| work_area.intersection(&monitor_rect))
+                    .unwrap_or(monitor_rect);
+                PlacementArea {
+                    native_id: monitor.native_identifier().to_string(),
+                    position: rect.position().into(),
+                    size: rect.size().into(),
+                    hidpi_factor: monitor.hidpi_factor(),
+                }
+            })
+            .collect();
+        let (position, size, _) = match fit_placement(placement, &areas) {
+            Some(fitted) => fitted,
+            None => return,
+        };
+        // The window manager won't move or resize a maximized window.
+        self.set_maximized(false);
+        let (x, y) = position.into();
+        self.set_position_physical(x, y);
+        let (width, height) = size.into();
+        self.set_inner_size_physical(width, height);
+        if placement.maximized {
+            self.set_maximized(true);
+        }
     }
 
     pub(crate) fn adjust_for_dpi(
@@ -965,6 +1500,9 @@ impl UnownedWindow {
     }
 
     pub fn set_resizable(&self, resizable: bool) {
+        if self.is_destroyed() {
+            return;
+        }
         if util::wm_name_is_one_of(&["Xfwm4"]) {
             // Making the window unresizable on Xfwm prevents further changes to `WM_NORMAL_HINTS` from being detected.
             // This makes it impossible for resizing to be re-enabled, and also breaks DPI scaling. As such, we choose
@@ -974,12 +1512,14 @@ impl UnownedWindow {
         }
 
         let (logical_min, logical_max) = if resizable {
-            let shared_state_lock = self.shared_state.lock();
+            let mut shared_state_lock = self.shared_state.lock();
+            shared_state_lock.resizable = true;
             (
                 shared_state_lock.min_inner_size,
                 shared_state_lock.max_inner_size,
             )
         } else {
+            self.shared_state.lock().resizable = false;
             let window_size = Some(self.inner_size());
             (window_size.clone(), window_size)
         };
@@ -1043,6 +1583,15 @@ impl UnownedWindow {
     }
 
     fn get_cursor(&self, cursor: CursorIcon) -> ffi::Cursor {
+        *self
+            .xconn
+            .cursor_cache
+            .lock()
+            .entry(cursor)
+            .or_insert_with(|| self.load_system_cursor(cursor))
+    }
+
+    fn load_system_cursor(&self, cursor: CursorIcon) -> ffi::Cursor {
         let load = |name: &[u8]| self.load_cursor(name);
 
         let loadn = |names: &[&[u8]]| self.load_first_existing_cursor(names);
@@ -1096,26 +1645,55 @@ impl UnownedWindow {
         }
     }
 
+    // This doesn't free the cursor, since system cursors are cached on the connection.
     fn update_cursor(&self, cursor: ffi::Cursor) {
         unsafe {
             (self.xconn.xlib.XDefineCursor)(self.xconn.display, self.xwindow, cursor);
-            if cursor != 0 {
-                (self.xconn.xlib.XFreeCursor)(self.xconn.display, cursor);
-            }
-            self.xconn
-                .flush_requests()
-                .expect("Failed to set or free the cursor");
         }
+        self.xconn
+            .flush_requests()
+            .expect("Failed to set the cursor");
+    }
+
+    fn define_custom_cursor(&self, cursor: &util::CustomCursor) {
+        self.update_cursor(cursor.cursor());
     }
 
     #[inline]
     pub fn set_cursor_icon(&self, cursor: CursorIcon) {
+        if self.is_destroyed() {
+            return;
+        }
         *self.cursor.lock() = cursor;
+        *self.custom_cursor.lock() = None;
         if *self.cursor_visible.lock() {
             self.update_cursor(self.get_cursor(cursor));
         }
     }
 
+    #[inline]
+    pub fn create_custom_cursor(
+        &self,
+        rgba: &[u8],
+        width: u32,
+        height: u32,
+        hotspot_x: u32,
+        hotspot_y: u32,
+    ) -> Result<util::CustomCursor, BadCursor> {
+        util::CustomCursor::from_rgba(&self.xconn, rgba, width, height, hotspot_x, hotspot_y)
+    }
+
+    #[inline]
+    pub fn set_custom_cursor(&self, cursor: &util::CustomCursor) {
+        if self.is_destroyed() {
+            return;
+        }
+        *self.custom_cursor.lock() = Some(cursor.clone());
+        if *self.cursor_visible.lock() {
+            self.define_custom_cursor(cursor);
+        }
+    }
+
     // TODO: This could maybe be cached. I don't think it's worth
     // the complexity, since cursor changes are not so common,
     // and this is just allocating a 1x1 pixmap...
@@ -1188,17 +1766,21 @@ impl UnownedWindow {
 
             match result {
                 ffi::GrabSuccess => Ok(()),
-                ffi::AlreadyGrabbed => {
-                    Err("Cursor could not be grabbed: already grabbed by another client")
+                // Both of these typically happen when the window hasn't been mapped or focused
+                // yet, so the caller can try again later.
+                ffi::AlreadyGrabbed | ffi::GrabNotViewable => Err(ExternalError::NotFocused),
+                ffi::GrabInvalidTime => {
+                    Err(ExternalError::Os(os_error!(OsError::XMisc(
+                        "Cursor could not be grabbed: invalid time"
+                    ))))
                 },
-                ffi::GrabInvalidTime => Err("Cursor could not be grabbed: invalid time"),
-                ffi::GrabNotViewable => {
-                    Err("Cursor could not be grabbed: grab location not viewable")
+                ffi::GrabFrozen => {
+                    Err(ExternalError::Os(os_error!(OsError::XMisc(
+                        "Cursor could not be grabbed: frozen by another client"
+                    ))))
                 },
-                ffi::GrabFrozen => Err("Cursor could not be grabbed: frozen by another client"),
                 _ => unreachable!(),
             }
-            .map_err(|err| ExternalError::Os(os_error!(OsError::XMisc(err))))
         } else {
             self.xconn
                 .flush_requests()
@@ -1210,21 +1792,109 @@ impl UnownedWindow {
         result
     }
 
+    fn grab_keyboard(&self) -> Result<(), ExternalError> {
+        let result = unsafe {
+            (self.xconn.xlib.XGrabKeyboard)(
+                self.xconn.display,
+                self.xwindow,
+                ffi::True,
+                ffi::GrabModeAsync,
+                ffi::GrabModeAsync,
+                ffi::CurrentTime,
+            )
+        };
+        match result {
+            ffi::GrabSuccess => Ok(()),
+            // Another client, usually the window manager, holds the keyboard, or the window
+            // isn't mapped yet. Either way, the caller can try again later.
+            ffi::AlreadyGrabbed | ffi::GrabNotViewable => Err(ExternalError::NotFocused),
+            ffi::GrabInvalidTime => {
+                Err(ExternalError::Os(os_error!(OsError::XMisc(
+                    "Keyboard could not be grabbed: invalid time"
+                ))))
+            },
+            ffi::GrabFrozen => {
+                Err(ExternalError::Os(os_error!(OsError::XMisc(
+                    "Keyboard could not be grabbed: frozen by another client"
+                ))))
+            },
+            _ => unreachable!(),
+        }
+    }
+
+    fn release_keyboard_grab(&self) {
+        let mut grabbed_lock = self.keyboard_grabbed.lock();
+        if *grabbed_lock {
+            unsafe {
+                (self.xconn.xlib.XUngrabKeyboard)(self.xconn.display, ffi::CurrentTime);
+            }
+            *grabbed_lock = false;
+        }
+    }
+
+    #[inline]
+    pub fn set_keyboard_grab(&self, grab: bool) -> Result<(), ExternalError> {
+        if self.is_destroyed() {
+            return if grab {
+                Err(ExternalError::NotFocused)
+            } else {
+                Ok(())
+            };
+        }
+        let mut grabbed_lock = self.keyboard_grabbed.lock();
+        if grab == *grabbed_lock {
+            return Ok(());
+        }
+        if grab {
+            self.grab_keyboard()?;
+        } else {
+            unsafe {
+                (self.xconn.xlib.XUngrabKeyboard)(self.xconn.display, ffi::CurrentTime);
+            }
+        }
+        self.xconn
+            .flush_requests()
+            .map_err(|err| ExternalError::Os(os_error!(OsError::XError(err))))?;
+        *grabbed_lock = grab;
+        Ok(())
+    }
+
+    /// Takes the keyboard back after the window manager grabbed it for a moment, e.g. while
+    /// switching workspaces.
+    pub(crate) fn regrab_keyboard(&self) {
+        if *self.keyboard_grabbed.lock() {
+            // If this fails, the grab is retried on the next `FocusIn`.
+            if self.grab_keyboard().is_ok() {
+                let _ = self.xconn.flush_requests();
+            }
+        }
+    }
+
     #[inline]
     pub fn set_cursor_visible(&self, visible: bool) {
+        if self.is_destroyed() {
+            return;
+        }
         let mut visible_lock = self.cursor_visible.lock();
         if visible == *visible_lock {
             return;
         }
-        let cursor = if visible {
-            self.get_cursor(*self.cursor.lock())
-        } else {
-            self.create_empty_cursor()
-                .expect("Failed to create empty cursor")
-        };
         *visible_lock = visible;
         drop(visible_lock);
-        self.update_cursor(cursor);
+        if visible {
+            if let Some(ref cursor) = *self.custom_cursor.lock() {
+                return self.define_custom_cursor(cursor);
+            }
+        }
+        if visible {
+            self.update_cursor(self.get_cursor(*self.cursor.lock()));
+        } else {
+            let cursor = self
+                .create_empty_cursor()
+                .expect("Failed to create empty cursor");
+            self.update_cursor(cursor);
+            unsafe { (self.xconn.xlib.XFreeCursor)(self.xconn.display, cursor) };
+        }
     }
 
     #[inline]
@@ -1250,11 +1920,111 @@ impl UnownedWindow {
         self.set_cursor_position_physical(x, y)
     }
 
+    /// Hands an interactive move/resize over to the window manager via `_NET_WM_MOVERESIZE`.
+    ///
+    /// `action` is one of the `_NET_WM_MOVERESIZE_*` direction constants.
+    fn drag_initiate(&self, action: c_long) -> Result<(), ExternalError> {
+        let pointer = self
+            .xconn
+            .query_pointer(self.xwindow, util::VIRTUAL_CORE_POINTER)
+            .map_err(|err| ExternalError::Os(os_error!(OsError::XError(err))))?;
+        if !pointer.is_button_down(ffi::Button1) {
+            return Err(ExternalError::Os(os_error!(OsError::XMisc(
+                "Window drag could not be initiated: the left mouse button isn't pressed"
+            ))));
+        }
+        let (root_x, root_y) = (pointer.root_x as c_long, pointer.root_y as c_long);
+        drop(pointer);
+
+        // The window manager can't take over the pointer while we hold a grab on it, so we have
+        // to release both our own grab and the implicit one from the button press.
+        let mut grabbed_lock = self.cursor_grabbed.lock();
+        unsafe {
+            (self.xconn.xlib.XUngrabPointer)(self.xconn.display, ffi::CurrentTime);
+        }
+        *grabbed_lock = false;
+
+        let moveresize_atom = unsafe { self.xconn.get_atom_unchecked(b"_NET_WM_MOVERESIZE\0") };
+        self.xconn
+            .send_client_msg(
+                self.xwindow,
+                self.root,
+                moveresize_atom,
+                Some(ffi::SubstructureRedirectMask | ffi::SubstructureNotifyMask),
+                [
+                    root_x,
+                    root_y,
+                    action,
+                    ffi::Button1 as c_long,
+                    1, // Source indication: normal application
+                ],
+            )
+            .flush()
+            .map_err(|err| ExternalError::Os(os_error!(OsError::XError(err))))
+    }
+
+    #[inline]
+    pub fn drag_window(&self) -> Result<(), ExternalError> {
+        // _NET_WM_MOVERESIZE_MOVE
+        self.drag_initiate(8)
+    }
+
+    #[inline]
+    pub fn drag_resize_window(&self, direction: ResizeDirection) -> Result<(), ExternalError> {
+        // The `_NET_WM_MOVERESIZE_SIZE_*` constants.
+        self.drag_initiate(match direction {
+            ResizeDirection::NorthWest => 0,
+            ResizeDirection::North => 1,
+            ResizeDirection::NorthEast => 2,
+            ResizeDirection::East => 3,
+            ResizeDirection::SouthEast => 4,
+            ResizeDirection::South => 5,
+            ResizeDirection::SouthWest => 6,
+            ResizeDirection::West => 7,
+        })
+    }
+
+    #[inline]
+    pub fn show_window_menu(&self, logical_position: LogicalPosition) {
+        if self.is_destroyed() {
+            return;
+        }
+        let (x, y): (i32, i32) = logical_position.to_physical(self.hidpi_factor()).into();
+        let (inner_x, inner_y) = self.inner_position_physical();
+
+        // Just like with `_NET_WM_MOVERESIZE`, the window manager needs the pointer for the menu.
+        let mut grabbed_lock = self.cursor_grabbed.lock();
+        unsafe {
+            (self.xconn.xlib.XUngrabPointer)(self.xconn.display, ffi::CurrentTime);
+        }
+        *grabbed_lock = false;
+
+        // Window managers that don't support this simply ignore the message.
+        let show_window_menu_atom =
+            unsafe { self.xconn.get_atom_unchecked(b"_GTK_SHOW_WINDOW_MENU\0") };
+        self.xconn
+            .send_client_msg(
+                self.xwindow,
+                self.root,
+                show_window_menu_atom,
+                Some(ffi::SubstructureRedirectMask | ffi::SubstructureNotifyMask),
+                [
+                    util::VIRTUAL_CORE_POINTER as c_long,
+                    (inner_x + x) as c_long,
+                    (inner_y + y) as c_long,
+                    0,
+                    0,
+                ],
+            )
+            .flush()
+            .expect("Failed to show the window menu");
+    }
+
     pub(crate) fn set_ime_position_physical(&self, x: i32, y: i32) {
         let _ = self
             .ime_sender
             .lock()
-            .send((self.xwindow, x as i16, y as i16));
+            .send(ImeRequest::Position(self.xwindow, x as i16, y as i16));
     }
 
     #[inline]
@@ -1263,6 +2033,30 @@ impl UnownedWindow {
         self.set_ime_position_physical(x, y);
     }
 
+    #[inline]
+    pub fn set_ime_allowed(&self, allowed: bool) {
+        let _ = self
+            .ime_sender
+            .lock()
+            .send(ImeRequest::Allow(self.xwindow, allowed));
+    }
+
+    #[inline]
+    pub fn reset_dead_keys(&self) {
+        let _ = self.ime_sender.lock().send(ImeRequest::Reset(self.xwindow));
+    }
+
+    // X11 has no notion of disabled windows, so the event processor drops their input instead.
+    #[inline]
+    pub fn set_enabled(&self, enabled: bool) {
+        *self.enabled.lock() = enabled;
+    }
+
+    #[inline]
+    pub(crate) fn is_enabled(&self) -> bool {
+        *self.enabled.lock()
+    }
+
     #[inline]
     pub fn id(&self) -> WindowId {
         WindowId(self.xwindow)
@@ -1275,4 +2069,7 @@ impl UnownedWindow {
             .unwrap()
             .insert(WindowId(self.xwindow));
     }
+
+    #[inline]
+    pub fn pre_present_notify(&self) {}
 }
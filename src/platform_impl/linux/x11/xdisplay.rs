@@ -1,9 +1,10 @@
-use std::{error::Error, fmt, os::raw::c_int, ptr};
+use std::{collections::HashMap, error::Error, fmt, os::raw::c_int, ptr};
 
 use libc;
 use parking_lot::Mutex;
 
 use super::ffi;
+use crate::window::CursorIcon;
 
 /// A connection to an X server.
 pub struct XConnection {
@@ -16,9 +17,14 @@ pub struct XConnection {
     pub xinput2: ffi::XInput2,
     pub xlib_xcb: ffi::Xlib_xcb,
     pub xrender: ffi::Xrender,
+    /// Used for window shapes. Not every system ships XFixes, so it's optional.
+    pub xfixes: Option<ffi::Xfixes>,
     pub display: *mut ffi::Display,
     pub x11_fd: c_int,
     pub latest_error: Mutex<Option<XError>>,
+    /// System cursors that have been loaded so far. They're never freed, since they live for as
+    /// long as the display does.
+    pub cursor_cache: Mutex<HashMap<CursorIcon, ffi::Cursor>>,
 }
 
 unsafe impl Send for XConnection {}
@@ -37,6 +43,7 @@ impl XConnection {
         let xinput2 = ffi::XInput2::open()?;
         let xlib_xcb = ffi::Xlib_xcb::open()?;
         let xrender = ffi::Xrender::open()?;
+        let xfixes = ffi::Xfixes::open().ok();
 
         unsafe { (xlib.XInitThreads)() };
         unsafe { (xlib.XSetErrorHandler)(error_handler) };
@@ -61,9 +68,11 @@ impl XConnection {
             xinput2,
             xlib_xcb,
             xrender,
+            xfixes,
             display,
             x11_fd: fd,
             latest_error: Mutex::new(None),
+            cursor_cache: Default::default(),
         })
     }
 
@@ -3,6 +3,7 @@ use std::collections::VecDeque;
 use cocoa::{
     appkit::{self, NSEvent},
     base::id,
+    foundation::NSInteger,
 };
 use objc::{
     declare::ClassDecl,
@@ -10,8 +11,13 @@ use objc::{
 };
 
 use crate::{
-    event::{DeviceEvent, Event},
-    platform_impl::platform::{app_state::AppState, util, DEVICE_ID},
+    event::{DeviceEvent, ElementState, Event, KeyboardInput},
+    platform_impl::platform::{
+        app_state::AppState,
+        event::{event_mods, get_scancode, scancode_to_keycode},
+        util, DEVICE_ID,
+    },
+    window::Icon,
 };
 
 pub struct AppClass(pub *const Class);
@@ -48,6 +54,7 @@ extern "C" fn send_event(this: &Object, _sel: Sel, event: id) {
                 appkit::NSEventModifierFlags::NSCommandKeyMask,
             )
         {
+            maybe_dispatch_device_event(event);
             let key_window: id = msg_send![this, keyWindow];
             let _: () = msg_send![key_window, sendEvent: event];
         } else {
@@ -101,6 +108,60 @@ unsafe fn maybe_dispatch_device_event(event: id) {
 
             AppState::queue_events(events);
         },
+        appkit::NSLeftMouseDown
+        | appkit::NSLeftMouseUp
+        | appkit::NSRightMouseDown
+        | appkit::NSRightMouseUp
+        | appkit::NSOtherMouseDown
+        | appkit::NSOtherMouseUp => {
+            let state = match event_type {
+                appkit::NSLeftMouseDown | appkit::NSRightMouseDown | appkit::NSOtherMouseDown => {
+                    ElementState::Pressed
+                },
+                _ => ElementState::Released,
+            };
+            // Left, middle, and right are numbered 1, 2 and 3 respectively, for consistency with
+            // the other platforms. AppKit numbers them 0, 2 and 1, and the buttons after them
+            // are shifted by one to stay clear of those.
+            let button_number: NSInteger = msg_send![event, buttonNumber];
+            let button = match button_number {
+                0 => 1,
+                1 => 3,
+                2 => 2,
+                n => n as u32 + 1,
+            };
+
+            AppState::queue_event(Event::DeviceEvent {
+                device_id: DEVICE_ID,
+                event: DeviceEvent::Button { button, state },
+            });
+        },
+        appkit::NSKeyDown | appkit::NSKeyUp => {
+            let state = if event_type == appkit::NSKeyDown {
+                ElementState::Pressed
+            } else {
+                ElementState::Released
+            };
+            let scancode = get_scancode(event);
+
+            AppState::queue_event(Event::DeviceEvent {
+                device_id: DEVICE_ID,
+                event: DeviceEvent::Key(KeyboardInput {
+                    scancode: scancode as u32,
+                    state,
+                    virtual_keycode: scancode_to_keycode(scancode),
+                    modifiers: event_mods(event),
+                }),
+            });
+        },
         _ => (),
     }
 }
+
+pub fn set_dock_badge(badge: Option<&str>) {
+    unsafe { util::set_dock_badge_async(badge.map(str::to_owned)) };
+}
+
+pub fn set_dock_icon(icon: Option<Icon>) {
+    unsafe { util::set_dock_icon_async(icon) };
+}
@@ -223,7 +223,15 @@ pub fn check_function_keys(string: &String) -> Option<VirtualKeyCode> {
 }
 
 pub fn event_mods(event: id) -> ModifiersState {
-    let flags = unsafe { NSEvent::modifierFlags(event) };
+    mods_from_flags(unsafe { NSEvent::modifierFlags(event) })
+}
+
+/// Returns the modifiers that are pressed right now, outside of any event.
+pub fn current_mods() -> ModifiersState {
+    mods_from_flags(unsafe { msg_send![class!(NSEvent), modifierFlags] })
+}
+
+fn mods_from_flags(flags: NSEventModifierFlags) -> ModifiersState {
     ModifiersState {
         shift: flags.contains(NSEventModifierFlags::NSShiftKeyMask),
         ctrl: flags.contains(NSEventModifierFlags::NSControlKeyMask),
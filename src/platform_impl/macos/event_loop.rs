@@ -10,7 +10,9 @@ use cocoa::{
 
 use crate::{
     event::Event,
-    event_loop::{ControlFlow, EventLoopClosed, EventLoopWindowTarget as RootWindowTarget},
+    event_loop::{
+        ControlFlow, DeviceEventFilter, EventLoopClosed, EventLoopWindowTarget as RootWindowTarget,
+    },
     platform_impl::platform::{
         app::APP_CLASS,
         app_delegate::APP_DELEGATE_CLASS,
@@ -33,6 +35,16 @@ impl<T> Default for EventLoopWindowTarget<T> {
     }
 }
 
+impl<T> EventLoopWindowTarget<T> {
+    #[inline]
+    pub fn set_device_event_filter(&self, _filter: DeviceEventFilter) {}
+
+    #[inline]
+    pub fn primary_monitor(&self) -> MonitorHandle {
+        monitor::primary_monitor()
+    }
+}
+
 pub struct EventLoop<T: 'static> {
     window_target: RootWindowTarget<T>,
     _delegate: IdRef,
@@ -114,8 +126,8 @@ pub struct Proxy<T> {
     source: CFRunLoopSourceRef,
 }
 
-unsafe impl<T> Send for Proxy<T> {}
-unsafe impl<T> Sync for Proxy<T> {}
+unsafe impl<T: Send> Send for Proxy<T> {}
+unsafe impl<T: Send> Sync for Proxy<T> {}
 
 impl<T> Proxy<T> {
     fn new(sender: mpsc::Sender<T>) -> Self {
@@ -2,14 +2,27 @@
 
 #![allow(dead_code, non_snake_case, non_upper_case_globals)]
 
+use std::os::raw::c_void;
+
 use cocoa::{
     base::id,
     foundation::{NSInteger, NSUInteger},
 };
+use core_foundation::{array::CFArrayRef, dictionary::CFDictionaryRef};
+use core_graphics::{base::CGError, display::CGDirectDisplayID};
 use objc;
 
 pub const NSNotFound: NSInteger = NSInteger::max_value();
 
+pub type NSTrackingAreaOptions = NSUInteger;
+pub const NSTrackingMouseEnteredAndExited: NSTrackingAreaOptions = 0x01;
+pub const NSTrackingActiveAlways: NSTrackingAreaOptions = 0x80;
+pub const NSTrackingInVisibleRect: NSTrackingAreaOptions = 0x200;
+
+pub type NSWindowCollectionBehavior = NSUInteger;
+pub const NSWindowCollectionBehaviorTransient: NSWindowCollectionBehavior = 1 << 3;
+pub const NSWindowCollectionBehaviorIgnoresCycle: NSWindowCollectionBehavior = 1 << 6;
+
 #[repr(C)]
 pub struct NSRange {
     pub location: NSUInteger,
@@ -35,6 +48,14 @@ unsafe impl objc::Encode for NSRange {
     }
 }
 
+#[repr(C)]
+#[derive(Debug, Clone, Copy)]
+pub struct NSOperatingSystemVersion {
+    pub major: NSInteger,
+    pub minor: NSInteger,
+    pub patch: NSInteger,
+}
+
 pub trait NSMutableAttributedString: Sized {
     unsafe fn alloc(_: Self) -> id {
         msg_send![class!(NSMutableAttributedString), alloc]
@@ -97,6 +118,21 @@ pub const kCGDesktopIconWindowLevelKey: NSInteger = 18;
 pub const kCGCursorWindowLevelKey: NSInteger = 19;
 pub const kCGNumberOfWindowLevelKeys: NSInteger = 20;
 
+#[repr(isize)]
+#[derive(Debug, Clone, Copy)]
+pub enum NSWindowOrderingMode {
+    NSWindowBelow = -1,
+    NSWindowOut = 0,
+    NSWindowAbove = 1,
+}
+
+#[repr(usize)]
+#[derive(Debug, Clone, Copy)]
+pub enum NSWindowSharingType {
+    NSWindowSharingNone = 0,
+    NSWindowSharingReadOnly = 1,
+}
+
 #[derive(Debug, Clone, Copy)]
 pub enum NSWindowLevel {
     NSNormalWindowLevel = kCGBaseWindowLevelKey as _,
@@ -108,3 +144,19 @@ pub enum NSWindowLevel {
     NSPopUpMenuWindowLevel = kCGPopUpMenuWindowLevelKey as _,
     NSScreenSaverWindowLevel = kCGScreenSaverWindowLevelKey as _,
 }
+
+pub type CGDisplayModeRef = *const c_void;
+
+#[link(name = "CoreGraphics", kind = "framework")]
+extern "C" {
+    pub fn CGDisplayCopyAllDisplayModes(
+        display: CGDirectDisplayID,
+        options: CFDictionaryRef,
+    ) -> CFArrayRef;
+    pub fn CGDisplaySetDisplayMode(
+        display: CGDirectDisplayID,
+        mode: CGDisplayModeRef,
+        options: CFDictionaryRef,
+    ) -> CGError;
+    pub fn CGRestorePermanentDisplayConfiguration();
+}
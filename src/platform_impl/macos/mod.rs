@@ -16,8 +16,10 @@ mod window_delegate;
 use std::{fmt, ops::Deref, sync::Arc};
 
 pub use self::{
+    app::{set_dock_badge, set_dock_icon},
     event_loop::{EventLoop, EventLoopWindowTarget, Proxy as EventLoopProxy},
     monitor::MonitorHandle,
+    util::CustomCursor,
     window::{Id as WindowId, PlatformSpecificWindowBuilderAttributes, UnownedWindow},
 };
 use crate::{
@@ -46,6 +48,7 @@ pub struct Window {
 pub enum OsError {
     CGError(core_graphics::base::CGError),
     CreationError(&'static str),
+    Misc(&'static str),
 }
 
 unsafe impl Send for Window {}
@@ -70,11 +73,21 @@ impl Window {
     }
 }
 
+impl OsError {
+    pub fn raw_os_error(&self) -> Option<i32> {
+        match self {
+            OsError::CGError(e) => Some(*e),
+            OsError::CreationError(_) | OsError::Misc(_) => None,
+        }
+    }
+}
+
 impl fmt::Display for OsError {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         match self {
             OsError::CGError(e) => f.pad(&format!("CGError {}", e)),
             OsError::CreationError(e) => f.pad(e),
+            OsError::Misc(e) => f.pad(e),
         }
     }
 }
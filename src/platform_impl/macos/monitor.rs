@@ -1,10 +1,14 @@
-use std::{collections::VecDeque, fmt};
+use std::{collections::VecDeque, fmt, ptr};
 
 use cocoa::{
     appkit::NSScreen,
     base::{id, nil},
     foundation::{NSString, NSUInteger},
 };
+use core_foundation::{
+    array::CFArrayGetValueAtIndex,
+    base::{CFIndex, CFRelease, CFTypeRef},
+};
 use core_graphics::display::{CGDirectDisplayID, CGDisplay, CGDisplayBounds, CGDisplayMode};
 use core_video_sys::{
     kCVReturnSuccess, kCVTimeIsIndefinite, CVDisplayLinkCreateWithCGDisplay,
@@ -14,10 +18,10 @@ use core_video_sys::{
 use crate::{
     dpi::{PhysicalPosition, PhysicalSize},
     monitor::VideoMode,
-    platform_impl::platform::util::IdRef,
+    platform_impl::platform::{ffi, util::IdRef, OsError},
 };
 
-#[derive(Clone, PartialEq)]
+#[derive(Clone, PartialEq, Eq, Hash)]
 pub struct MonitorHandle(CGDirectDisplayID);
 
 pub fn available_monitors() -> VecDeque<MonitorHandle> {
@@ -139,6 +143,31 @@ impl MonitorHandle {
             })
     }
 
+    /// Switches the monitor to `video_mode`, which has to be one of its `video_modes`. The
+    /// user's display configuration is brought back with `restore_video_modes`.
+    pub(crate) fn set_video_mode(&self, video_mode: &VideoMode) -> Result<(), OsError> {
+        // `video_modes` lists the modes in the order `CGDisplayCopyAllDisplayModes` returns them.
+        let index = self
+            .video_modes()
+            .position(|mode| mode == *video_mode)
+            .ok_or(OsError::Misc(
+                "The video mode isn't supported by the monitor",
+            ))?;
+        unsafe {
+            let modes = ffi::CGDisplayCopyAllDisplayModes(self.0, ptr::null());
+            if modes.is_null() {
+                return Err(OsError::Misc("Couldn't obtain the list of display modes"));
+            }
+            let mode = CFArrayGetValueAtIndex(modes, index as CFIndex);
+            let result = ffi::CGDisplaySetDisplayMode(self.0, mode, ptr::null());
+            CFRelease(modes as CFTypeRef);
+            match result {
+                0 => Ok(()),
+                e => Err(OsError::CGError(e)),
+            }
+        }
+    }
+
     pub(crate) fn ns_screen(&self) -> Option<id> {
         unsafe {
             let native_id = self.native_identifier();
@@ -162,3 +191,8 @@ impl MonitorHandle {
         }
     }
 }
+
+/// Switches every monitor back to the video mode from the user's display preferences.
+pub(crate) fn restore_video_modes() {
+    unsafe { ffi::CGRestorePermanentDisplayConfiguration() }
+}
@@ -4,15 +4,26 @@ use std::{
 };
 
 use cocoa::{
-    appkit::{CGFloat, NSScreen, NSWindow, NSWindowStyleMask},
+    appkit::{
+        CGFloat, NSApp, NSScreen, NSView, NSWindow, NSWindowButton, NSWindowStyleMask,
+        NSWindowTitleVisibility,
+    },
     base::{id, nil},
-    foundation::{NSAutoreleasePool, NSPoint, NSSize, NSString},
+    foundation::{NSAutoreleasePool, NSInteger, NSPoint, NSSize, NSString, NSUInteger},
 };
 use dispatch::ffi::{dispatch_async_f, dispatch_get_main_queue, dispatch_sync_f};
+use objc::runtime::{NO, YES};
 
 use crate::{
     dpi::LogicalSize,
-    platform_impl::platform::{ffi, util::IdRef, window::SharedState},
+    event::{Event, WindowEvent},
+    platform_impl::platform::{
+        app_state::AppState,
+        ffi,
+        util::{self, IdRef},
+        window::{get_window_id, SharedState},
+    },
+    window::{Icon, WindowButtons, WindowId as RootWindowId},
 };
 
 unsafe fn set_style_mask(ns_window: id, ns_view: id, mask: NSWindowStyleMask) {
@@ -395,6 +406,39 @@ pub unsafe fn set_title_async(ns_window: id, title: String) {
     );
 }
 
+struct SetSubtitleData {
+    ns_window: id,
+    subtitle: String,
+}
+impl SetSubtitleData {
+    fn new_ptr(ns_window: id, subtitle: String) -> *mut Self {
+        Box::into_raw(Box::new(SetSubtitleData {
+            ns_window,
+            subtitle,
+        }))
+    }
+}
+extern "C" fn set_subtitle_callback(context: *mut c_void) {
+    unsafe {
+        let context_ptr = context as *mut SetSubtitleData;
+        {
+            let context = &*context_ptr;
+            let subtitle = IdRef::new(NSString::alloc(nil).init_str(&context.subtitle));
+            let () = msg_send![context.ns_window, setSubtitle: *subtitle];
+        }
+        Box::from_raw(context_ptr);
+    }
+}
+// Same as `set_title_async`. `setSubtitle:` only exists on macOS 11 and later.
+pub unsafe fn set_subtitle_async(ns_window: id, subtitle: String) {
+    let context = SetSubtitleData::new_ptr(ns_window, subtitle);
+    dispatch_async_f(
+        dispatch_get_main_queue(),
+        context as *mut _,
+        set_subtitle_callback,
+    );
+}
+
 struct CloseData {
     ns_window: id,
 }
@@ -421,3 +465,263 @@ pub unsafe fn close_async(ns_window: id) {
     let context = CloseData::new_ptr(ns_window);
     dispatch_async_f(dispatch_get_main_queue(), context as *mut _, close_callback);
 }
+
+struct SetTitlebarHiddenData {
+    ns_window: id,
+    ns_view: id,
+    hidden: bool,
+}
+impl SetTitlebarHiddenData {
+    fn new_ptr(ns_window: id, ns_view: id, hidden: bool) -> *mut Self {
+        Box::into_raw(Box::new(SetTitlebarHiddenData {
+            ns_window,
+            ns_view,
+            hidden,
+        }))
+    }
+}
+extern "C" fn set_titlebar_hidden_callback(context: *mut c_void) {
+    unsafe {
+        let context_ptr = context as *mut SetTitlebarHiddenData;
+        {
+            let context = &*context_ptr;
+            let titlebar_mask = NSWindowStyleMask::NSTitledWindowMask
+                | NSWindowStyleMask::NSClosableWindowMask
+                | NSWindowStyleMask::NSMiniaturizableWindowMask;
+            let mask = context.ns_window.styleMask();
+            if mask.contains(titlebar_mask) == context.hidden {
+                util::toggle_style_mask(
+                    context.ns_window,
+                    context.ns_view,
+                    titlebar_mask,
+                    !context.hidden,
+                );
+                context.ns_window.setTitleVisibility_(if context.hidden {
+                    NSWindowTitleVisibility::NSWindowTitleHidden
+                } else {
+                    NSWindowTitleVisibility::NSWindowTitleVisible
+                });
+
+                // The frame stays put, so the content takes over the space of the titlebar or
+                // gives it back, which `windowDidResize:` doesn't hear about.
+                let frame = NSView::frame(context.ns_view);
+                AppState::queue_event(Event::WindowEvent {
+                    window_id: RootWindowId(get_window_id(context.ns_window)),
+                    event: WindowEvent::Resized(
+                        (frame.size.width as f64, frame.size.height as f64).into(),
+                    ),
+                });
+            }
+        }
+        Box::from_raw(context_ptr);
+    }
+}
+// Same as `set_style_mask_async`, with the title visibility changed along with the mask.
+pub unsafe fn set_titlebar_hidden_async(ns_window: id, ns_view: id, hidden: bool) {
+    let context = SetTitlebarHiddenData::new_ptr(ns_window, ns_view, hidden);
+    dispatch_async_f(
+        dispatch_get_main_queue(),
+        context as *mut _,
+        set_titlebar_hidden_callback,
+    );
+}
+
+struct SetSkipTaskbarData {
+    ns_window: id,
+    skip: bool,
+}
+impl SetSkipTaskbarData {
+    fn new_ptr(ns_window: id, skip: bool) -> *mut Self {
+        Box::into_raw(Box::new(SetSkipTaskbarData { ns_window, skip }))
+    }
+}
+extern "C" fn set_skip_taskbar_callback(context: *mut c_void) {
+    unsafe {
+        let context_ptr = context as *mut SetSkipTaskbarData;
+        {
+            let context = &*context_ptr;
+            util::set_skip_taskbar(context.ns_window, context.skip);
+        }
+        Box::from_raw(context_ptr);
+    }
+}
+// `setCollectionBehavior:` isn't thread-safe.
+pub unsafe fn set_skip_taskbar_async(ns_window: id, skip: bool) {
+    let context = SetSkipTaskbarData::new_ptr(ns_window, skip);
+    dispatch_async_f(
+        dispatch_get_main_queue(),
+        context as *mut _,
+        set_skip_taskbar_callback,
+    );
+}
+
+struct SetBlurData {
+    ns_window: id,
+    ns_view: id,
+    blur: bool,
+}
+impl SetBlurData {
+    fn new_ptr(ns_window: id, ns_view: id, blur: bool) -> *mut Self {
+        Box::into_raw(Box::new(SetBlurData {
+            ns_window,
+            ns_view,
+            blur,
+        }))
+    }
+}
+extern "C" fn set_blur_callback(context: *mut c_void) {
+    unsafe {
+        let context_ptr = context as *mut SetBlurData;
+        {
+            let context = &*context_ptr;
+            let ns_window = context.ns_window;
+            let ns_view = context.ns_view;
+            if context.blur {
+                // The `NSVisualEffectView` becomes the content view, with our view on top of it, so
+                // the blur only shows through where the view is transparent.
+                let frame = NSView::frame(ns_window.contentView());
+                let blur_view: id = msg_send![class!(NSVisualEffectView), alloc];
+                let blur_view: id = msg_send![blur_view, initWithFrame: frame];
+                // `NSVisualEffectBlendingModeBehindWindow` and `NSVisualEffectStateActive`, so
+                // the blur doesn't go away while the window is in the background.
+                let _: () = msg_send![blur_view, setBlendingMode: 0 as NSInteger];
+                let _: () = msg_send![blur_view, setState: 1 as NSInteger];
+                ns_window.setContentView_(blur_view);
+                let _: () = msg_send![blur_view, release];
+                // `NSViewWidthSizable | NSViewHeightSizable`
+                let _: () = msg_send![ns_view, setAutoresizingMask: 18 as NSUInteger];
+                let _: () = msg_send![ns_view, setFrame: NSView::bounds(blur_view)];
+                blur_view.addSubview_(ns_view);
+            } else if ns_window.contentView() != ns_view {
+                // Our view is kept alive by the `UnownedWindow`, so taking it out of the
+                // `NSVisualEffectView` doesn't free it.
+                let _: () = msg_send![ns_view, removeFromSuperview];
+                ns_window.setContentView_(ns_view);
+            }
+            // If we don't do this, key handling will break.
+            ns_window.makeFirstResponder_(ns_view);
+        }
+        Box::from_raw(context_ptr);
+    }
+}
+// `setContentView:` isn't thread-safe.
+pub unsafe fn set_blur_async(ns_window: id, ns_view: id, blur: bool) {
+    let context = SetBlurData::new_ptr(ns_window, ns_view, blur);
+    dispatch_async_f(
+        dispatch_get_main_queue(),
+        context as *mut _,
+        set_blur_callback,
+    );
+}
+
+struct SetEnabledButtonsData {
+    ns_window: id,
+    buttons: WindowButtons,
+}
+impl SetEnabledButtonsData {
+    fn new_ptr(ns_window: id, buttons: WindowButtons) -> *mut Self {
+        Box::into_raw(Box::new(SetEnabledButtonsData { ns_window, buttons }))
+    }
+}
+extern "C" fn set_enabled_buttons_callback(context: *mut c_void) {
+    unsafe {
+        let context_ptr = context as *mut SetEnabledButtonsData;
+        {
+            let context = &*context_ptr;
+            for &(titlebar_button, flag) in &[
+                (NSWindowButton::NSWindowCloseButton, WindowButtons::CLOSE),
+                (
+                    NSWindowButton::NSWindowMiniaturizeButton,
+                    WindowButtons::MINIMIZE,
+                ),
+                (NSWindowButton::NSWindowZoomButton, WindowButtons::MAXIMIZE),
+            ] {
+                let button = context.ns_window.standardWindowButton_(titlebar_button);
+                let enabled = if context.buttons.contains(flag) {
+                    YES
+                } else {
+                    NO
+                };
+                let _: () = msg_send![button, setEnabled: enabled];
+            }
+        }
+        Box::from_raw(context_ptr);
+    }
+}
+// `setEnabled:` isn't thread-safe.
+pub unsafe fn set_enabled_buttons_async(ns_window: id, buttons: WindowButtons) {
+    let context = SetEnabledButtonsData::new_ptr(ns_window, buttons);
+    dispatch_async_f(
+        dispatch_get_main_queue(),
+        context as *mut _,
+        set_enabled_buttons_callback,
+    );
+}
+
+struct SetDockIconData {
+    icon: Option<Icon>,
+}
+impl SetDockIconData {
+    fn new_ptr(icon: Option<Icon>) -> *mut Self {
+        Box::into_raw(Box::new(SetDockIconData { icon }))
+    }
+}
+extern "C" fn set_dock_icon_callback(context: *mut c_void) {
+    unsafe {
+        let context_ptr = context as *mut SetDockIconData;
+        {
+            let context = &*context_ptr;
+            let image = context
+                .icon
+                .as_ref()
+                .and_then(|icon| util::image_from_rgba(&icon.rgba, icon.width, icon.height));
+            // Setting `nil` brings back the icon from the application bundle.
+            let image = image.as_ref().map_or(nil, |image| **image);
+            let _: () = msg_send![NSApp(), setApplicationIconImage: image];
+        }
+        Box::from_raw(context_ptr);
+    }
+}
+// `setApplicationIconImage:` isn't thread-safe.
+pub unsafe fn set_dock_icon_async(icon: Option<Icon>) {
+    let context = SetDockIconData::new_ptr(icon);
+    dispatch_async_f(
+        dispatch_get_main_queue(),
+        context as *mut _,
+        set_dock_icon_callback,
+    );
+}
+
+struct SetDockBadgeData {
+    badge: Option<String>,
+}
+impl SetDockBadgeData {
+    fn new_ptr(badge: Option<String>) -> *mut Self {
+        Box::into_raw(Box::new(SetDockBadgeData { badge }))
+    }
+}
+extern "C" fn set_dock_badge_callback(context: *mut c_void) {
+    unsafe {
+        let context_ptr = context as *mut SetDockBadgeData;
+        {
+            let context = &*context_ptr;
+            let dock_tile: id = msg_send![NSApp(), dockTile];
+            let label = context
+                .badge
+                .as_ref()
+                .map(|badge| IdRef::new(NSString::alloc(nil).init_str(badge)));
+            let label = label.as_ref().map_or(nil, |label| **label);
+            let _: () = msg_send![dock_tile, setBadgeLabel: label];
+        }
+        Box::from_raw(context_ptr);
+    }
+}
+// `NSDockTile` isn't thread-safe.
+pub unsafe fn set_dock_badge_async(badge: Option<String>) {
+    let context = SetDockBadgeData::new_ptr(badge);
+    dispatch_async_f(
+        dispatch_get_main_queue(),
+        context as *mut _,
+        set_dock_badge_callback,
+    );
+}
@@ -1,16 +1,20 @@
+use std::{cell::RefCell, collections::HashMap, fmt, ptr};
+
 use cocoa::{
     appkit::NSImage,
     base::{id, nil},
-    foundation::{NSDictionary, NSPoint, NSString},
+    foundation::{NSDictionary, NSPoint, NSSize, NSString},
 };
-use objc::runtime::Sel;
+use objc::runtime::{Sel, NO, YES};
 
-use crate::window::CursorIcon;
+use super::IdRef;
+use crate::window::{BadCursor, CursorIcon};
 
 pub enum Cursor {
     Native(&'static str),
     Undocumented(&'static str),
     WebKit(&'static str),
+    Custom(CustomCursor),
 }
 
 impl From<CursorIcon> for Cursor {
@@ -93,11 +97,92 @@ impl Cursor {
                 };
                 msg_send![class, performSelector: sel]
             },
-            Cursor::WebKit(cursor_name) => load_webkit_cursor(cursor_name),
+            Cursor::WebKit(cursor_name) => {
+                thread_local! {
+                    // Unlike the native cursors, these are read from disk, so they're kept around
+                    // after the first load.
+                    static WEBKIT_CURSORS: RefCell<HashMap<&'static str, id>> =
+                        RefCell::new(HashMap::new());
+                }
+                WEBKIT_CURSORS.with(|cursors| {
+                    *cursors
+                        .borrow_mut()
+                        .entry(*cursor_name)
+                        .or_insert_with(|| load_webkit_cursor(cursor_name))
+                })
+            },
+            Cursor::Custom(cursor) => *cursor.0,
         }
     }
 }
 
+/// An `NSCursor` created from RGBA data, which is released once the last clone is dropped.
+#[derive(Clone)]
+pub struct CustomCursor(IdRef);
+
+// `NSCursor` is immutable once created, so it's safe to share it between threads.
+unsafe impl Send for CustomCursor {}
+unsafe impl Sync for CustomCursor {}
+
+/// Creates an `NSImage` from 32bpp RGBA pixels, which have already been validated.
+pub unsafe fn image_from_rgba(rgba: &[u8], width: u32, height: u32) -> Option<IdRef> {
+    // `NSAlphaNonpremultipliedBitmapFormat`
+    const NON_PREMULTIPLIED_ALPHA: usize = 1 << 1;
+
+    let bitmap: id = msg_send![class!(NSBitmapImageRep), alloc];
+    let bitmap: id = msg_send![bitmap,
+        initWithBitmapDataPlanes:ptr::null_mut::<*mut u8>()
+        pixelsWide:width as isize
+        pixelsHigh:height as isize
+        bitsPerSample:8 as isize
+        samplesPerPixel:4 as isize
+        hasAlpha:YES
+        isPlanar:NO
+        colorSpaceName:NSString::alloc(nil).init_str("NSDeviceRGBColorSpace")
+        bitmapFormat:NON_PREMULTIPLIED_ALPHA
+        bytesPerRow:(width * 4) as isize
+        bitsPerPixel:32 as isize
+    ];
+    let bitmap = IdRef::new(bitmap).non_nil()?;
+    let bitmap_data: *mut u8 = msg_send![*bitmap, bitmapData];
+    ptr::copy_nonoverlapping(rgba.as_ptr(), bitmap_data, rgba.len());
+
+    let image =
+        IdRef::new(NSImage::alloc(nil).initWithSize_(NSSize::new(width as f64, height as f64)));
+    let _: () = msg_send![*image, addRepresentation:*bitmap];
+    Some(image)
+}
+
+impl CustomCursor {
+    pub fn from_rgba(
+        rgba: &[u8],
+        width: u32,
+        height: u32,
+        hotspot_x: u32,
+        hotspot_y: u32,
+    ) -> Result<Self, BadCursor> {
+        unsafe {
+            let image = image_from_rgba(rgba, width, height).ok_or(BadCursor::CreationFailed)?;
+
+            let cursor: id = msg_send![class!(NSCursor), alloc];
+            let cursor: id = msg_send![cursor,
+                initWithImage:*image
+                hotSpot:NSPoint::new(hotspot_x as f64, hotspot_y as f64)
+            ];
+            IdRef::new(cursor)
+                .non_nil()
+                .map(CustomCursor)
+                .ok_or(BadCursor::CreationFailed)
+        }
+    }
+}
+
+impl fmt::Debug for CustomCursor {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_tuple("CustomCursor").field(&*self.0).finish()
+    }
+}
+
 // Note that loading `busybutclickable` with this code won't animate the frames;
 // instead you'll just get them all in a column.
 pub unsafe fn load_webkit_cursor(cursor_name: &str) -> id {
@@ -124,3 +209,35 @@ pub unsafe fn load_webkit_cursor(cursor_name: &str) -> id {
         hotSpot:point
     ]
 }
+
+// Used to hide the cursor while it's over a view, without affecting it anywhere else.
+pub unsafe fn invisible_cursor() -> id {
+    // 16x16 fully transparent GIF, as produced by
+    // `convert -size 16x16 xc:none cursor.gif`.
+    static CURSOR_BYTES: &[u8] = &[
+        0x47, 0x49, 0x46, 0x38, 0x39, 0x61, 0x10, 0x00, 0x10, 0x00, 0xF0, 0x00, 0x00, 0x00, 0x00,
+        0x00, 0x00, 0x00, 0x00, 0x21, 0xF9, 0x04, 0x01, 0x00, 0x00, 0x00, 0x00, 0x2C, 0x00, 0x00,
+        0x00, 0x00, 0x10, 0x00, 0x10, 0x00, 0x00, 0x02, 0x0E, 0x84, 0x8F, 0xA9, 0xCB, 0xED, 0x0F,
+        0xA3, 0x9C, 0xB4, 0xDA, 0x8B, 0xB3, 0x3E, 0x05, 0x00, 0x3B,
+    ];
+
+    thread_local! {
+        // `NSCursor` can't be created before the application is initialized, so this is lazy.
+        static CURSOR_OBJECT: id = unsafe {
+            let cursor_data: id = msg_send![class!(NSData),
+                dataWithBytesNoCopy:CURSOR_BYTES.as_ptr()
+                length:CURSOR_BYTES.len()
+                freeWhenDone:NO
+            ];
+            let ns_image: id = msg_send![class!(NSImage), alloc];
+            let ns_image: id = msg_send![ns_image, initWithData: cursor_data];
+            let cursor: id = msg_send![class!(NSCursor), alloc];
+            msg_send![cursor,
+                initWithImage:ns_image
+                hotSpot:NSPoint::new(0.0, 0.0)
+            ]
+        };
+    }
+
+    CURSOR_OBJECT.with(|cursor| *cursor)
+}
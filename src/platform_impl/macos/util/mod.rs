@@ -8,10 +8,10 @@ use std::ops::{BitAnd, Deref};
 use cocoa::{
     appkit::{NSApp, NSWindowStyleMask},
     base::{id, nil},
-    foundation::{NSAutoreleasePool, NSRect, NSUInteger},
+    foundation::{NSAutoreleasePool, NSInteger, NSRect, NSUInteger},
 };
 use core_graphics::display::CGDisplay;
-use objc::runtime::{Class, Object, Sel, BOOL, YES};
+use objc::runtime::{Class, Object, NO, YES};
 
 use crate::platform_impl::platform::ffi;
 
@@ -106,8 +106,13 @@ pub unsafe fn open_emoji_picker() {
     let () = msg_send![NSApp(), orderFrontCharacterPalette: nil];
 }
 
-pub extern "C" fn yes(_: &Object, _: Sel) -> BOOL {
-    YES
+/// Whether we're running on at least the given version of macOS.
+pub fn os_version_at_least(major: NSInteger, minor: NSInteger) -> bool {
+    let version: ffi::NSOperatingSystemVersion = unsafe {
+        let process_info: id = msg_send![class!(NSProcessInfo), processInfo];
+        msg_send![process_info, operatingSystemVersion]
+    };
+    (version.major, version.minor) >= (major, minor)
 }
 
 pub unsafe fn toggle_style_mask(window: id, view: id, mask: NSWindowStyleMask, on: bool) {
@@ -123,3 +128,19 @@ pub unsafe fn toggle_style_mask(window: id, view: id, mask: NSWindowStyleMask, o
     // If we don't do this, key handling will break. Therefore, never call `setStyleMask` directly!
     window.makeFirstResponder_(view);
 }
+
+// There's no taskbar on macOS, and the Dock lists applications, so the closest thing is leaving the
+// window out of the places that list windows.
+pub unsafe fn set_skip_taskbar(window: id, skip: bool) {
+    let flags =
+        ffi::NSWindowCollectionBehaviorTransient | ffi::NSWindowCollectionBehaviorIgnoresCycle;
+    let behavior: ffi::NSWindowCollectionBehavior = msg_send![window, collectionBehavior];
+    let behavior = if skip {
+        behavior | flags
+    } else {
+        behavior & !flags
+    };
+    let _: () = msg_send![window, setCollectionBehavior: behavior];
+    let excluded = if skip { YES } else { NO };
+    let _: () = msg_send![window, setExcludedFromWindowsMenu: excluded];
+}
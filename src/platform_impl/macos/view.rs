@@ -9,7 +9,7 @@ use std::{
 use cocoa::{
     appkit::{NSApp, NSEvent, NSEventModifierFlags, NSEventPhase, NSView, NSWindow},
     base::{id, nil},
-    foundation::{NSPoint, NSRect, NSSize, NSString, NSUInteger},
+    foundation::{NSArray, NSPoint, NSRect, NSSize, NSString, NSUInteger},
 };
 use objc::{
     declare::ClassDecl,
@@ -18,8 +18,8 @@ use objc::{
 
 use crate::{
     event::{
-        DeviceEvent, ElementState, Event, KeyboardInput, MouseButton, MouseScrollDelta, TouchPhase,
-        VirtualKeyCode, WindowEvent,
+        DeviceEvent, ElementState, Event, Ime, KeyboardInput, MouseButton, MouseScrollDelta,
+        TouchPhase, VirtualKeyCode, WindowEvent,
     },
     platform_impl::platform::{
         app_state::AppState,
@@ -32,7 +32,7 @@ use crate::{
         window::get_window_id,
         DEVICE_ID,
     },
-    window::WindowId,
+    window::{Theme, WindowId},
 };
 
 #[derive(Default)]
@@ -43,25 +43,47 @@ struct Modifiers {
     alt_pressed: bool,
 }
 
+pub struct CursorState {
+    pub visible: bool,
+    pub cursor: util::Cursor,
+}
+
+impl Default for CursorState {
+    fn default() -> Self {
+        Self {
+            visible: true,
+            cursor: Default::default(),
+        }
+    }
+}
+
 struct ViewState {
     ns_window: id,
-    pub cursor: Arc<Mutex<util::Cursor>>,
+    pub cursor_state: Arc<Mutex<CursorState>>,
     ime_spot: Option<(f64, f64)>,
+    ime_allowed: bool,
     raw_characters: Option<String>,
     is_key_down: bool,
     modifiers: Modifiers,
+    // Input is dropped rather than queued up while this is `false`.
+    input_enabled: bool,
+    // The theme last reported through `ThemeChanged`.
+    theme: Theme,
 }
 
-pub fn new_view(ns_window: id) -> (IdRef, Weak<Mutex<util::Cursor>>) {
-    let cursor = Default::default();
-    let cursor_access = Arc::downgrade(&cursor);
+pub fn new_view(ns_window: id) -> (IdRef, Weak<Mutex<CursorState>>) {
+    let cursor_state = Default::default();
+    let cursor_access = Arc::downgrade(&cursor_state);
     let state = ViewState {
         ns_window,
-        cursor,
+        cursor_state,
         ime_spot: None,
+        ime_allowed: true,
         raw_characters: None,
         is_key_down: false,
         modifiers: Default::default(),
+        input_enabled: true,
+        theme: Theme::Light,
     };
     unsafe {
         // This is free'd in `dealloc`
@@ -77,14 +99,38 @@ pub fn new_view(ns_window: id) -> (IdRef, Weak<Mutex<util::Cursor>>) {
 pub unsafe fn set_ime_position(ns_view: id, input_context: id, x: f64, y: f64) {
     let state_ptr: *mut c_void = *(*ns_view).get_mut_ivar("winitState");
     let state = &mut *(state_ptr as *mut ViewState);
-    let content_rect =
-        NSWindow::contentRectForFrameRect_(state.ns_window, NSWindow::frame(state.ns_window));
-    let base_x = content_rect.origin.x as f64;
-    let base_y = (content_rect.origin.y + content_rect.size.height) as f64;
-    state.ime_spot = Some((base_x + x, base_y - y));
+    // This is kept relative to the view, and only converted to screen coordinates once the input
+    // context asks for it, so that the spot follows the window around.
+    state.ime_spot = Some((x, y));
     let _: () = msg_send![input_context, invalidateCharacterCoordinates];
 }
 
+pub unsafe fn set_ime_allowed(ns_view: id, input_context: id, allowed: bool) {
+    let state_ptr: *mut c_void = *(*ns_view).get_mut_ivar("winitState");
+    let state = &mut *(state_ptr as *mut ViewState);
+    if state.ime_allowed == allowed {
+        return;
+    }
+    state.ime_allowed = allowed;
+    if !allowed {
+        // Drop any composition that's still in progress.
+        let _: () = msg_send![ns_view, unmarkText];
+        let _: () = msg_send![input_context, discardMarkedText];
+    }
+}
+
+pub unsafe fn reset_dead_keys(ns_view: id, input_context: id) {
+    // Dead keys are just marked text until the next key press.
+    let _: () = msg_send![ns_view, unmarkText];
+    let _: () = msg_send![input_context, discardMarkedText];
+}
+
+pub unsafe fn set_input_enabled(ns_view: id, enabled: bool) {
+    let state_ptr: *mut c_void = *(*ns_view).get_mut_ivar("winitState");
+    let state = &mut *(state_ptr as *mut ViewState);
+    state.input_enabled = enabled;
+}
+
 struct ViewClass(*const Class);
 unsafe impl Send for ViewClass {}
 unsafe impl Sync for ViewClass {}
@@ -102,6 +148,10 @@ lazy_static! {
             sel!(viewDidMoveToWindow),
             view_did_move_to_window as extern "C" fn(&Object, Sel),
         );
+        decl.add_method(
+            sel!(viewDidChangeEffectiveAppearance),
+            view_did_change_effective_appearance as extern "C" fn(&Object, Sel),
+        );
         decl.add_method(
             sel!(drawRect:),
             draw_rect as extern "C" fn(&Object, Sel, id),
@@ -238,6 +288,7 @@ lazy_static! {
         );
         decl.add_ivar::<*mut c_void>("winitState");
         decl.add_ivar::<id>("markedText");
+        decl.add_ivar::<id>("trackingArea");
         let protocol = Protocol::get("NSTextInputClient").unwrap();
         decl.add_protocol(&protocol);
         ViewClass(decl.register())
@@ -249,6 +300,10 @@ extern "C" fn dealloc(this: &Object, _sel: Sel) {
         let state: *mut c_void = *this.get_ivar("winitState");
         let marked_text: id = *this.get_ivar("markedText");
         let _: () = msg_send![marked_text, release];
+        let tracking_area: id = *this.get_ivar("trackingArea");
+        if tracking_area != nil {
+            let _: () = msg_send![tracking_area, release];
+        }
         Box::from_raw(state as *mut ViewState);
     }
 }
@@ -261,6 +316,7 @@ extern "C" fn init_with_winit(this: &Object, _sel: Sel, state: *mut c_void) -> i
             let marked_text =
                 <id as NSMutableAttributedString>::init(NSMutableAttributedString::alloc(nil));
             (*this).set_ivar("markedText", marked_text);
+            (*this).set_ivar("trackingArea", nil);
         }
         this
     }
@@ -269,17 +325,76 @@ extern "C" fn init_with_winit(this: &Object, _sel: Sel, state: *mut c_void) -> i
 extern "C" fn view_did_move_to_window(this: &Object, _sel: Sel) {
     trace!("Triggered `viewDidMoveToWindow`");
     unsafe {
-        let rect: NSRect = msg_send![this, visibleRect];
-        let _: () = msg_send![this,
-            addTrackingRect:rect
+        let this = this as *const _ as *mut Object;
+        let old_tracking_area: id = *(*this).get_ivar("trackingArea");
+        if old_tracking_area != nil {
+            let _: () = msg_send![this, removeTrackingArea: old_tracking_area];
+            let _: () = msg_send![old_tracking_area, release];
+        }
+
+        // `NSTrackingInVisibleRect` makes the area follow the visible rect of the view, so the
+        // whole view keeps reporting `mouseEntered` and `mouseExited` after it's resized.
+        let options: NSTrackingAreaOptions =
+            NSTrackingMouseEnteredAndExited | NSTrackingActiveAlways | NSTrackingInVisibleRect;
+        let tracking_area: id = msg_send![class!(NSTrackingArea), alloc];
+        let tracking_area: id = msg_send![tracking_area,
+            initWithRect:NSRect::new(NSPoint::new(0.0, 0.0), NSSize::new(0.0, 0.0))
+            options:options
             owner:this
-            userData:nil
-            assumeInside:NO
+            userInfo:nil
         ];
+        let _: () = msg_send![this, addTrackingArea: tracking_area];
+        (*this).set_ivar("trackingArea", tracking_area);
+
+        // The appearance is inherited from the window, so it's only known from here on.
+        let state_ptr: *mut c_void = *(*this).get_ivar("winitState");
+        let state = &mut *(state_ptr as *mut ViewState);
+        state.theme = effective_theme(this);
     }
     trace!("Completed `viewDidMoveToWindow`");
 }
 
+// `effectiveAppearance` and dark mode were introduced in macOS 10.14.
+unsafe fn effective_theme(view: id) -> Theme {
+    if !util::os_version_at_least(10, 14) {
+        return Theme::Light;
+    }
+    let aqua = IdRef::new(NSString::alloc(nil).init_str("NSAppearanceNameAqua"));
+    let dark_aqua = IdRef::new(NSString::alloc(nil).init_str("NSAppearanceNameDarkAqua"));
+    let names = NSArray::arrayWithObjects(nil, &[*aqua, *dark_aqua]);
+    let appearance: id = msg_send![view, effectiveAppearance];
+    let name: id = msg_send![appearance, bestMatchFromAppearancesWithNames: names];
+    let is_dark: BOOL = if name == nil {
+        NO
+    } else {
+        msg_send![name, isEqualToString: *dark_aqua]
+    };
+    if is_dark == YES {
+        Theme::Dark
+    } else {
+        Theme::Light
+    }
+}
+
+extern "C" fn view_did_change_effective_appearance(this: &Object, _sel: Sel) {
+    trace!("Triggered `viewDidChangeEffectiveAppearance`");
+    unsafe {
+        let this = this as *const _ as *mut Object;
+        let state_ptr: *mut c_void = *(*this).get_ivar("winitState");
+        let state = &mut *(state_ptr as *mut ViewState);
+
+        let theme = effective_theme(this);
+        if theme != state.theme {
+            state.theme = theme;
+            AppState::queue_event(Event::WindowEvent {
+                window_id: WindowId(get_window_id(state.ns_window)),
+                event: WindowEvent::ThemeChanged(theme),
+            });
+        }
+    }
+    trace!("Completed `viewDidChangeEffectiveAppearance`");
+}
+
 extern "C" fn draw_rect(this: &Object, _sel: Sel, rect: id) {
     unsafe {
         let state_ptr: *mut c_void = *this.get_ivar("winitState");
@@ -309,7 +424,12 @@ extern "C" fn reset_cursor_rects(this: &Object, _sel: Sel) {
         let state = &mut *(state_ptr as *mut ViewState);
 
         let bounds: NSRect = msg_send![this, bounds];
-        let cursor = state.cursor.lock().unwrap().load();
+        let cursor_state = state.cursor_state.lock().unwrap();
+        let cursor = if cursor_state.visible {
+            cursor_state.cursor.load()
+        } else {
+            util::invisible_cursor()
+        };
         let _: () = msg_send![this,
             addCursorRect:bounds
             cursor:cursor
@@ -346,15 +466,42 @@ extern "C" fn selected_range(_this: &Object, _sel: Sel) -> NSRange {
     util::EMPTY_RANGE
 }
 
+// Converts an offset in UTF-16 code units, which is what `NSRange` counts, to a byte offset.
+fn utf16_to_byte_offset(string: &str, offset: usize) -> usize {
+    let mut utf16_len = 0;
+    for (index, character) in string.char_indices() {
+        if utf16_len >= offset {
+            return index;
+        }
+        utf16_len += character.len_utf16();
+    }
+    string.len()
+}
+
+unsafe fn ns_string_to_string(string: id) -> String {
+    let slice = slice::from_raw_parts(string.UTF8String() as *const c_uchar, string.len());
+    str::from_utf8_unchecked(slice).to_owned()
+}
+
+unsafe fn queue_ime_event(this: &Object, event: Ime) {
+    let state_ptr: *mut c_void = *this.get_ivar("winitState");
+    let state = &mut *(state_ptr as *mut ViewState);
+    AppState::queue_event(Event::WindowEvent {
+        window_id: WindowId(get_window_id(state.ns_window)),
+        event: WindowEvent::Ime(event),
+    });
+}
+
 extern "C" fn set_marked_text(
     this: &mut Object,
     _sel: Sel,
     string: id,
-    _selected_range: NSRange,
+    selected_range: NSRange,
     _replacement_range: NSRange,
 ) {
     trace!("Triggered `setMarkedText`");
     unsafe {
+        let had_marked_text = has_marked_text(this, sel!(hasMarkedText)) != NO;
         let marked_text_ref: &mut id = this.get_mut_ivar("markedText");
         let _: () = msg_send![(*marked_text_ref), release];
         let marked_text = NSMutableAttributedString::alloc(nil);
@@ -365,6 +512,25 @@ extern "C" fn set_marked_text(
             marked_text.initWithString(string);
         };
         *marked_text_ref = marked_text;
+
+        let text = ns_string_to_string(marked_text.mutableString());
+        if text.is_empty() {
+            if had_marked_text {
+                queue_ime_event(this, Ime::Preedit(String::new(), None));
+                queue_ime_event(this, Ime::Disabled);
+            }
+        } else {
+            if !had_marked_text {
+                queue_ime_event(this, Ime::Enabled);
+            }
+            let start = selected_range.location as usize;
+            let end = start + selected_range.length as usize;
+            let cursor = (
+                utf16_to_byte_offset(&text, start),
+                utf16_to_byte_offset(&text, end),
+            );
+            queue_ime_event(this, Ime::Preedit(text, Some(cursor)));
+        }
     }
     trace!("Completed `setMarkedText`");
 }
@@ -372,11 +538,16 @@ extern "C" fn set_marked_text(
 extern "C" fn unmark_text(this: &Object, _sel: Sel) {
     trace!("Triggered `unmarkText`");
     unsafe {
+        let had_marked_text = has_marked_text(this, sel!(hasMarkedText)) != NO;
         let marked_text: id = *this.get_ivar("markedText");
         let mutable_string = marked_text.mutableString();
         let _: () = msg_send![mutable_string, setString:""];
         let input_context: id = msg_send![this, inputContext];
         let _: () = msg_send![input_context, discardMarkedText];
+        if had_marked_text {
+            queue_ime_event(this, Ime::Preedit(String::new(), None));
+            queue_ime_event(this, Ime::Disabled);
+        }
     }
     trace!("Completed `unmarkText`");
 }
@@ -414,15 +585,21 @@ extern "C" fn first_rect_for_character_range(
         trace!("Triggered `firstRectForCharacterRange`");
         let state_ptr: *mut c_void = *this.get_ivar("winitState");
         let state = &mut *(state_ptr as *mut ViewState);
-        let (x, y) = state.ime_spot.unwrap_or_else(|| {
-            let content_rect = NSWindow::contentRectForFrameRect_(
-                state.ns_window,
-                NSWindow::frame(state.ns_window),
-            );
-            let x = content_rect.origin.x;
-            let y = util::bottom_left_to_top_left(content_rect);
-            (x, y)
-        });
+        let content_rect =
+            NSWindow::contentRectForFrameRect_(state.ns_window, NSWindow::frame(state.ns_window));
+        let (x, y) = match state.ime_spot {
+            Some((x, y)) => {
+                let base_x = content_rect.origin.x as f64;
+                let base_y = (content_rect.origin.y + content_rect.size.height) as f64;
+                (base_x + x, base_y - y)
+            },
+            None => {
+                (
+                    content_rect.origin.x as f64,
+                    util::bottom_left_to_top_left(content_rect),
+                )
+            },
+        };
         trace!("Completed `firstRectForCharacterRange`");
         NSRect::new(NSPoint::new(x as _, y as _), NSSize::new(0.0, 0.0))
     }
@@ -433,6 +610,9 @@ extern "C" fn insert_text(this: &Object, _sel: Sel, string: id, _replacement_ran
     unsafe {
         let state_ptr: *mut c_void = *this.get_ivar("winitState");
         let state = &mut *(state_ptr as *mut ViewState);
+        if !state.input_enabled {
+            return;
+        }
 
         let has_attr = msg_send![string, isKindOfClass: class!(NSAttributedString)];
         let characters = if has_attr {
@@ -448,6 +628,17 @@ extern "C" fn insert_text(this: &Object, _sel: Sel, string: id, _replacement_ran
         let string = str::from_utf8_unchecked(slice);
         state.is_key_down = true;
 
+        // Text inserted while composing is the result of the composition, which also ends it.
+        let marked_text: id = *this.get_ivar("markedText");
+        if marked_text.length() > 0 {
+            let _: () = msg_send![marked_text.mutableString(), setString:""];
+            queue_ime_event(this, Ime::Preedit(String::new(), None));
+            queue_ime_event(this, Ime::Commit(string.to_owned()));
+            queue_ime_event(this, Ime::Disabled);
+            trace!("Completed `insertText`");
+            return;
+        }
+
         // We don't need this now, but it's here if that changes.
         //let event: id = msg_send![NSApp(), currentEvent];
 
@@ -547,6 +738,10 @@ extern "C" fn key_down(this: &Object, _sel: Sel, event: id) {
     unsafe {
         let state_ptr: *mut c_void = *this.get_ivar("winitState");
         let state = &mut *(state_ptr as *mut ViewState);
+        // Returning before `interpretKeyEvents:` also keeps the input method out of it.
+        if !state.input_enabled {
+            return;
+        }
         let window_id = WindowId(get_window_id(state.ns_window));
         let characters = get_characters(event, false);
 
@@ -556,6 +751,8 @@ extern "C" fn key_down(this: &Object, _sel: Sel, event: id) {
         let virtual_keycode = retrieve_keycode(event);
 
         let is_repeat = msg_send![event, isARepeat];
+        // While composing, the input method gets the key, and reports what it did with it.
+        let is_composing = has_marked_text(this, sel!(hasMarkedText)) != NO;
 
         let window_event = Event::WindowEvent {
             window_id,
@@ -571,9 +768,11 @@ extern "C" fn key_down(this: &Object, _sel: Sel, event: id) {
         };
 
         let pass_along = {
-            AppState::queue_event(window_event);
+            if !is_composing {
+                AppState::queue_event(window_event);
+            }
             // Emit `ReceivedCharacter` for key repeats
-            if is_repeat && state.is_key_down {
+            if is_repeat && state.is_key_down && !is_composing {
                 for character in characters.chars() {
                     AppState::queue_event(Event::WindowEvent {
                         window_id,
@@ -586,12 +785,23 @@ extern "C" fn key_down(this: &Object, _sel: Sel, event: id) {
             }
         };
 
-        if pass_along {
+        if pass_along && state.ime_allowed {
             // Some keys (and only *some*, with no known reason) don't trigger `insertText`, while others do...
             // So, we don't give repeats the opportunity to trigger that, since otherwise our hack will cause some
             // keys to generate twice as many characters.
             let array: id = msg_send![class!(NSArray), arrayWithObject: event];
             let _: () = msg_send![this, interpretKeyEvents: array];
+        } else if pass_along {
+            // Without IME, the input method never sees the event, so we emit the characters
+            // produced by the keyboard layout ourselves. AppKit encodes function keys (arrows,
+            // F1, etc.) in the private use area, which we skip.
+            let is_function_key = |c: &char| ('\u{F700}'..='\u{F8FF}').contains(c);
+            for character in characters.chars().filter(|c| !is_function_key(c)) {
+                AppState::queue_event(Event::WindowEvent {
+                    window_id,
+                    event: WindowEvent::ReceivedCharacter(character),
+                });
+            }
         }
     }
     trace!("Completed `keyDown`");
@@ -604,6 +814,9 @@ extern "C" fn key_up(this: &Object, _sel: Sel, event: id) {
         let state = &mut *(state_ptr as *mut ViewState);
 
         state.is_key_down = false;
+        if !state.input_enabled {
+            return;
+        }
 
         let scancode = get_scancode(event) as u32;
         let virtual_keycode = retrieve_keycode(event);
@@ -670,6 +883,15 @@ extern "C" fn flags_changed(this: &Object, _sel: Sel, event: id) {
             events.push_back(window_event);
         }
 
+        if !events.is_empty() {
+            events.push_back(WindowEvent::ModifiersChanged(event_mods(event)));
+        }
+
+        // The modifiers are still tracked, so that they're right once input is enabled again.
+        if !state.input_enabled {
+            return;
+        }
+
         for event in events {
             AppState::queue_event(Event::WindowEvent {
                 window_id: WindowId(get_window_id(state.ns_window)),
@@ -738,6 +960,9 @@ fn mouse_click(this: &Object, event: id, button: MouseButton, button_state: Elem
     unsafe {
         let state_ptr: *mut c_void = *this.get_ivar("winitState");
         let state = &mut *(state_ptr as *mut ViewState);
+        if !state.input_enabled {
+            return;
+        }
 
         let window_event = Event::WindowEvent {
             window_id: WindowId(get_window_id(state.ns_window)),
@@ -781,6 +1006,9 @@ fn mouse_motion(this: &Object, event: id) {
     unsafe {
         let state_ptr: *mut c_void = *this.get_ivar("winitState");
         let state = &mut *(state_ptr as *mut ViewState);
+        if !state.input_enabled {
+            return;
+        }
 
         // We have to do this to have access to the `NSView` trait...
         let view: id = this as *const _ as *mut _;
@@ -889,6 +1117,12 @@ extern "C" fn mouse_exited(this: &Object, _sel: Sel, _event: id) {
 extern "C" fn scroll_wheel(this: &Object, _sel: Sel, event: id) {
     trace!("Triggered `scrollWheel`");
     unsafe {
+        let state_ptr: *mut c_void = *this.get_ivar("winitState");
+        let state = &mut *(state_ptr as *mut ViewState);
+        if !state.input_enabled {
+            return;
+        }
+
         let delta = {
             let (x, y) = (event.scrollingDeltaX(), event.scrollingDeltaY());
             if event.hasPreciseScrollingDeltas() == YES {
@@ -897,11 +1131,19 @@ extern "C" fn scroll_wheel(this: &Object, _sel: Sel, event: id) {
                 MouseScrollDelta::LineDelta(x as f32, y as f32)
             }
         };
-        let phase = match event.phase() {
-            NSEventPhase::NSEventPhaseMayBegin | NSEventPhase::NSEventPhaseBegan => {
+        // Once the fingers are lifted, the scrolling may carry on with momentum, which has a
+        // phase of its own and ends with another `Ended`.
+        let momentum_phase: NSEventPhase = msg_send![event, momentumPhase];
+        let phase = match (event.phase(), momentum_phase) {
+            (NSEventPhase::NSEventPhaseMayBegin, _) | (NSEventPhase::NSEventPhaseBegan, _) => {
                 TouchPhase::Started
             },
-            NSEventPhase::NSEventPhaseEnded => TouchPhase::Ended,
+            (NSEventPhase::NSEventPhaseCancelled, _) | (_, NSEventPhase::NSEventPhaseCancelled) => {
+                TouchPhase::Cancelled
+            },
+            (NSEventPhase::NSEventPhaseEnded, _) | (_, NSEventPhase::NSEventPhaseEnded) => {
+                TouchPhase::Ended
+            },
             _ => TouchPhase::Moved,
         };
 
@@ -910,9 +1152,6 @@ extern "C" fn scroll_wheel(this: &Object, _sel: Sel, event: id) {
             event: DeviceEvent::MouseWheel { delta },
         };
 
-        let state_ptr: *mut c_void = *this.get_ivar("winitState");
-        let state = &mut *(state_ptr as *mut ViewState);
-
         let window_event = Event::WindowEvent {
             window_id: WindowId(get_window_id(state.ns_window)),
             event: WindowEvent::MouseWheel {
@@ -934,6 +1173,9 @@ extern "C" fn pressure_change_with_event(this: &Object, _sel: Sel, event: id) {
     unsafe {
         let state_ptr: *mut c_void = *this.get_ivar("winitState");
         let state = &mut *(state_ptr as *mut ViewState);
+        if !state.input_enabled {
+            return;
+        }
 
         let pressure = event.pressure();
         let stage = event.stage();
@@ -1,9 +1,10 @@
 use std::{
     collections::VecDeque,
     f64,
+    ffi::CStr,
     os::raw::c_void,
     sync::{
-        atomic::{AtomicBool, Ordering},
+        atomic::{AtomicBool, AtomicUsize, Ordering},
         Arc, Mutex, Weak,
     },
 };
@@ -15,7 +16,9 @@ use cocoa::{
         NSWindow, NSWindowButton, NSWindowStyleMask,
     },
     base::{id, nil},
-    foundation::{NSAutoreleasePool, NSDictionary, NSPoint, NSRect, NSSize, NSString},
+    foundation::{
+        NSAutoreleasePool, NSDictionary, NSInteger, NSPoint, NSRect, NSSize, NSString, NSUInteger,
+    },
 };
 use core_graphics::display::CGDisplay;
 use objc::{
@@ -27,18 +30,23 @@ use crate::{
     dpi::{LogicalPosition, LogicalSize},
     error::{ExternalError, NotSupportedError, OsError as RootOsError},
     icon::Icon,
-    monitor::MonitorHandle as RootMonitorHandle,
+    monitor::{MonitorHandle as RootMonitorHandle, VideoMode},
     platform::macos::{ActivationPolicy, WindowExtMacOS},
     platform_impl::platform::{
+        app,
         app_state::AppState,
         ffi,
         monitor::{self, MonitorHandle},
         util::{self, IdRef},
-        view::{self, new_view},
+        view::{self, new_view, CursorState},
         window_delegate::new_delegate,
         OsError,
     },
-    window::{CursorIcon, WindowAttributes, WindowId as RootWindowId},
+    window::{
+        clamp_inner_size, fit_placement, BadCursor, CursorIcon, FullscreenType, PlacementArea,
+        Rect, ResizeDirection, UserAttentionType, WindowAttributes, WindowButtons,
+        WindowId as RootWindowId, WindowPlacement,
+    },
 };
 
 #[derive(Debug, Copy, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
@@ -50,10 +58,11 @@ impl Id {
     }
 }
 
-// Convert the `cocoa::base::id` associated with a window to a usize to use as a unique identifier
-// for the window.
+// Get the unique identifier stored in a `WinitWindow`. The address of the window isn't used, since
+// a new window can be allocated where a closed one used to be, and the id of a closed window must
+// never match a live one.
 pub fn get_window_id(window_cocoa_id: id) -> Id {
-    Id(window_cocoa_id as *const Object as _)
+    Id(unsafe { *(*window_cocoa_id).get_ivar::<usize>("winitWindowId") })
 }
 
 #[derive(Clone, Default)]
@@ -66,6 +75,9 @@ pub struct PlatformSpecificWindowBuilderAttributes {
     pub titlebar_buttons_hidden: bool,
     pub fullsize_content_view: bool,
     pub resize_increments: Option<LogicalSize>,
+    pub has_shadow: Option<bool>,
+    pub tabbing_identifier: Option<String>,
+    pub tab_of: Option<Id>,
 }
 
 fn create_app(activation_policy: ActivationPolicy) -> Option<id> {
@@ -86,8 +98,8 @@ fn create_app(activation_policy: ActivationPolicy) -> Option<id> {
     }
 }
 
-unsafe fn create_view(ns_window: id) -> Option<(IdRef, Weak<Mutex<util::Cursor>>)> {
-    let (ns_view, cursor) = new_view(ns_window);
+unsafe fn create_view(ns_window: id) -> Option<(IdRef, Weak<Mutex<CursorState>>)> {
+    let (ns_view, cursor_state) = new_view(ns_window);
     ns_view.non_nil().map(|ns_view| {
         ns_view.setWantsBestResolutionOpenGLSurface_(YES);
 
@@ -102,7 +114,7 @@ unsafe fn create_view(ns_window: id) -> Option<(IdRef, Weak<Mutex<util::Cursor>>
 
         ns_window.setContentView_(*ns_view);
         ns_window.makeFirstResponder_(*ns_view);
-        (ns_view, cursor)
+        (ns_view, cursor_state)
     })
 }
 
@@ -113,8 +125,8 @@ fn create_window(
     unsafe {
         let pool = NSAutoreleasePool::new(nil);
         let screen = match attrs.fullscreen {
-            Some(ref monitor_id) => {
-                let monitor_screen = monitor_id.inner.ns_screen();
+            Some(ref fullscreen) => {
+                let monitor_screen = fullscreen.monitor().and_then(|m| m.inner.ns_screen());
                 Some(monitor_screen.unwrap_or(appkit::NSScreen::mainScreen(nil)))
             },
             _ => None,
@@ -149,7 +161,8 @@ fn create_window(
             masks &= !NSWindowStyleMask::NSResizableWindowMask;
         }
 
-        if pl_attrs.fullsize_content_view {
+        // A transparent titlebar is only useful when there's content behind it to show through.
+        if pl_attrs.fullsize_content_view || pl_attrs.titlebar_transparent {
             masks |= NSWindowStyleMask::NSFullSizeContentViewWindowMask;
         }
 
@@ -161,6 +174,10 @@ fn create_window(
             NO,
         ));
         let res = ns_window.non_nil().map(|ns_window| {
+            static NEXT_ID: AtomicUsize = AtomicUsize::new(1);
+            (**ns_window).set_ivar("winitWindowId", NEXT_ID.fetch_add(1, Ordering::Relaxed));
+            (**ns_window).set_ivar("winitActive", if attrs.active { YES } else { NO });
+
             let title = IdRef::new(NSString::alloc(nil).init_str(&attrs.title));
             ns_window.setReleasedWhenClosed_(NO);
             ns_window.setTitle_(*title);
@@ -173,19 +190,26 @@ fn create_window(
                 ns_window.setTitleVisibility_(appkit::NSWindowTitleVisibility::NSWindowTitleHidden);
             }
             if pl_attrs.titlebar_buttons_hidden {
-                for titlebar_button in &[
-                    NSWindowButton::NSWindowFullScreenButton,
-                    NSWindowButton::NSWindowMiniaturizeButton,
-                    NSWindowButton::NSWindowCloseButton,
-                    NSWindowButton::NSWindowZoomButton,
-                ] {
-                    let button = ns_window.standardWindowButton_(*titlebar_button);
-                    let _: () = msg_send![button, setHidden: YES];
-                }
+                set_titlebar_buttons_hidden(*ns_window, true);
             }
             if pl_attrs.movable_by_window_background {
                 ns_window.setMovableByWindowBackground_(YES);
             }
+            if let Some(ref tabbing_identifier) = pl_attrs.tabbing_identifier {
+                if util::os_version_at_least(10, 12) {
+                    let tabbing_identifier =
+                        IdRef::new(NSString::alloc(nil).init_str(tabbing_identifier));
+                    let _: () = msg_send![*ns_window, setTabbingIdentifier: *tabbing_identifier];
+                }
+            }
+            // A transparent window without decorations is usually drawing a shape of its own,
+            // which the rectangular shadow wouldn't follow.
+            let has_shadow = pl_attrs
+                .has_shadow
+                .unwrap_or(!(attrs.transparent && !attrs.decorations));
+            if !has_shadow {
+                let _: () = msg_send![*ns_window, setHasShadow: NO];
+            }
 
             if attrs.always_on_top {
                 let _: () = msg_send![
@@ -194,6 +218,17 @@ fn create_window(
                 ];
             }
 
+            if attrs.skip_taskbar {
+                util::set_skip_taskbar(*ns_window, true);
+            }
+
+            if attrs.content_protected {
+                let _: () = msg_send![
+                    *ns_window,
+                    setSharingType: ffi::NSWindowSharingType::NSWindowSharingNone
+                ];
+            }
+
             if let Some(increments) = pl_attrs.resize_increments {
                 let (x, y) = (increments.width, increments.height);
                 if x >= 1.0 && y >= 1.0 {
@@ -218,27 +253,45 @@ lazy_static! {
     static ref WINDOW_CLASS: WindowClass = unsafe {
         let window_superclass = class!(NSWindow);
         let mut decl = ClassDecl::new("WinitWindow", window_superclass).unwrap();
+        decl.add_ivar::<usize>("winitWindowId");
+        decl.add_ivar::<BOOL>("winitActive");
         decl.add_method(
             sel!(canBecomeMainWindow),
-            util::yes as extern "C" fn(&Object, Sel) -> BOOL,
+            can_become_active as extern "C" fn(&Object, Sel) -> BOOL,
         );
         decl.add_method(
             sel!(canBecomeKeyWindow),
-            util::yes as extern "C" fn(&Object, Sel) -> BOOL,
+            can_become_active as extern "C" fn(&Object, Sel) -> BOOL,
         );
         WindowClass(decl.register())
     };
 }
 
+// Windows built with `with_active(false)` never become key, so they don't take keyboard focus.
+extern "C" fn can_become_active(this: &Object, _: Sel) -> BOOL {
+    unsafe { *this.get_ivar("winitActive") }
+}
+
 #[derive(Default)]
 pub struct SharedState {
     pub resizable: bool,
     pub fullscreen: Option<RootMonitorHandle>,
+    /// The fullscreen state reported by `Window::fullscreen`. Unlike `fullscreen`, this changes
+    /// as soon as it's requested, instead of once the transition is over.
+    pub target_fullscreen: Option<FullscreenType>,
+    /// The video mode the monitor is switched to in exclusive fullscreen.
+    video_mode: Option<VideoMode>,
     pub maximized: bool,
     pub standard_frame: Option<NSRect>,
     is_simple_fullscreen: bool,
+    /// The frame to restore when leaving simple fullscreen. This is kept apart from
+    /// `standard_frame` so that entering simple fullscreen while maximized doesn't lose the frame
+    /// the window unzooms to.
+    simple_fullscreen_frame: Option<NSRect>,
     pub saved_style: Option<NSWindowStyleMask>,
     save_presentation_opts: Option<NSApplicationPresentationOptions>,
+    /// The identifier of the last request made with `request_user_attention`, for cancelling it.
+    user_attention_request: Option<NSInteger>,
 }
 
 impl SharedState {
@@ -259,6 +312,7 @@ impl From<WindowAttributes> for SharedState {
             // the initial value and the first `set_fullscreen` call would be
             // identical, resulting in a no-op.
             fullscreen: None,
+            video_mode: attribs.video_mode,
             maximized: attribs.maximized,
             ..Default::default()
         }
@@ -271,8 +325,9 @@ pub struct UnownedWindow {
     input_context: IdRef, // never changes
     pub shared_state: Arc<Mutex<SharedState>>,
     decorations: AtomicBool,
-    cursor: Weak<Mutex<util::Cursor>>,
-    cursor_visible: AtomicBool,
+    closed: AtomicBool,
+    cursor_state: Weak<Mutex<CursorState>>,
+    blurred: AtomicBool,
 }
 
 unsafe impl Send for UnownedWindow {}
@@ -289,6 +344,16 @@ impl UnownedWindow {
             }
         }
 
+        if let (Some(FullscreenType::Exclusive(monitor)), Some(video_mode)) =
+            (&win_attribs.fullscreen, &win_attribs.video_mode)
+        {
+            if !monitor.video_modes().any(|mode| mode == *video_mode) {
+                return Err(os_error!(OsError::CreationError(
+                    "The video mode isn't supported by the monitor"
+                )));
+            }
+        }
+
         let pool = unsafe { NSAutoreleasePool::new(nil) };
 
         let ns_app = create_app(pl_attribs.activation_policy).ok_or_else(|| {
@@ -301,7 +366,7 @@ impl UnownedWindow {
             os_error!(OsError::CreationError("Couldn't create `NSWindow`"))
         })?;
 
-        let (ns_view, cursor) = unsafe { create_view(*ns_window) }.ok_or_else(|| {
+        let (ns_view, cursor_state) = unsafe { create_view(*ns_window) }.ok_or_else(|| {
             unsafe { pool.drain() };
             os_error!(OsError::CreationError("Couldn't create `NSView`"))
         })?;
@@ -314,7 +379,9 @@ impl UnownedWindow {
                 ns_window.setBackgroundColor_(NSColor::clearColor(nil));
             }
 
-            ns_app.activateIgnoringOtherApps_(YES);
+            if win_attribs.active {
+                ns_app.activateIgnoringOtherApps_(YES);
+            }
 
             win_attribs
                 .min_inner_size
@@ -340,7 +407,9 @@ impl UnownedWindow {
         let fullscreen = win_attribs.fullscreen.take();
         let maximized = win_attribs.maximized;
         let visible = win_attribs.visible;
+        let active = win_attribs.active;
         let decorations = win_attribs.decorations;
+        let window_icon = win_attribs.window_icon.take();
 
         let window = Arc::new(UnownedWindow {
             ns_view,
@@ -348,32 +417,54 @@ impl UnownedWindow {
             input_context,
             shared_state: Arc::new(Mutex::new(win_attribs.into())),
             decorations: AtomicBool::new(decorations),
-            cursor,
-            cursor_visible: AtomicBool::new(true),
+            closed: AtomicBool::new(false),
+            cursor_state,
+            blurred: AtomicBool::new(false),
         });
 
         let delegate = new_delegate(&window, fullscreen.is_some());
 
+        if window_icon.is_some() {
+            window.set_window_icon(window_icon);
+        }
+
+        if let Some(tab_of) = pl_attribs.tab_of {
+            unsafe {
+                if let Some(ns_window) = ns_window_with_id(tab_of) {
+                    if util::os_version_at_least(10, 12) {
+                        let _: () = msg_send![
+                            ns_window,
+                            addTabbedWindow: *window.ns_window
+                            ordered: ffi::NSWindowOrderingMode::NSWindowAbove
+                        ];
+                    }
+                }
+            }
+        }
+
         // Set fullscreen mode after we setup everything
-        if let Some(monitor) = fullscreen {
-            if monitor.inner != window.current_monitor().inner {
-                // To do this with native fullscreen, we probably need to
-                // warp the window... while we could use
-                // `enterFullScreenMode`, they're idiomatically different
-                // fullscreen modes, so we'd have to support both anyway.
-                unimplemented!();
+        if let Some(fullscreen) = fullscreen {
+            if let Some(monitor) = fullscreen.monitor() {
+                if monitor.inner != window.current_monitor_inner().inner {
+                    // To do this with native fullscreen, we probably need to
+                    // warp the window... while we could use
+                    // `enterFullScreenMode`, they're idiomatically different
+                    // fullscreen modes, so we'd have to support both anyway.
+                    unimplemented!();
+                }
             }
-            window.set_fullscreen(Some(monitor));
+            window.set_fullscreen(Some(fullscreen));
         }
 
         // Setting the window as key has to happen *after* we set the fullscreen
         // state, since otherwise we'll briefly see the window at normal size
         // before it transitions.
         unsafe {
-            if visible {
-                window.ns_window.makeKeyAndOrderFront_(nil);
-            } else {
-                window.ns_window.makeKeyWindow();
+            match (visible, active) {
+                (true, true) => window.ns_window.makeKeyAndOrderFront_(nil),
+                (true, false) => window.ns_window.orderFront_(nil),
+                (false, true) => window.ns_window.makeKeyWindow(),
+                (false, false) => (),
             }
         }
 
@@ -404,6 +495,23 @@ impl UnownedWindow {
         }
     }
 
+    pub fn is_focused(&self) -> bool {
+        let is_key_window: BOOL = unsafe { msg_send![*self.ns_window, isKeyWindow] };
+        is_key_window == YES
+    }
+
+    pub fn get_title(&self) -> String {
+        unsafe {
+            let title: id = msg_send![*self.ns_window, title];
+            if title == nil {
+                return String::new();
+            }
+            CStr::from_ptr(title.UTF8String())
+                .to_string_lossy()
+                .into_owned()
+        }
+    }
+
     pub fn set_visible(&self, visible: bool) {
         match visible {
             true => unsafe { util::make_key_and_order_front_async(*self.ns_window) },
@@ -411,10 +519,30 @@ impl UnownedWindow {
         }
     }
 
+    pub fn close(&self) {
+        // The window stays retained by `ns_window`, so messaging it afterwards is harmless.
+        if !self.closed.swap(true, Ordering::AcqRel) && *self.ns_window != nil {
+            unsafe { util::close_async(*self.ns_window) };
+        }
+    }
+
+    pub fn is_visible(&self) -> Option<bool> {
+        // Unlike on other platforms, minimized windows aren't visible to AppKit.
+        let (is_visible, is_miniaturized): (BOOL, BOOL) = unsafe {
+            (
+                msg_send![*self.ns_window, isVisible],
+                msg_send![*self.ns_window, isMiniaturized],
+            )
+        };
+        Some(is_visible == YES || is_miniaturized == YES)
+    }
+
     pub fn request_redraw(&self) {
         AppState::queue_redraw(RootWindowId(self.id()));
     }
 
+    pub fn pre_present_notify(&self) {}
+
     pub fn outer_position(&self) -> Result<LogicalPosition, NotSupportedError> {
         let frame_rect = unsafe { NSWindow::frame(*self.ns_window) };
         Ok((
@@ -463,10 +591,43 @@ impl UnownedWindow {
     }
 
     #[inline]
-    pub fn set_inner_size(&self, size: LogicalSize) {
+    pub fn set_inner_size(&self, size: LogicalSize) -> Option<LogicalSize> {
+        let inner_size = self.inner_size();
+        let outer_size = self.outer_size();
+        let (decoration_width, decoration_height) = (
+            outer_size.width - inner_size.width,
+            outer_size.height - inner_size.height,
+        );
+        let (min_size, max_size, available) = unsafe {
+            // These are set for the whole window by `set_min_inner_size` and `set_max_inner_size`.
+            let min_size: NSSize = msg_send![*self.ns_window, minSize];
+            let max_size: NSSize = msg_send![*self.ns_window, maxSize];
+            let available = self
+                .current_monitor_inner()
+                .inner
+                .ns_screen()
+                .map(|screen| NSScreen::visibleFrame(screen).size);
+            let to_inner_size = |size: NSSize| {
+                LogicalSize::new(
+                    size.width as f64 - decoration_width,
+                    size.height as f64 - decoration_height,
+                )
+            };
+            (
+                to_inner_size(min_size),
+                to_inner_size(max_size),
+                available.map(to_inner_size),
+            )
+        };
+        let size = clamp_inner_size(size, Some(min_size), Some(max_size), available);
+        if size == inner_size {
+            return Some(size);
+        }
+        // `windowDidResize:` sends the `Resized` event once the size is applied.
         unsafe {
             util::set_content_size_async(*self.ns_window, size);
         }
+        Some(size)
     }
 
     pub fn set_min_inner_size(&self, dimensions: Option<LogicalSize>) {
@@ -483,6 +644,69 @@ impl UnownedWindow {
         }
     }
 
+    pub fn get_placement(&self) -> Option<WindowPlacement> {
+        let maximized = self.is_zoomed();
+        let frame = {
+            let shared_state_lock = self.shared_state.lock().unwrap();
+            // While zoomed with `set_maximized`, this is the frame the window is restored to.
+            match shared_state_lock.standard_frame {
+                Some(standard_frame) if maximized => standard_frame,
+                _ => unsafe { NSWindow::frame(*self.ns_window) },
+            }
+        };
+        let content_rect = unsafe { NSWindow::contentRectForFrameRect_(*self.ns_window, frame) };
+        Some(WindowPlacement {
+            position: (frame.origin.x as f64, util::bottom_left_to_top_left(frame)).into(),
+            inner_size: (
+                content_rect.size.width as f64,
+                content_rect.size.height as f64,
+            )
+                .into(),
+            maximized,
+            monitor_native_id: Some(
+                self.current_monitor_inner()
+                    .inner
+                    .native_identifier()
+                    .to_string(),
+            ),
+        })
+    }
+
+    pub fn set_placement(&self, placement: &WindowPlacement) {
+        let areas: Vec<_> = monitor::available_monitors()
+            .iter()
+            .map(|monitor| {
+                PlacementArea {
+                    native_id: monitor.native_identifier().to_string(),
+                    position: monitor.position(),
+                    size: monitor.size(),
+                    hidpi_factor: monitor.hidpi_factor(),
+                }
+            })
+            .collect();
+        let (position, size, dpi_factor) = match fit_placement(placement, &areas) {
+            Some(fitted) => fitted,
+            None => return,
+        };
+        // These are all queued on the main thread, so the window is unzoomed before it's moved,
+        // and the frame saved when zooming it again is the restored one.
+        let is_zoomed = self.is_zoomed();
+        let shared_state = Arc::downgrade(&self.shared_state);
+        unsafe {
+            if is_zoomed {
+                util::set_maximized_async(*self.ns_window, true, false, shared_state.clone());
+            }
+            self.set_outer_position(LogicalPosition::from_physical(position, dpi_factor));
+            util::set_content_size_async(
+                *self.ns_window,
+                LogicalSize::from_physical(size, dpi_factor),
+            );
+            if placement.maximized {
+                util::set_maximized_async(*self.ns_window, false, true, shared_state);
+            }
+        }
+    }
+
     #[inline]
     pub fn set_resizable(&self, resizable: bool) {
         let fullscreen = {
@@ -505,8 +729,31 @@ impl UnownedWindow {
 
     pub fn set_cursor_icon(&self, cursor: CursorIcon) {
         let cursor = util::Cursor::from(cursor);
-        if let Some(cursor_access) = self.cursor.upgrade() {
-            *cursor_access.lock().unwrap() = cursor;
+        if let Some(cursor_access) = self.cursor_state.upgrade() {
+            cursor_access.lock().unwrap().cursor = cursor;
+        }
+        unsafe {
+            let _: () = msg_send![*self.ns_window,
+                invalidateCursorRectsForView:*self.ns_view
+            ];
+        }
+    }
+
+    #[inline]
+    pub fn create_custom_cursor(
+        &self,
+        rgba: &[u8],
+        width: u32,
+        height: u32,
+        hotspot_x: u32,
+        hotspot_y: u32,
+    ) -> Result<util::CustomCursor, BadCursor> {
+        util::CustomCursor::from_rgba(rgba, width, height, hotspot_x, hotspot_y)
+    }
+
+    pub fn set_custom_cursor(&self, cursor: &util::CustomCursor) {
+        if let Some(cursor_access) = self.cursor_state.upgrade() {
+            cursor_access.lock().unwrap().cursor = util::Cursor::Custom(cursor.clone());
         }
         unsafe {
             let _: () = msg_send![*self.ns_window,
@@ -524,16 +771,20 @@ impl UnownedWindow {
 
     #[inline]
     pub fn set_cursor_visible(&self, visible: bool) {
-        let cursor_class = class!(NSCursor);
-        // macOS uses a "hide counter" like Windows does, so we avoid incrementing it more than once.
-        // (otherwise, `hide_cursor(false)` would need to be called n times!)
-        if visible != self.cursor_visible.load(Ordering::Acquire) {
-            if visible {
-                let _: () = unsafe { msg_send![cursor_class, unhide] };
-            } else {
-                let _: () = unsafe { msg_send![cursor_class, hide] };
+        // Rather than using `[NSCursor hide]`, which hides the cursor for the whole application
+        // until it's unhidden, we swap in a transparent cursor rect over the view. That way the
+        // cursor reappears as soon as it leaves the window.
+        if let Some(cursor_access) = self.cursor_state.upgrade() {
+            let mut cursor_state = cursor_access.lock().unwrap();
+            if visible != cursor_state.visible {
+                cursor_state.visible = visible;
+                drop(cursor_state);
+                unsafe {
+                    let _: () = msg_send![*self.ns_window,
+                        invalidateCursorRectsForView:*self.ns_view
+                    ];
+                }
             }
-            self.cursor_visible.store(visible, Ordering::Release);
         }
     }
 
@@ -560,6 +811,51 @@ impl UnownedWindow {
         Ok(())
     }
 
+    #[inline]
+    pub fn request_user_attention(&self, request_type: Option<UserAttentionType>) {
+        let mut shared_state_lock = self.shared_state.lock().unwrap();
+        unsafe {
+            if let Some(request_id) = shared_state_lock.user_attention_request.take() {
+                let _: () = msg_send![NSApp(), cancelUserAttentionRequest: request_id];
+            }
+            if let Some(request_type) = request_type {
+                let request_type = match request_type {
+                    UserAttentionType::Critical => NSRequestUserAttentionType::NSCriticalRequest,
+                    UserAttentionType::Informational => {
+                        NSRequestUserAttentionType::NSInformationalRequest
+                    },
+                };
+                let request_id: NSInteger = msg_send![NSApp(), requestUserAttention: request_type];
+                shared_state_lock.user_attention_request = Some(request_id);
+            }
+        }
+    }
+
+    #[inline]
+    pub fn drag_window(&self) -> Result<(), ExternalError> {
+        unsafe {
+            let buttons: NSUInteger = msg_send![class!(NSEvent), pressedMouseButtons];
+            if buttons & 1 == 0 {
+                return Err(ExternalError::Os(os_error!(OsError::Misc(
+                    "Window drag could not be initiated: the left mouse button isn't pressed"
+                ))));
+            }
+            let event: id = msg_send![NSApp(), currentEvent];
+            let _: () = msg_send![*self.ns_window, performWindowDragWithEvent: event];
+        }
+        Ok(())
+    }
+
+    #[inline]
+    pub fn drag_resize_window(&self, _direction: ResizeDirection) -> Result<(), ExternalError> {
+        Err(ExternalError::NotSupported(NotSupportedError::new()))
+    }
+
+    #[inline]
+    pub fn show_window_menu(&self, _position: LogicalPosition) {
+        // There's no such thing as a window menu on macOS.
+    }
+
     pub(crate) fn is_zoomed(&self) -> bool {
         // because `isZoomed` doesn't work if the window's borderless,
         // we make it resizable temporalily.
@@ -600,6 +896,13 @@ impl UnownedWindow {
             let mut shared_state_lock = self.shared_state.lock().unwrap();
 
             shared_state_lock.fullscreen = None;
+            // The user left exclusive fullscreen on their own.
+            if let Some(FullscreenType::Exclusive(_)) = shared_state_lock.target_fullscreen {
+                if shared_state_lock.video_mode.is_some() {
+                    monitor::restore_video_modes();
+                }
+            }
+            shared_state_lock.target_fullscreen = None;
 
             let mask = self.saved_style(&mut *shared_state_lock);
 
@@ -627,19 +930,49 @@ impl UnownedWindow {
     }
 
     #[inline]
-    pub fn fullscreen(&self) -> Option<RootMonitorHandle> {
+    pub fn get_maximized(&self) -> bool {
+        !self.closed.load(Ordering::Acquire) && self.is_zoomed()
+    }
+
+    #[inline]
+    pub fn fullscreen(&self) -> Option<FullscreenType> {
         let shared_state_lock = self.shared_state.lock().unwrap();
-        shared_state_lock.fullscreen.clone()
+        shared_state_lock.target_fullscreen.clone()
     }
 
     #[inline]
     /// TODO: Right now set_fullscreen do not work on switching monitors
     /// in fullscreen mode
-    pub fn set_fullscreen(&self, monitor: Option<RootMonitorHandle>) {
-        let shared_state_lock = self.shared_state.lock().unwrap();
+    pub fn set_fullscreen(&self, fullscreen: Option<FullscreenType>) {
+        // Both kinds of fullscreen use the native `toggleFullScreen:`, which always covers the
+        // screen the window is on.
+        let fullscreen = fullscreen.map(|fullscreen| {
+            match fullscreen {
+                FullscreenType::Borderless(None) => {
+                    FullscreenType::Borderless(Some(self.current_monitor_inner()))
+                },
+                fullscreen => fullscreen,
+            }
+        });
+        let monitor = fullscreen.as_ref().and_then(|f| f.monitor().cloned());
+        let mut shared_state_lock = self.shared_state.lock().unwrap();
         if shared_state_lock.is_simple_fullscreen {
             return;
         }
+        if let Some(ref video_mode) = shared_state_lock.video_mode {
+            match (&shared_state_lock.target_fullscreen, &fullscreen) {
+                (Some(FullscreenType::Exclusive(_)), Some(FullscreenType::Exclusive(_))) => (),
+                (Some(FullscreenType::Exclusive(_)), _) => monitor::restore_video_modes(),
+                (_, Some(FullscreenType::Exclusive(monitor))) => {
+                    // Still go fullscreen in the current video mode if this fails.
+                    if let Err(e) = monitor.inner.set_video_mode(video_mode) {
+                        warn!("Failed to switch the video mode: {}", e);
+                    }
+                },
+                _ => (),
+            }
+        }
+        shared_state_lock.target_fullscreen = fullscreen;
 
         let not_fullscreen = {
             trace!("Locked shared state in `set_fullscreen`");
@@ -703,10 +1036,26 @@ impl UnownedWindow {
                 }
                 new_mask
             };
+            // Changing the style mask keeps the frame, so the title bar would otherwise take its
+            // space from the content.
+            let inner_size = self.inner_size();
             self.set_style_mask_async(new_mask);
+            unsafe {
+                util::set_content_size_async(*self.ns_window, inner_size);
+            }
         }
     }
 
+    #[inline]
+    pub fn get_resizable(&self) -> bool {
+        self.shared_state.lock().unwrap().resizable
+    }
+
+    #[inline]
+    pub fn get_decorations(&self) -> bool {
+        self.decorations.load(Ordering::Acquire)
+    }
+
     #[inline]
     pub fn set_always_on_top(&self, always_on_top: bool) {
         let level = if always_on_top {
@@ -718,15 +1067,55 @@ impl UnownedWindow {
     }
 
     #[inline]
-    pub fn set_window_icon(&self, _icon: Option<Icon>) {
-        // macOS doesn't have window icons. Though, there is
-        // `setRepresentedFilename`, but that's semantically distinct and should
-        // only be used when the window is in some way representing a specific
-        // file/directory. For instance, Terminal.app uses this for the CWD.
-        // Anyway, that should eventually be implemented as
-        // `WindowBuilderExt::with_represented_file` or something, and doesn't
-        // have anything to do with `set_window_icon`.
+    pub fn set_skip_taskbar(&self, skip: bool) {
+        unsafe { util::set_skip_taskbar_async(*self.ns_window, skip) };
+    }
+
+    #[inline]
+    pub fn set_enabled_buttons(&self, buttons: WindowButtons) {
+        unsafe { util::set_enabled_buttons_async(*self.ns_window, buttons) };
+    }
+
+    #[inline]
+    pub fn set_content_protected(&self, protected: bool) {
+        let sharing_type = if protected {
+            ffi::NSWindowSharingType::NSWindowSharingNone
+        } else {
+            ffi::NSWindowSharingType::NSWindowSharingReadOnly
+        };
+        unsafe {
+            let _: () = msg_send![*self.ns_window, setSharingType: sharing_type];
+        }
+    }
+
+    #[inline]
+    pub fn set_opacity(&self, opacity: f32) {
+        unsafe {
+            let _: () = msg_send![*self.ns_window, setAlphaValue: opacity as CGFloat];
+        }
+    }
+
+    #[inline]
+    pub fn set_shape(&self, _shape: Option<&[Rect]>) -> Result<(), ExternalError> {
+        Err(ExternalError::NotSupported(NotSupportedError::new()))
+    }
+
+    #[inline]
+    pub fn set_cursor_hittest(&self, hittest: bool) -> Result<(), ExternalError> {
+        unsafe {
+            let ignores_mouse_events = if hittest { NO } else { YES };
+            let _: () = msg_send![*self.ns_window, setIgnoresMouseEvents: ignores_mouse_events];
+        }
+        Ok(())
+    }
+
+    #[inline]
+    pub fn set_window_icon(&self, icon: Option<Icon>) {
+        // macOS doesn't have window icons, so the closest match is the application icon in the
+        // Dock. There is `setRepresentedFilename`, but that's semantically distinct and should
+        // only be used when the window is in some way representing a specific file/directory.
         // https://developer.apple.com/library/content/documentation/Cocoa/Conceptual/WinPanel/Tasks/SettingWindowTitle.html
+        app::set_dock_icon(icon);
     }
 
     #[inline]
@@ -742,9 +1131,57 @@ impl UnownedWindow {
     }
 
     #[inline]
-    pub fn current_monitor(&self) -> RootMonitorHandle {
+    pub fn reset_dead_keys(&self) {
+        unsafe {
+            view::reset_dead_keys(*self.ns_view, *self.input_context);
+        }
+    }
+
+    #[inline]
+    pub fn set_ime_allowed(&self, allowed: bool) {
+        unsafe {
+            view::set_ime_allowed(*self.ns_view, *self.input_context, allowed);
+        }
+    }
+
+    #[inline]
+    pub fn set_enabled(&self, enabled: bool) {
+        unsafe {
+            view::set_input_enabled(*self.ns_view, enabled);
+            if !enabled {
+                // Hand the keyboard focus over to the parent window, if there's one.
+                let is_key: BOOL = msg_send![*self.ns_window, isKeyWindow];
+                let parent: id = msg_send![*self.ns_window, parentWindow];
+                if is_key == YES && parent != nil {
+                    let () = msg_send![parent, makeKeyWindow];
+                }
+            }
+        }
+    }
+
+    #[inline]
+    pub fn current_monitor(&self) -> Option<RootMonitorHandle> {
         unsafe {
             let screen: id = msg_send![*self.ns_window, screen];
+            if screen == nil {
+                None
+            } else {
+                Some(self.current_monitor_inner())
+            }
+        }
+    }
+
+    // Falls back to the primary monitor while the window is entirely offscreen.
+    #[inline]
+    pub(crate) fn current_monitor_inner(&self) -> RootMonitorHandle {
+        unsafe {
+            let screen: id = msg_send![*self.ns_window, screen];
+            // `screen` is nil while the window is entirely offscreen.
+            if screen == nil {
+                return RootMonitorHandle {
+                    inner: monitor::primary_monitor(),
+                };
+            }
             let desc = NSScreen::deviceDescription(screen);
             let key = IdRef::new(NSString::alloc(nil).init_str("NSScreenNumber"));
             let value = NSDictionary::valueForKey_(desc, *key);
@@ -777,16 +1214,6 @@ impl WindowExtMacOS for UnownedWindow {
         *self.ns_view as *mut _
     }
 
-    #[inline]
-    fn request_user_attention(&self, is_critical: bool) {
-        unsafe {
-            NSApp().requestUserAttention_(match is_critical {
-                true => NSRequestUserAttentionType::NSCriticalRequest,
-                false => NSRequestUserAttentionType::NSInformationalRequest,
-            });
-        }
-    }
-
     #[inline]
     fn simple_fullscreen(&self) -> bool {
         let shared_state_lock = self.shared_state.lock().unwrap();
@@ -812,7 +1239,7 @@ impl WindowExtMacOS for UnownedWindow {
 
             if fullscreen {
                 // Remember the original window's settings
-                shared_state_lock.standard_frame = Some(NSWindow::frame(*self.ns_window));
+                shared_state_lock.simple_fullscreen_frame = Some(NSWindow::frame(*self.ns_window));
                 shared_state_lock.saved_style = Some(self.ns_window.styleMask());
                 shared_state_lock.save_presentation_opts = Some(app.presentationOptions_());
 
@@ -859,11 +1286,14 @@ impl WindowExtMacOS for UnownedWindow {
                 self.set_style_mask_async(new_mask);
                 shared_state_lock.is_simple_fullscreen = false;
 
-                if let Some(presentation_opts) = shared_state_lock.save_presentation_opts {
+                if let Some(presentation_opts) = shared_state_lock.save_presentation_opts.take() {
                     app.setPresentationOptions_(presentation_opts);
                 }
 
-                let frame = shared_state_lock.saved_standard_frame();
+                let frame = shared_state_lock
+                    .simple_fullscreen_frame
+                    .take()
+                    .unwrap_or_else(|| shared_state_lock.saved_standard_frame());
                 NSWindow::setFrame_display_(*self.ns_window, frame, YES);
                 NSWindow::setMovable_(*self.ns_window, YES);
 
@@ -871,15 +1301,133 @@ impl WindowExtMacOS for UnownedWindow {
             }
         }
     }
+
+    #[inline]
+    fn set_subtitle(&self, subtitle: &str) {
+        if util::os_version_at_least(11, 0) {
+            unsafe { util::set_subtitle_async(*self.ns_window, subtitle.to_string()) };
+        }
+    }
+
+    #[inline]
+    fn set_titlebar_hidden(&self, hidden: bool) {
+        {
+            let shared_state_lock = self.shared_state.lock().unwrap();
+            if shared_state_lock.fullscreen.is_some() || shared_state_lock.is_simple_fullscreen {
+                return;
+            }
+        }
+        if !self.decorations.load(Ordering::Acquire) {
+            return;
+        }
+
+        unsafe { util::set_titlebar_hidden_async(*self.ns_window, *self.ns_view, hidden) };
+    }
+
+    #[inline]
+    fn set_titlebar_buttons_hidden(&self, hidden: bool) {
+        unsafe { set_titlebar_buttons_hidden(*self.ns_window, hidden) };
+    }
+
+    #[inline]
+    fn has_shadow(&self) -> bool {
+        let has_shadow: BOOL = unsafe { msg_send![*self.ns_window, hasShadow] };
+        has_shadow == YES
+    }
+
+    #[inline]
+    fn set_has_shadow(&self, has_shadow: bool) {
+        let has_shadow = if has_shadow { YES } else { NO };
+        unsafe {
+            let _: () = msg_send![*self.ns_window, setHasShadow: has_shadow];
+        }
+    }
+
+    #[inline]
+    fn select_next_tab(&self) {
+        if util::os_version_at_least(10, 12) {
+            unsafe {
+                let _: () = msg_send![*self.ns_window, selectNextTab: nil];
+            }
+        }
+    }
+
+    #[inline]
+    fn select_previous_tab(&self) {
+        if util::os_version_at_least(10, 12) {
+            unsafe {
+                let _: () = msg_send![*self.ns_window, selectPreviousTab: nil];
+            }
+        }
+    }
+
+    #[inline]
+    fn num_tabs(&self) -> usize {
+        if !util::os_version_at_least(10, 12) {
+            return 1;
+        }
+        unsafe {
+            // This is `nil` while the window isn't in a tab group.
+            let tabbed_windows: id = msg_send![*self.ns_window, tabbedWindows];
+            if tabbed_windows == nil {
+                1
+            } else {
+                let count: NSUInteger = msg_send![tabbed_windows, count];
+                count as usize
+            }
+        }
+    }
+
+    #[inline]
+    fn invalidate_shadow(&self) {
+        unsafe {
+            let _: () = msg_send![*self.ns_window, invalidateShadow];
+        }
+    }
+
+    fn set_blur(&self, blur: bool) -> Result<(), ExternalError> {
+        let is_opaque: BOOL = unsafe { msg_send![*self.ns_window, isOpaque] };
+        if is_opaque == YES || !util::os_version_at_least(10, 10) {
+            return Err(ExternalError::NotSupported(NotSupportedError::new()));
+        }
+
+        if blur != self.blurred.swap(blur, Ordering::AcqRel) {
+            unsafe { util::set_blur_async(*self.ns_window, *self.ns_view, blur) };
+        }
+        Ok(())
+    }
 }
 
 impl Drop for UnownedWindow {
     fn drop(&mut self) {
         trace!("Dropping `UnownedWindow` ({:?})", self as *mut _);
         // Close the window if it has not yet been closed.
-        if *self.ns_window != nil {
-            unsafe { util::close_async(*self.ns_window) };
-        }
+        self.close();
+    }
+}
+
+// Windows are looked up by id, so that `WindowBuilder` doesn't hold on to an `NSWindow`.
+unsafe fn ns_window_with_id(window_id: Id) -> Option<id> {
+    let ns_windows: id = msg_send![NSApp(), windows];
+    let count: NSUInteger = msg_send![ns_windows, count];
+    (0..count)
+        .map(|index| -> id { msg_send![ns_windows, objectAtIndex: index] })
+        .find(|&ns_window| {
+            let is_winit_window: BOOL = msg_send![ns_window, isKindOfClass: WINDOW_CLASS.0];
+            is_winit_window == YES && get_window_id(ns_window) == window_id
+        })
+}
+
+unsafe fn set_titlebar_buttons_hidden(window: id, hidden: bool) {
+    let hidden = if hidden { YES } else { NO };
+    for titlebar_button in &[
+        NSWindowButton::NSWindowFullScreenButton,
+        NSWindowButton::NSWindowMiniaturizeButton,
+        NSWindowButton::NSWindowCloseButton,
+        NSWindowButton::NSWindowZoomButton,
+    ] {
+        let button = window.standardWindowButton_(*titlebar_button);
+        let _: () = msg_send![button, setHidden: hidden];
     }
 }
 
@@ -7,7 +7,7 @@ use std::{
 use cocoa::{
     appkit::{self, NSView, NSWindow},
     base::{id, nil},
-    foundation::NSAutoreleasePool,
+    foundation::{NSAutoreleasePool, NSUInteger},
 };
 use objc::{
     declare::ClassDecl,
@@ -15,14 +15,15 @@ use objc::{
 };
 
 use crate::{
-    dpi::LogicalSize,
-    event::{Event, WindowEvent},
+    dpi::{LogicalSize, PhysicalSize},
+    event::{Event, ModifiersState, WindowEvent},
     platform_impl::platform::{
         app_state::AppState,
+        event::current_mods,
         util::{self, IdRef},
         window::{get_window_id, UnownedWindow},
     },
-    window::WindowId,
+    window::{FullscreenType, WindowId},
 };
 
 pub struct WindowDelegateState {
@@ -43,6 +44,7 @@ pub struct WindowDelegateState {
 
     // Used to prevent redundant events.
     previous_dpi_factor: f64,
+    previous_occluded: bool,
 }
 
 impl WindowDelegateState {
@@ -56,10 +58,11 @@ impl WindowDelegateState {
             initial_fullscreen,
             previous_position: None,
             previous_dpi_factor: dpi_factor,
+            previous_occluded: false,
         };
 
         if dpi_factor != 1.0 {
-            delegate_state.emit_event(WindowEvent::HiDpiFactorChanged(dpi_factor));
+            delegate_state.emit_scale_factor_changed_event(dpi_factor);
             delegate_state.emit_resize_event();
         }
 
@@ -81,6 +84,24 @@ impl WindowDelegateState {
         AppState::queue_event(event);
     }
 
+    // The content keeps its logical size, so only the number of pixels behind it changes. This
+    // goes through the same path as `emit_resize_event`, so that it arrives before `Resized`.
+    fn emit_scale_factor_changed_event(&mut self, dpi_factor: f64) {
+        let rect = unsafe { NSView::frame(*self.ns_view) };
+        let new_inner_size = PhysicalSize::new(
+            rect.size.width as f64 * dpi_factor,
+            rect.size.height as f64 * dpi_factor,
+        );
+        let event = Event::WindowEvent {
+            window_id: WindowId(get_window_id(*self.ns_window)),
+            event: WindowEvent::ScaleFactorChanged {
+                scale_factor: dpi_factor,
+                new_inner_size,
+            },
+        };
+        AppState::send_event_immediately(event);
+    }
+
     pub fn emit_resize_event(&mut self) {
         let rect = unsafe { NSView::frame(*self.ns_view) };
         let size = LogicalSize::new(rect.size.width as f64, rect.size.height as f64);
@@ -160,6 +181,10 @@ lazy_static! {
             sel!(windowDidResignKey:),
             window_did_resign_key as extern "C" fn(&Object, Sel, id),
         );
+        decl.add_method(
+            sel!(windowDidChangeOcclusionState:),
+            window_did_change_occlusion_state as extern "C" fn(&Object, Sel, id),
+        );
 
         decl.add_method(
             sel!(draggingEntered:),
@@ -278,7 +303,7 @@ extern "C" fn window_did_change_screen(this: &Object, _: Sel, _: id) {
         let dpi_factor = unsafe { NSWindow::backingScaleFactor(*state.ns_window) } as f64;
         if state.previous_dpi_factor != dpi_factor {
             state.previous_dpi_factor = dpi_factor;
-            state.emit_event(WindowEvent::HiDpiFactorChanged(dpi_factor));
+            state.emit_scale_factor_changed_event(dpi_factor);
             state.emit_resize_event();
         }
     });
@@ -292,7 +317,7 @@ extern "C" fn window_did_change_backing_properties(this: &Object, _: Sel, _: id)
         let dpi_factor = unsafe { NSWindow::backingScaleFactor(*state.ns_window) } as f64;
         if state.previous_dpi_factor != dpi_factor {
             state.previous_dpi_factor = dpi_factor;
-            state.emit_event(WindowEvent::HiDpiFactorChanged(dpi_factor));
+            state.emit_scale_factor_changed_event(dpi_factor);
             state.emit_resize_event();
         }
     });
@@ -305,6 +330,8 @@ extern "C" fn window_did_become_key(this: &Object, _: Sel, _: id) {
         // TODO: center the cursor if the window had mouse grab when it
         // lost focus
         state.emit_event(WindowEvent::Focused(true));
+        // The modifiers may have changed while another application had focus.
+        state.emit_event(WindowEvent::ModifiersChanged(current_mods()));
     });
     trace!("Completed `windowDidBecomeKey:`");
 }
@@ -312,11 +339,27 @@ extern "C" fn window_did_become_key(this: &Object, _: Sel, _: id) {
 extern "C" fn window_did_resign_key(this: &Object, _: Sel, _: id) {
     trace!("Triggered `windowDidResignKey:`");
     with_state(this, |state| {
+        state.emit_event(WindowEvent::ModifiersChanged(ModifiersState::default()));
         state.emit_event(WindowEvent::Focused(false));
     });
     trace!("Completed `windowDidResignKey:`");
 }
 
+// Minimized windows and windows on another space are occluded too.
+extern "C" fn window_did_change_occlusion_state(this: &Object, _: Sel, _: id) {
+    trace!("Triggered `windowDidChangeOcclusionState:`");
+    with_state(this, |state| {
+        // `NSWindowOcclusionStateVisible`
+        let occlusion_state: NSUInteger = unsafe { msg_send![*state.ns_window, occlusionState] };
+        let occluded = occlusion_state & (1 << 1) == 0;
+        if occluded != state.previous_occluded {
+            state.previous_occluded = occluded;
+            state.emit_event(WindowEvent::Occluded(occluded));
+        }
+    });
+    trace!("Completed `windowDidChangeOcclusionState:`");
+}
+
 /// Invoked when the dragged image enters destination bounds or frame
 extern "C" fn dragging_entered(this: &Object, _: Sel, sender: id) -> BOOL {
     trace!("Triggered `draggingEntered:`");
@@ -413,9 +456,16 @@ extern "C" fn window_did_enter_fullscreen(this: &Object, _: Sel, _: id) {
     trace!("Triggered `windowDidEnterFullscreen:`");
     with_state(this, |state| {
         state.with_window(|window| {
-            let monitor = window.current_monitor();
+            let monitor = window.current_monitor_inner();
             trace!("Locked shared state in `window_did_enter_fullscreen`");
-            window.shared_state.lock().unwrap().fullscreen = Some(monitor);
+            let mut shared_state_lock = window.shared_state.lock().unwrap();
+            // Entering fullscreen through the title bar button doesn't go through
+            // `set_fullscreen`.
+            if shared_state_lock.target_fullscreen.is_none() {
+                shared_state_lock.target_fullscreen =
+                    Some(FullscreenType::Borderless(Some(monitor.clone())));
+            }
+            shared_state_lock.fullscreen = Some(monitor);
             trace!("Unlocked shared state in `window_will_enter_fullscreen`");
         });
         state.initial_fullscreen = false;
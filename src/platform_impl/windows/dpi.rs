@@ -1,7 +1,6 @@
 #![allow(non_snake_case, unused_unsafe)]
 
 use std::{
-    mem,
     os::raw::c_void,
     sync::{Once, ONCE_INIT},
 };
@@ -41,7 +40,7 @@ type EnableNonClientDpiScaling = unsafe extern "system" fn(hwnd: HWND) -> BOOL;
 
 // Helper function to dynamically load function pointer.
 // `library` and `function` must be zero-terminated.
-fn get_function_impl(library: &str, function: &str) -> Option<*const c_void> {
+pub fn get_function_impl(library: &str, function: &str) -> Option<*const c_void> {
     assert_eq!(library.chars().last(), Some('\0'));
     assert_eq!(function.chars().last(), Some('\0'));
 
@@ -61,8 +60,11 @@ fn get_function_impl(library: &str, function: &str) -> Option<*const c_void> {
 
 macro_rules! get_function {
     ($lib:expr, $func:ident) => {
-        get_function_impl(concat!($lib, '\0'), concat!(stringify!($func), '\0'))
-            .map(|f| unsafe { mem::transmute::<*const _, $func>(f) })
+        $crate::platform_impl::platform::dpi::get_function_impl(
+            concat!($lib, '\0'),
+            concat!(stringify!($func), '\0'),
+        )
+        .map(|f| unsafe { ::std::mem::transmute::<*const _, $func>(f) })
     };
 }
 
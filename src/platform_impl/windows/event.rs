@@ -47,6 +47,33 @@ unsafe fn get_char(keyboard_state: &[u8; 256], v_key: u32, hkl: HKL) -> Option<c
     }
 }
 
+/// Throws away a pending dead key, which is part of the keyboard state of the calling thread.
+pub fn reset_dead_keys() {
+    unsafe {
+        let hkl = winuser::GetKeyboardLayout(0);
+        let keyboard_state = [0u8; 256];
+        let mut unicode_bytes = [0u16; 5];
+        let scancode =
+            winuser::MapVirtualKeyExW(winuser::VK_SPACE as UINT, winuser::MAPVK_VK_TO_VSC, hkl);
+        // Pressing space after a dead key outputs the dead key on its own, which clears it. A
+        // negative result means that a dead key was left behind after all, so we try again.
+        for _ in 0..5 {
+            let len = winuser::ToUnicodeEx(
+                winuser::VK_SPACE as UINT,
+                scancode,
+                keyboard_state.as_ptr(),
+                unicode_bytes.as_mut_ptr(),
+                unicode_bytes.len() as _,
+                0,
+                hkl,
+            );
+            if len >= 0 {
+                break;
+            }
+        }
+    }
+}
+
 /// Figures out if the keyboard layout has an AltGr key instead of an Alt key.
 ///
 /// Unfortunately, the Windows API doesn't give a way for us to conveniently figure that out. So,
@@ -15,7 +15,7 @@
 use parking_lot::Mutex;
 use std::{
     any::Any,
-    cell::RefCell,
+    cell::{Cell, RefCell},
     collections::VecDeque,
     marker::PhantomData,
     mem, panic, ptr,
@@ -45,15 +45,20 @@ use winapi::{
 
 use crate::{
     dpi::{LogicalPosition, LogicalSize, PhysicalSize},
-    event::{DeviceEvent, Event, KeyboardInput, StartCause, Touch, TouchPhase, WindowEvent},
-    event_loop::{ControlFlow, EventLoopClosed, EventLoopWindowTarget as RootELW},
+    event::{DeviceEvent, Event, Ime, KeyboardInput, StartCause, Touch, TouchPhase, WindowEvent},
+    event_loop::{
+        ControlFlow, DeviceEventFilter, EventLoopClosed, EventLoopWindowTarget as RootELW,
+    },
     platform_impl::platform::{
         dpi::{
             become_dpi_aware, dpi_to_scale_factor, enable_non_client_dpi_scaling, hwnd_scale_factor,
         },
         drop_handler::FileDropHandler,
         event::{self, handle_extended_keys, process_key_params, vkey_to_winit_vkey},
-        raw_input::{get_raw_input_data, get_raw_mouse_button_state},
+        raw_input::{
+            get_raw_input_data, get_raw_mouse_button_state,
+            register_all_mice_and_keyboards_for_raw_input,
+        },
         util,
         window::adjust_size,
         window_state::{CursorFlags, WindowFlags, WindowState},
@@ -65,7 +70,7 @@ use crate::{
 pub(crate) struct SubclassInput<T> {
     pub window_state: Arc<Mutex<WindowState>>,
     pub event_loop_runner: EventLoopRunnerShared<T>,
-    pub file_drop_handler: FileDropHandler,
+    pub file_drop_handler: Option<FileDropHandler>,
 }
 
 impl<T> SubclassInput<T> {
@@ -95,6 +100,9 @@ pub struct EventLoopWindowTarget<T> {
     trigger_newevents_on_redraw: Arc<AtomicBool>,
     thread_msg_target: HWND,
     pub(crate) runner_shared: EventLoopRunnerShared<T>,
+    device_event_filter: Cell<DeviceEventFilter>,
+    // The window raw input was last registered for, since raw input can only target one window.
+    raw_input_window: Cell<HWND>,
 }
 
 impl<T: 'static> EventLoop<T> {
@@ -125,6 +133,8 @@ impl<T: 'static> EventLoop<T> {
                     trigger_newevents_on_redraw: Arc::new(AtomicBool::new(true)),
                     thread_msg_target,
                     runner_shared,
+                    device_event_filter: Default::default(),
+                    raw_input_window: Cell::new(ptr::null_mut()),
                 },
                 _marker: PhantomData,
             },
@@ -240,6 +250,19 @@ impl<T> EventLoopWindowTarget<T> {
             target_window: self.thread_msg_target,
         }
     }
+
+    pub(crate) fn register_for_raw_input(&self, window: HWND) {
+        self.raw_input_window.set(window);
+        register_all_mice_and_keyboards_for_raw_input(window, self.device_event_filter.get());
+    }
+
+    pub fn set_device_event_filter(&self, filter: DeviceEventFilter) {
+        self.device_event_filter.set(filter);
+        let window = self.raw_input_window.get();
+        if !window.is_null() {
+            register_all_mice_and_keyboards_for_raw_input(window, filter);
+        }
+    }
 }
 
 pub(crate) type EventLoopRunnerShared<T> = Rc<ELRShared<T>>;
@@ -627,7 +650,11 @@ impl EventLoopThreadExecutor {
                     raw as *mut () as usize as WPARAM,
                     0,
                 );
-                assert!(res != 0, "PostMessage failed ; is the messages queue full?");
+                if res == 0 {
+                    // The event loop (and the windows along with it) is already gone, so there's
+                    // nothing left for the closure to act on. Reclaim it instead of leaking it.
+                    drop(Box::from_raw(raw));
+                }
             }
         }
     }
@@ -641,17 +668,20 @@ pub struct EventLoopProxy<T: 'static> {
     event_send: Sender<T>,
 }
 unsafe impl<T: Send + 'static> Send for EventLoopProxy<T> {}
+unsafe impl<T: Send + 'static> Sync for EventLoopProxy<T> {}
 
 impl<T: 'static> EventLoopProxy<T> {
     pub fn send_event(&self, event: T) -> Result<(), EventLoopClosed> {
+        // The receiver is dropped along with the thread's message target window, so a failed send
+        // means we mustn't post to that `HWND` anymore. Queueing the event before posting also
+        // ensures it's there by the time the message is handled.
+        self.event_send.send(event).map_err(|_| EventLoopClosed)?;
+        // The event is queued at this point, so it's delivered by the next wakeup message even if
+        // posting this one fails because the thread's message queue is full.
         unsafe {
-            if winuser::PostMessageW(self.target_window, *USER_EVENT_MSG_ID, 0, 0) != 0 {
-                self.event_send.send(event).ok();
-                Ok(())
-            } else {
-                Err(EventLoopClosed)
-            }
+            winuser::PostMessageW(self.target_window, *USER_EVENT_MSG_ID, 0, 0);
         }
+        Ok(())
     }
 }
 
@@ -801,6 +831,23 @@ pub(crate) fn subclass_window<T>(window: HWND, subclass_input: SubclassInput<T>)
     assert_eq!(subclass_result, 1);
 }
 
+/// Emits `ModifiersChanged` if the modifiers differ from the ones last sent to the window.
+unsafe fn update_modifiers<T>(window: HWND, subclass_input: &SubclassInput<T>) {
+    use crate::event::WindowEvent::ModifiersChanged;
+
+    let modifiers = event::get_key_mods();
+    let mut window_state = subclass_input.window_state.lock();
+    if window_state.modifiers_state != modifiers {
+        window_state.modifiers_state = modifiers;
+        drop(window_state);
+
+        subclass_input.send_event(Event::WindowEvent {
+            window_id: RootWindowId(WindowId(window)),
+            event: ModifiersChanged(modifiers),
+        });
+    }
+}
+
 /// Any window whose callback is configured to this function will have its events propagated
 /// through the events loop of the thread the window was created in.
 //
@@ -863,7 +910,10 @@ unsafe extern "system" fn public_window_callback<T>(
 
         winuser::WM_DESTROY => {
             use crate::event::WindowEvent::Destroyed;
-            ole2::RevokeDragDrop(window);
+            if subclass_input.file_drop_handler.is_some() {
+                ole2::RevokeDragDrop(window);
+            }
+            subclass_input.window_state.lock().title.clear();
             subclass_input.send_event(Event::WindowEvent {
                 window_id: RootWindowId(WindowId(window)),
                 event: Destroyed,
@@ -932,6 +982,20 @@ unsafe extern "system" fn public_window_callback<T>(
                 });
             }
 
+            // Windows doesn't report whether the window is covered, only whether it's minimized
+            // or cloaked, which is checked whenever the window is moved, resized or shown.
+            let occluded = winuser::IsIconic(window) != 0 || util::is_cloaked(window);
+            let changed = {
+                let mut window_state = subclass_input.window_state.lock();
+                mem::replace(&mut window_state.occluded, occluded) != occluded
+            };
+            if changed {
+                subclass_input.send_event(Event::WindowEvent {
+                    window_id: RootWindowId(WindowId(window)),
+                    event: WindowEvent::Occluded(occluded),
+                });
+            }
+
             // This is necessary for us to still get sent WM_SIZE.
             commctrl::DefSubclassProc(window, msg, wparam, lparam)
         },
@@ -948,7 +1012,7 @@ unsafe extern "system" fn public_window_callback<T>(
                 event: Resized(logical_size),
             };
 
-            {
+            let shape_region = {
                 let mut w = subclass_input.window_state.lock();
                 // See WindowFlags::MARKER_RETAIN_STATE_ON_SIZE docs for info on why this `if` check exists.
                 if !w
@@ -958,6 +1022,14 @@ unsafe extern "system" fn public_window_callback<T>(
                     let maximized = wparam == winuser::SIZE_MAXIMIZED;
                     w.set_window_flags_in_place(|f| f.set(WindowFlags::MAXIMIZED, maximized));
                 }
+                w.shape_region()
+            };
+
+            // The offset of the client area within the window may have changed, so the shape has
+            // to be applied again. This happens before `Resized` is sent, so that a new shape set
+            // by the handler wins.
+            if let Some(shape_region) = shape_region {
+                let _ = util::set_window_region(window, Some(&shape_region));
             }
 
             subclass_input.send_event(event);
@@ -966,11 +1038,31 @@ unsafe extern "system" fn public_window_callback<T>(
 
         winuser::WM_CHAR => {
             use crate::event::WindowEvent::ReceivedCharacter;
-            let chr: char = mem::transmute(wparam as u32);
-            subclass_input.send_event(Event::WindowEvent {
-                window_id: RootWindowId(WindowId(window)),
-                event: ReceivedCharacter(chr),
-            });
+            use std::char;
+            let is_high_surrogate = (0xD800..=0xDBFF).contains(&wparam);
+            let is_low_surrogate = (0xDC00..=0xDFFF).contains(&wparam);
+
+            let chr = if is_high_surrogate {
+                subclass_input.window_state.lock().high_surrogate = Some(wparam as u16);
+                None
+            } else if is_low_surrogate {
+                let high_surrogate = subclass_input.window_state.lock().high_surrogate.take();
+                high_surrogate.and_then(|high_surrogate| {
+                    char::decode_utf16([high_surrogate, wparam as u16].iter().cloned())
+                        .next()
+                        .and_then(Result::ok)
+                })
+            } else {
+                subclass_input.window_state.lock().high_surrogate = None;
+                char::from_u32(wparam as u32)
+            };
+
+            if let Some(chr) = chr {
+                subclass_input.send_event(Event::WindowEvent {
+                    window_id: RootWindowId(WindowId(window)),
+                    event: ReceivedCharacter(chr),
+                });
+            }
             0
         },
 
@@ -1089,7 +1181,11 @@ unsafe extern "system" fn public_window_callback<T>(
             use crate::event::{ElementState::Pressed, VirtualKeyCode};
             if msg == winuser::WM_SYSKEYDOWN && wparam as i32 == winuser::VK_F4 {
                 commctrl::DefSubclassProc(window, msg, wparam, lparam)
+            } else if wparam as i32 == winuser::VK_PROCESSKEY {
+                // The key went to the IME, which reports what it did with it through `Ime` events.
+                0
             } else {
+                update_modifiers(window, subclass_input);
                 if let Some((scancode, vkey)) = process_key_params(wparam, lparam) {
                     subclass_input.send_event(Event::WindowEvent {
                         window_id: RootWindowId(WindowId(window)),
@@ -1118,6 +1214,7 @@ unsafe extern "system" fn public_window_callback<T>(
 
         winuser::WM_KEYUP | winuser::WM_SYSKEYUP => {
             use crate::event::ElementState::Released;
+            update_modifiers(window, subclass_input);
             if let Some((scancode, vkey)) = process_key_params(wparam, lparam) {
                 subclass_input.send_event(Event::WindowEvent {
                     window_id: RootWindowId(WindowId(window)),
@@ -1414,9 +1511,13 @@ unsafe extern "system" fn public_window_callback<T>(
             ) > 0
             {
                 let dpi_factor = hwnd_scale_factor(window);
+                // The touch points are in hundredths of a pixel in screen coordinates, so they're
+                // moved relative to the client area, like the cursor position is.
+                let mut client_origin = POINT { x: 0, y: 0 };
+                winuser::ClientToScreen(window, &mut client_origin);
                 for input in &inputs {
-                    let x = (input.x as f64) / 100f64;
-                    let y = (input.y as f64) / 100f64;
+                    let x = (input.x as f64) / 100f64 - client_origin.x as f64;
+                    let y = (input.y as f64) / 100f64 - client_origin.y as f64;
                     let location = LogicalPosition::from_physical((x, y), dpi_factor);
                     subclass_input.send_event(Event::WindowEvent {
                         window_id: RootWindowId(WindowId(window)),
@@ -1431,6 +1532,7 @@ unsafe extern "system" fn public_window_callback<T>(
                                 continue;
                             },
                             location,
+                            force: None,
                             id: input.dwID as u64,
                             device_id: DEVICE_ID,
                         }),
@@ -1447,12 +1549,28 @@ unsafe extern "system" fn public_window_callback<T>(
                 window_id: RootWindowId(WindowId(window)),
                 event: Focused(true),
             });
+            // The modifiers may have changed while another window had focus.
+            update_modifiers(window, subclass_input);
 
             0
         },
 
         winuser::WM_KILLFOCUS => {
-            use crate::event::WindowEvent::Focused;
+            use crate::event::{
+                ModifiersState,
+                WindowEvent::{Focused, ModifiersChanged},
+            };
+            let modifiers_changed = {
+                let mut window_state = subclass_input.window_state.lock();
+                mem::replace(&mut window_state.modifiers_state, ModifiersState::default())
+                    != ModifiersState::default()
+            };
+            if modifiers_changed {
+                subclass_input.send_event(Event::WindowEvent {
+                    window_id: RootWindowId(WindowId(window)),
+                    event: ModifiersChanged(ModifiersState::default()),
+                });
+            }
             subclass_input.send_event(Event::WindowEvent {
                 window_id: RootWindowId(WindowId(window)),
                 event: Focused(false),
@@ -1468,7 +1586,10 @@ unsafe extern "system" fn public_window_callback<T>(
                     .cursor_flags()
                     .contains(CursorFlags::IN_WINDOW)
                 {
-                    Some(window_state.mouse.cursor)
+                    Some(match window_state.mouse.custom_cursor {
+                        Some(ref cursor) => cursor.handle(),
+                        None => util::load_system_cursor(window_state.mouse.cursor),
+                    })
                 } else {
                     None
                 }
@@ -1476,7 +1597,6 @@ unsafe extern "system" fn public_window_callback<T>(
 
             match set_cursor_to {
                 Some(cursor) => {
-                    let cursor = winuser::LoadCursorW(ptr::null_mut(), cursor.to_windows_cursor());
                     winuser::SetCursor(cursor);
                     0
                 },
@@ -1484,6 +1604,95 @@ unsafe extern "system" fn public_window_callback<T>(
             }
         },
 
+        winuser::WM_POINTERDOWN | winuser::WM_POINTERUPDATE => {
+            // Pen input is coalesced, so we go through the history to send every reported value.
+            if let Some(history) = util::pen_info_history(LOWORD(wparam as DWORD) as UINT) {
+                for pen_info in history {
+                    let device_id = wrap_device_id(pen_info.pointerInfo.sourceDevice as _);
+                    let axes = [
+                        (winuser::PEN_MASK_PRESSURE, pen_info.pressure as f64),
+                        (winuser::PEN_MASK_ROTATION, pen_info.rotation as f64),
+                        (winuser::PEN_MASK_TILT_X, pen_info.tiltX as f64),
+                        (winuser::PEN_MASK_TILT_Y, pen_info.tiltY as f64),
+                    ];
+                    for (axis, &(mask, value)) in axes.iter().enumerate() {
+                        if pen_info.penMask & mask != 0 {
+                            subclass_input.send_event(Event::WindowEvent {
+                                window_id: RootWindowId(WindowId(window)),
+                                event: WindowEvent::AxisMotion {
+                                    device_id,
+                                    axis: axis as u32,
+                                    value,
+                                },
+                            });
+                        }
+                    }
+                }
+            }
+            // The default procedure turns the pointer messages into the mouse messages we handle.
+            commctrl::DefSubclassProc(window, msg, wparam, lparam)
+        },
+
+        winuser::WM_IME_STARTCOMPOSITION => {
+            let ime_position: Option<(i32, i32)> = {
+                let window_state = subclass_input.window_state.lock();
+                window_state
+                    .ime_position
+                    .map(|position| position.to_physical(window_state.dpi_factor).into())
+            };
+            if let Some((x, y)) = ime_position {
+                util::set_ime_position(window, x, y);
+            }
+            subclass_input.send_event(Event::WindowEvent {
+                window_id: RootWindowId(WindowId(window)),
+                event: WindowEvent::Ime(Ime::Enabled),
+            });
+            commctrl::DefSubclassProc(window, msg, wparam, lparam)
+        },
+
+        winuser::WM_IME_COMPOSITION => {
+            let flags = lparam as DWORD;
+            if flags & util::GCS_RESULTSTR != 0 {
+                if let Some((text, _)) = util::ime_composition_string(window, util::GCS_RESULTSTR) {
+                    subclass_input.send_event(Event::WindowEvent {
+                        window_id: RootWindowId(WindowId(window)),
+                        event: WindowEvent::Ime(Ime::Commit(text)),
+                    });
+                }
+            }
+            if flags & util::GCS_COMPSTR != 0 {
+                if let Some((text, cursor)) =
+                    util::ime_composition_string(window, util::GCS_COMPSTR)
+                {
+                    subclass_input.send_event(Event::WindowEvent {
+                        window_id: RootWindowId(WindowId(window)),
+                        event: WindowEvent::Ime(Ime::Preedit(text, Some((cursor, cursor)))),
+                    });
+                }
+            }
+            // The committed text was sent above, so don't let the default procedure turn it into
+            // `WM_CHAR` messages as well.
+            if flags & util::GCS_RESULTSTR != 0 {
+                0
+            } else {
+                commctrl::DefSubclassProc(window, msg, wparam, lparam)
+            }
+        },
+
+        winuser::WM_IME_ENDCOMPOSITION => {
+            // Clear the preedit, since the IME doesn't send an empty composition string when it's
+            // cancelled.
+            subclass_input.send_event(Event::WindowEvent {
+                window_id: RootWindowId(WindowId(window)),
+                event: WindowEvent::Ime(Ime::Preedit(String::new(), None)),
+            });
+            subclass_input.send_event(Event::WindowEvent {
+                window_id: RootWindowId(WindowId(window)),
+                event: WindowEvent::Ime(Ime::Disabled),
+            });
+            commctrl::DefSubclassProc(window, msg, wparam, lparam)
+        },
+
         winuser::WM_DROPFILES => {
             // See `FileDropHandler` for implementation.
             0
@@ -1521,7 +1730,7 @@ unsafe extern "system" fn public_window_callback<T>(
         // Only sent on Windows 8.1 or newer. On Windows 7 and older user has to log out to change
         // DPI, therefore all applications are closed while DPI is changing.
         winuser::WM_DPICHANGED => {
-            use crate::event::WindowEvent::HiDpiFactorChanged;
+            use crate::event::WindowEvent::ScaleFactorChanged;
 
             // This message actually provides two DPI values - x and y. However MSDN says that
             // "you only need to use either the X-axis or the Y-axis value when scaling your
@@ -1530,17 +1739,50 @@ unsafe extern "system" fn public_window_callback<T>(
             let new_dpi_x = u32::from(LOWORD(wparam as DWORD));
             let new_dpi_factor = dpi_to_scale_factor(new_dpi_x);
 
-            let allow_resize = {
+            let (old_dpi_factor, allow_resize) = {
                 let mut window_state = subclass_input.window_state.lock();
                 let old_dpi_factor = window_state.dpi_factor;
                 window_state.dpi_factor = new_dpi_factor;
 
-                new_dpi_factor != old_dpi_factor && window_state.fullscreen.is_none()
+                (old_dpi_factor, window_state.fullscreen.is_none())
+            };
+            if new_dpi_factor == old_dpi_factor {
+                return 0;
+            }
+
+            // The logical size is conserved, which is also what the size suggested by Windows
+            // amounts to. The event has to be sent before resizing, since `SetWindowPos` emits
+            // `Resized` right away.
+            let old_client_rect = match util::get_client_rect(window) {
+                Ok(rect) => rect,
+                Err(_) => return 0,
+            };
+            let new_inner_size = PhysicalSize::new(
+                (old_client_rect.right - old_client_rect.left) as f64,
+                (old_client_rect.bottom - old_client_rect.top) as f64,
+            )
+            .to_logical(old_dpi_factor)
+            .to_physical(new_dpi_factor);
+            subclass_input.send_event(Event::WindowEvent {
+                window_id: RootWindowId(WindowId(window)),
+                event: ScaleFactorChanged {
+                    scale_factor: new_dpi_factor,
+                    new_inner_size,
+                },
+            });
+
+            // The application may have picked another size while handling the event.
+            let resized_by_user = match util::get_client_rect(window) {
+                Ok(rect) => {
+                    rect.right - rect.left != old_client_rect.right - old_client_rect.left
+                        || rect.bottom - rect.top != old_client_rect.bottom - old_client_rect.top
+                },
+                Err(_) => return 0,
             };
 
             // This prevents us from re-applying DPI adjustment to the restored size after exiting
             // fullscreen (the restored size is already DPI adjusted).
-            if allow_resize {
+            if allow_resize && !resized_by_user {
                 // Resize window to the size suggested by Windows.
                 let rect = &*(lparam as *const RECT);
                 winuser::SetWindowPos(
@@ -1552,16 +1794,69 @@ unsafe extern "system" fn public_window_callback<T>(
                     rect.bottom - rect.top,
                     winuser::SWP_NOZORDER | winuser::SWP_NOACTIVATE,
                 );
-            }
 
-            subclass_input.send_event(Event::WindowEvent {
-                window_id: RootWindowId(WindowId(window)),
-                event: HiDpiFactorChanged(new_dpi_factor),
-            });
+                // The suggested rect can be a few pixels off the size the event reported, since
+                // the frame doesn't scale exactly, so the client area is brought to that size.
+                let (new_width, new_height): (u32, u32) = new_inner_size.into();
+                if let (Ok(client_rect), Some(window_rect)) =
+                    (util::get_client_rect(window), util::get_window_rect(window))
+                {
+                    let dw = new_width as i32 - (client_rect.right - client_rect.left);
+                    let dh = new_height as i32 - (client_rect.bottom - client_rect.top);
+                    if dw != 0 || dh != 0 {
+                        winuser::SetWindowPos(
+                            window,
+                            ptr::null_mut(),
+                            0,
+                            0,
+                            window_rect.right - window_rect.left + dw,
+                            window_rect.bottom - window_rect.top + dh,
+                            winuser::SWP_NOZORDER | winuser::SWP_NOMOVE | winuser::SWP_NOACTIVATE,
+                        );
+                    }
+                }
+            }
 
             0
         },
 
+        // Also sent when another process changes the window text, so the cached title is updated
+        // here and not only in `set_title`.
+        winuser::WM_SETTEXT => {
+            let result = commctrl::DefSubclassProc(window, msg, wparam, lparam);
+            if result != 0 {
+                let text = lparam as *const u16;
+                subclass_input.window_state.lock().title = if text.is_null() {
+                    String::new()
+                } else {
+                    util::wchar_ptr_to_string(text)
+                };
+            }
+            result
+        },
+
+        // Sent to every top-level window when a setting changes. The dark mode setting is
+        // announced as a change to the "ImmersiveColorSet".
+        winuser::WM_SETTINGCHANGE => {
+            use crate::event::WindowEvent::ThemeChanged;
+
+            let setting = lparam as *const u16;
+            if !setting.is_null() && util::wchar_ptr_to_string(setting) == "ImmersiveColorSet" {
+                let theme = util::system_theme();
+                let changed = {
+                    let mut window_state = subclass_input.window_state.lock();
+                    mem::replace(&mut window_state.theme, theme) != theme
+                };
+                if changed {
+                    subclass_input.send_event(Event::WindowEvent {
+                        window_id: RootWindowId(WindowId(window)),
+                        event: ThemeChanged(theme),
+                    });
+                }
+            }
+            commctrl::DefSubclassProc(window, msg, wparam, lparam)
+        },
+
         _ => {
             if msg == *DESTROY_MSG_ID {
                 winuser::DestroyWindow(window);
@@ -1573,12 +1868,8 @@ unsafe extern "system" fn public_window_callback<T>(
                 });
                 0
             } else if msg == *INITIAL_DPI_MSG_ID {
-                use crate::event::WindowEvent::HiDpiFactorChanged;
+                use crate::event::WindowEvent::ScaleFactorChanged;
                 let scale_factor = dpi_to_scale_factor(wparam as u32);
-                subclass_input.send_event(Event::WindowEvent {
-                    window_id: RootWindowId(WindowId(window)),
-                    event: HiDpiFactorChanged(scale_factor),
-                });
                 // Automatically resize for actual DPI
                 let width = LOWORD(lparam as DWORD) as u32;
                 let height = HIWORD(lparam as DWORD) as u32;
@@ -1609,6 +1900,16 @@ unsafe extern "system" fn public_window_callback<T>(
                         | winuser::SWP_NOZORDER
                         | winuser::SWP_NOACTIVATE,
                 );
+                subclass_input.send_event(Event::WindowEvent {
+                    window_id: RootWindowId(WindowId(window)),
+                    event: ScaleFactorChanged {
+                        scale_factor,
+                        new_inner_size: PhysicalSize::new(
+                            adjusted_width as f64,
+                            adjusted_height as f64,
+                        ),
+                    },
+                });
                 0
             } else {
                 commctrl::DefSubclassProc(window, msg, wparam, lparam)
@@ -1700,7 +2001,9 @@ unsafe extern "system" fn thread_event_target_callback<T>(
             0
         },
         _ if msg == *USER_EVENT_MSG_ID => {
-            if let Ok(event) = subclass_input.user_event_receiver.recv() {
+            // A wakeup message can fail to be posted when the thread's message queue is full, so
+            // each one delivers every queued event instead of only its own.
+            for event in subclass_input.user_event_receiver.try_iter() {
                 subclass_input.send_event(Event::UserEvent(event));
             }
             0
@@ -1,15 +1,18 @@
-use std::{io, mem, os::windows::ffi::OsStrExt, path::Path, ptr};
+use std::{io, mem, os::windows::ffi::OsStrExt, path::Path, ptr, sync::Arc};
 
 use winapi::{
-    ctypes::{c_int, wchar_t},
+    ctypes::{c_int, c_void, wchar_t},
     shared::{
-        minwindef::{BYTE, LPARAM, WPARAM},
-        windef::{HICON, HWND},
+        minwindef::{BYTE, FALSE, LPARAM, WPARAM},
+        windef::{HCURSOR, HICON, HWND},
     },
-    um::winuser,
+    um::{wingdi, winuser},
 };
 
-use crate::icon::{Icon, Pixel, PIXEL_SIZE};
+use crate::{
+    icon::{Icon, Pixel, PIXEL_SIZE},
+    window::BadCursor,
+};
 
 impl Pixel {
     fn to_bgra(&mut self) {
@@ -108,3 +111,78 @@ pub fn unset_for_window(hwnd: HWND, icon_type: IconType) {
         winuser::SendMessageW(hwnd, winuser::WM_SETICON, icon_type as WPARAM, 0 as LPARAM);
     }
 }
+
+/// A cursor created from RGBA data, which is destroyed once the last clone is dropped.
+#[derive(Clone, Debug)]
+pub struct WinCursor {
+    inner: Arc<CursorHandle>,
+}
+
+#[derive(Debug)]
+struct CursorHandle(HCURSOR);
+
+unsafe impl Send for CursorHandle {}
+unsafe impl Sync for CursorHandle {}
+
+impl WinCursor {
+    pub fn from_rgba(
+        rgba: &[u8],
+        width: u32,
+        height: u32,
+        hotspot_x: u32,
+        hotspot_y: u32,
+    ) -> Result<Self, BadCursor> {
+        let mut bgra = rgba.to_vec();
+        for pixel in bgra.chunks_mut(PIXEL_SIZE) {
+            pixel.swap(0, 2);
+        }
+        // The color bitmap carries an alpha channel, so the mask is ignored, but it must still
+        // be provided. Monochrome bitmap rows are aligned to 16 bits.
+        let mask_stride = (width as usize + 15) / 16 * 2;
+        let mask = vec![0u8; mask_stride * height as usize];
+
+        let handle = unsafe {
+            let color_bitmap = wingdi::CreateBitmap(
+                width as c_int,
+                height as c_int,
+                1,
+                (PIXEL_SIZE * 8) as _,
+                bgra.as_ptr() as *const c_void,
+            );
+            let mask_bitmap = wingdi::CreateBitmap(
+                width as c_int,
+                height as c_int,
+                1,
+                1,
+                mask.as_ptr() as *const c_void,
+            );
+            let mut icon_info = winuser::ICONINFO {
+                fIcon: FALSE,
+                xHotspot: hotspot_x,
+                yHotspot: hotspot_y,
+                hbmMask: mask_bitmap,
+                hbmColor: color_bitmap,
+            };
+            let handle = winuser::CreateIconIndirect(&mut icon_info);
+            wingdi::DeleteObject(color_bitmap as _);
+            wingdi::DeleteObject(mask_bitmap as _);
+            handle
+        };
+        if handle.is_null() {
+            return Err(BadCursor::CreationFailed);
+        }
+        Ok(WinCursor {
+            inner: Arc::new(CursorHandle(handle)),
+        })
+    }
+
+    pub fn handle(&self) -> HCURSOR {
+        self.inner.0
+    }
+}
+
+impl Drop for CursorHandle {
+    fn drop(&mut self) {
+        unsafe { winuser::DestroyCursor(self.0) };
+    }
+}
@@ -4,17 +4,30 @@ use winapi::{self, shared::windef::HWND};
 
 pub use self::{
     event_loop::{EventLoop, EventLoopProxy, EventLoopWindowTarget},
+    icon::WinCursor as CustomCursor,
     monitor::MonitorHandle,
     window::Window,
 };
 
 use crate::{event::DeviceId as RootDeviceId, window::Icon};
 
-#[derive(Clone, Default)]
+#[derive(Clone)]
 pub struct PlatformSpecificWindowBuilderAttributes {
     pub parent: Option<HWND>,
     pub taskbar_icon: Option<Icon>,
     pub no_redirection_bitmap: bool,
+    pub drag_and_drop: bool,
+}
+
+impl Default for PlatformSpecificWindowBuilderAttributes {
+    fn default() -> Self {
+        Self {
+            parent: None,
+            taskbar_icon: None,
+            no_redirection_bitmap: false,
+            drag_and_drop: true,
+        }
+    }
 }
 
 unsafe impl Send for PlatformSpecificWindowBuilderAttributes {}
@@ -67,6 +80,7 @@ impl WindowId {
     }
 }
 
+#[macro_use]
 mod dpi;
 mod drop_handler;
 mod event;
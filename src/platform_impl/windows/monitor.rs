@@ -8,10 +8,11 @@ use winapi::{
 
 use std::{
     collections::{HashSet, VecDeque},
+    hash::{Hash, Hasher},
     io, mem, ptr,
 };
 
-use super::{util, EventLoop};
+use super::{util, EventLoop, EventLoopWindowTarget};
 use crate::{
     dpi::{PhysicalPosition, PhysicalSize},
     monitor::VideoMode,
@@ -43,14 +44,29 @@ pub struct MonitorHandle {
     hidpi_factor: f64,
 }
 
-// Send is not implemented for HMONITOR, we have to wrap it and implement it manually.
+// Send and Sync are not implemented for HMONITOR, we have to wrap it and implement them manually.
 // For more info see:
 // https://github.com/retep998/winapi-rs/issues/360
 // https://github.com/retep998/winapi-rs/issues/396
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
 struct HMonitor(HMONITOR);
 
 unsafe impl Send for HMonitor {}
+unsafe impl Sync for HMonitor {}
+
+impl PartialEq for MonitorHandle {
+    fn eq(&self, other: &Self) -> bool {
+        self.hmonitor == other.hmonitor
+    }
+}
+
+impl Eq for MonitorHandle {}
+
+impl Hash for MonitorHandle {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.hmonitor.hash(state);
+    }
+}
 
 unsafe extern "system" fn monitor_enum_proc(
     hmonitor: HMONITOR,
@@ -87,6 +103,16 @@ pub fn current_monitor(hwnd: HWND) -> MonitorHandle {
     MonitorHandle::from_hmonitor(hmonitor)
 }
 
+/// The offset of screen coordinates from the workspace coordinates used by `GetWindowPlacement`
+/// and `SetWindowPlacement`, which start after any toolbars docked to the top or left of the
+/// primary monitor.
+pub(crate) fn workspace_offset() -> (LONG, LONG) {
+    let primary = primary_monitor();
+    let rect = primary.monitor_info.rcWork;
+    let (x, y) = primary.position;
+    (rect.left - x as LONG, rect.top - y as LONG)
+}
+
 impl<T> EventLoop<T> {
     // TODO: Investigate opportunities for caching
     pub fn available_monitors(&self) -> VecDeque<MonitorHandle> {
@@ -98,6 +124,12 @@ impl<T> EventLoop<T> {
     }
 }
 
+impl<T> EventLoopWindowTarget<T> {
+    pub fn primary_monitor(&self) -> MonitorHandle {
+        primary_monitor()
+    }
+}
+
 impl Window {
     pub fn available_monitors(&self) -> VecDeque<MonitorHandle> {
         available_monitors()
@@ -176,11 +208,117 @@ impl MonitorHandle {
         self.position.into()
     }
 
+    /// The position of the monitor's work area, see `work_area_size`.
+    #[inline]
+    pub(crate) fn work_area_position(&self) -> PhysicalPosition {
+        let rect = self.monitor_info.rcWork;
+        (rect.left as i32, rect.top as i32).into()
+    }
+
+    /// The size of the monitor, without the taskbar and other toolbars docked to its edges.
+    #[inline]
+    pub(crate) fn work_area_size(&self) -> PhysicalSize {
+        let rect = self.monitor_info.rcWork;
+        (
+            (rect.right - rect.left) as u32,
+            (rect.bottom - rect.top) as u32,
+        )
+            .into()
+    }
+
     #[inline]
     pub fn hidpi_factor(&self) -> f64 {
         self.hidpi_factor
     }
 
+    /// Takes exclusive control of the monitor, or releases it again.
+    ///
+    /// The monitor is switched to `video_mode` if there is one, and otherwise keeps its current
+    /// video mode.
+    pub(crate) fn set_exclusive(
+        &self,
+        exclusive: bool,
+        video_mode: Option<&VideoMode>,
+    ) -> Result<(), io::Error> {
+        unsafe {
+            let device_name = self.monitor_info.szDevice.as_ptr();
+            let result = if exclusive {
+                let mut mode = match video_mode {
+                    Some(video_mode) => {
+                        self.display_settings(video_mode).ok_or_else(|| {
+                            io::Error::new(
+                                io::ErrorKind::Other,
+                                format!("video mode {} isn't supported by the monitor", video_mode),
+                            )
+                        })?
+                    },
+                    None => {
+                        let mut mode: wingdi::DEVMODEW = mem::zeroed();
+                        mode.dmSize = mem::size_of_val(&mode) as WORD;
+                        if winuser::EnumDisplaySettingsExW(
+                            device_name,
+                            winuser::ENUM_CURRENT_SETTINGS,
+                            &mut mode,
+                            0,
+                        ) == 0
+                        {
+                            return Err(io::Error::last_os_error());
+                        }
+                        mode
+                    },
+                };
+                winuser::ChangeDisplaySettingsExW(
+                    device_name,
+                    &mut mode,
+                    ptr::null_mut(),
+                    winuser::CDS_FULLSCREEN,
+                    ptr::null_mut(),
+                )
+            } else {
+                // Passing no mode restores the one stored in the registry.
+                winuser::ChangeDisplaySettingsExW(
+                    device_name,
+                    ptr::null_mut(),
+                    ptr::null_mut(),
+                    0,
+                    ptr::null_mut(),
+                )
+            };
+
+            if result == winuser::DISP_CHANGE_SUCCESSFUL {
+                Ok(())
+            } else {
+                Err(io::Error::new(
+                    io::ErrorKind::Other,
+                    format!("`ChangeDisplaySettingsExW` failed with {}", result),
+                ))
+            }
+        }
+    }
+
+    /// Finds the display settings matching `video_mode` among those the monitor supports.
+    fn display_settings(&self, video_mode: &VideoMode) -> Option<wingdi::DEVMODEW> {
+        let mut i = 0;
+        loop {
+            unsafe {
+                let device_name = self.monitor_info.szDevice.as_ptr();
+                let mut mode: wingdi::DEVMODEW = mem::zeroed();
+                mode.dmSize = mem::size_of_val(&mode) as WORD;
+                if winuser::EnumDisplaySettingsExW(device_name, i, &mut mode, 0) == 0 {
+                    return None;
+                }
+                i += 1;
+
+                if (mode.dmPelsWidth, mode.dmPelsHeight) == video_mode.size
+                    && mode.dmBitsPerPel as u16 == video_mode.bit_depth
+                    && mode.dmDisplayFrequency as u16 == video_mode.refresh_rate
+                {
+                    return Some(mode);
+                }
+            }
+        }
+    }
+
     #[inline]
     pub fn video_modes(&self) -> impl Iterator<Item = VideoMode> {
         // EnumDisplaySettingsExW can return duplicate values (or some of the
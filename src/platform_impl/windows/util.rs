@@ -1,20 +1,368 @@
 use std::{
+    cell::RefCell,
+    collections::HashMap,
     io, mem,
     ops::BitAnd,
     ptr, slice,
     sync::atomic::{AtomicBool, Ordering},
 };
 
-use crate::window::CursorIcon;
+use crate::{
+    platform::windows::BlurEffect,
+    window::{CursorIcon, Theme},
+};
 use winapi::{
-    ctypes::wchar_t,
+    ctypes::{c_void, wchar_t},
     shared::{
-        minwindef::{BOOL, DWORD},
-        windef::{HWND, POINT, RECT},
+        basetsd::SIZE_T,
+        minwindef::{BOOL, DWORD, FALSE, UINT, WPARAM},
+        ntdef::NTSTATUS,
+        windef::{HCURSOR, HWND, POINT, RECT},
+        winerror::{ERROR_SUCCESS, S_OK},
+    },
+    um::{
+        dwmapi,
+        winbase::lstrlenW,
+        wingdi,
+        winnt::{LONG, OSVERSIONINFOW},
+        winreg, winuser,
     },
-    um::{winbase::lstrlenW, winuser},
 };
 
+// `winapi` doesn't expose the IMM API, so we link against it ourselves.
+pub const IACE_DEFAULT: DWORD = 0x0010;
+pub const CFS_POINT: DWORD = 0x0002;
+pub const CFS_CANDIDATEPOS: DWORD = 0x0040;
+pub const GCS_COMPSTR: DWORD = 0x0008;
+pub const GCS_CURSORPOS: DWORD = 0x0080;
+pub const GCS_RESULTSTR: DWORD = 0x0800;
+
+#[repr(C)]
+pub struct CompositionForm {
+    pub style: DWORD,
+    pub current_pos: POINT,
+    pub area: RECT,
+}
+
+#[repr(C)]
+pub struct CandidateForm {
+    pub index: DWORD,
+    pub style: DWORD,
+    pub current_pos: POINT,
+    pub area: RECT,
+}
+
+// `WDA_EXCLUDEFROMCAPTURE` is only understood by Windows 10 version 2004 and later.
+pub const WDA_NONE: DWORD = 0x00000000;
+pub const WDA_MONITOR: DWORD = 0x00000001;
+pub const WDA_EXCLUDEFROMCAPTURE: DWORD = 0x00000011;
+
+#[link(name = "user32")]
+extern "system" {
+    pub fn SetWindowDisplayAffinity(hwnd: HWND, affinity: DWORD) -> BOOL;
+}
+
+pub fn set_content_protected(hwnd: HWND, protected: bool) {
+    unsafe {
+        if !protected {
+            SetWindowDisplayAffinity(hwnd, WDA_NONE);
+        } else if SetWindowDisplayAffinity(hwnd, WDA_EXCLUDEFROMCAPTURE) == 0 {
+            // Older versions of Windows can at least black out the window in captures.
+            SetWindowDisplayAffinity(hwnd, WDA_MONITOR);
+        }
+    }
+}
+
+#[link(name = "ntdll")]
+extern "system" {
+    pub fn RtlGetVersion(info: *mut OSVERSIONINFOW) -> NTSTATUS;
+}
+
+/// Returns the major version and build number of Windows. Unlike `GetVersionEx`, this isn't
+/// capped at Windows 8 for applications without a manifest.
+pub fn os_version() -> (DWORD, DWORD) {
+    unsafe {
+        let mut info: OSVERSIONINFOW = mem::zeroed();
+        info.dwOSVersionInfoSize = mem::size_of::<OSVERSIONINFOW>() as DWORD;
+        RtlGetVersion(&mut info);
+        (info.dwMajorVersion, info.dwBuildNumber)
+    }
+}
+
+// `SetWindowCompositionAttribute` is undocumented, so `winapi` has neither it nor its types.
+pub const WCA_ACCENT_POLICY: DWORD = 19;
+pub const ACCENT_DISABLED: DWORD = 0;
+pub const ACCENT_ENABLE_BLURBEHIND: DWORD = 3;
+// Only understood by Windows 10 version 1803 and later.
+pub const ACCENT_ENABLE_ACRYLICBLURBEHIND: DWORD = 4;
+const ACRYLIC_MIN_BUILD: DWORD = 17134;
+
+#[repr(C)]
+pub struct AccentPolicy {
+    pub accent_state: DWORD,
+    pub accent_flags: DWORD,
+    pub gradient_color: DWORD,
+    pub animation_id: DWORD,
+}
+
+#[repr(C)]
+pub struct WindowCompositionAttributeData {
+    pub attribute: DWORD,
+    pub data: *mut c_void,
+    pub size: SIZE_T,
+}
+
+type SetWindowCompositionAttribute =
+    unsafe extern "system" fn(hwnd: HWND, data: *mut WindowCompositionAttributeData) -> BOOL;
+
+lazy_static! {
+    // It isn't in the import library either, so it has to be looked up at runtime.
+    static ref SET_WINDOW_COMPOSITION_ATTRIBUTE: Option<SetWindowCompositionAttribute> =
+        get_function!("user32.dll", SetWindowCompositionAttribute);
+}
+
+/// Lets DWM compose the window with what's behind it, blurring it when `blur` is set.
+///
+/// Without blur, the blurred region is made empty, which keeps the transparency of the window.
+pub unsafe fn enable_blur_behind(hwnd: HWND, blur: bool) {
+    let region = match blur {
+        true => ptr::null_mut(),
+        false => wingdi::CreateRectRgn(0, 0, -1, -1),
+    };
+    let bb = dwmapi::DWM_BLURBEHIND {
+        dwFlags: dwmapi::DWM_BB_ENABLE | dwmapi::DWM_BB_BLURREGION,
+        fEnable: 1,
+        hRgnBlur: region,
+        fTransitionOnMaximized: 0,
+    };
+    dwmapi::DwmEnableBlurBehindWindow(hwnd, &bb);
+    if !region.is_null() {
+        wingdi::DeleteObject(region as _);
+    }
+}
+
+/// Returns whether DWM hides the window, for instance because it's on another virtual desktop.
+pub fn is_cloaked(hwnd: HWND) -> bool {
+    let mut cloaked: DWORD = 0;
+    let result = unsafe {
+        dwmapi::DwmGetWindowAttribute(
+            hwnd,
+            dwmapi::DWMWA_CLOAKED,
+            &mut cloaked as *mut DWORD as *mut c_void,
+            mem::size_of::<DWORD>() as DWORD,
+        )
+    };
+    // This fails before Windows 8, which doesn't cloak windows.
+    result == S_OK && cloaked != 0
+}
+
+/// Returns whether this version of Windows can apply `effect`.
+pub fn is_blur_effect_supported(effect: BlurEffect) -> bool {
+    let (major, build) = os_version();
+    match effect {
+        // Windows 7 blurs through DWM. Windows 8 accepts the same call, but doesn't blur anymore.
+        BlurEffect::None | BlurEffect::Blur if major < 10 => true,
+        BlurEffect::None | BlurEffect::Blur => SET_WINDOW_COMPOSITION_ATTRIBUTE.is_some(),
+        BlurEffect::Acrylic { .. } => {
+            major >= 10 && build >= ACRYLIC_MIN_BUILD && SET_WINDOW_COMPOSITION_ATTRIBUTE.is_some()
+        },
+    }
+}
+
+/// Applies `effect` to a transparent window. `is_blur_effect_supported` must have returned `true`
+/// for it.
+pub fn set_blur_behind(hwnd: HWND, effect: BlurEffect) {
+    let (major, _) = os_version();
+    if major < 10 {
+        unsafe { enable_blur_behind(hwnd, effect != BlurEffect::None) };
+        return;
+    }
+
+    let set_window_composition_attribute = match *SET_WINDOW_COMPOSITION_ATTRIBUTE {
+        Some(function) => function,
+        None => return,
+    };
+    let (accent_state, gradient_color) = match effect {
+        BlurEffect::None => (ACCENT_DISABLED, 0),
+        BlurEffect::Blur => (ACCENT_ENABLE_BLURBEHIND, 0),
+        BlurEffect::Acrylic { tint: (r, g, b, a) } => {
+            // The tint is stored as `0xAABBGGRR`.
+            let color = (a as DWORD) << 24 | (b as DWORD) << 16 | (g as DWORD) << 8 | r as DWORD;
+            (ACCENT_ENABLE_ACRYLICBLURBEHIND, color)
+        },
+    };
+    let mut policy = AccentPolicy {
+        accent_state,
+        accent_flags: 0,
+        gradient_color,
+        animation_id: 0,
+    };
+    let mut data = WindowCompositionAttributeData {
+        attribute: WCA_ACCENT_POLICY,
+        data: &mut policy as *mut AccentPolicy as *mut c_void,
+        size: mem::size_of::<AccentPolicy>() as SIZE_T,
+    };
+    unsafe { set_window_composition_attribute(hwnd, &mut data) };
+}
+
+#[link(name = "imm32")]
+extern "system" {
+    pub fn ImmAssociateContextEx(hwnd: HWND, himc: *mut c_void, flags: DWORD) -> BOOL;
+    pub fn ImmGetContext(hwnd: HWND) -> *mut c_void;
+    pub fn ImmReleaseContext(hwnd: HWND, himc: *mut c_void) -> BOOL;
+    pub fn ImmSetCompositionWindow(himc: *mut c_void, form: *mut CompositionForm) -> BOOL;
+    pub fn ImmSetCandidateWindow(himc: *mut c_void, form: *mut CandidateForm) -> BOOL;
+    pub fn ImmGetCompositionStringW(
+        himc: *mut c_void,
+        index: DWORD,
+        buf: *mut c_void,
+        len: DWORD,
+    ) -> LONG;
+}
+
+/// Reads the composition string `index` (`GCS_COMPSTR` or `GCS_RESULTSTR`) of the IME, along with
+/// the byte offset of the cursor in it.
+pub unsafe fn ime_composition_string(hwnd: HWND, index: DWORD) -> Option<(String, usize)> {
+    let himc = ImmGetContext(hwnd);
+    if himc.is_null() {
+        return None;
+    }
+    let result = {
+        // The length is in bytes, and is negative on error.
+        let len = ImmGetCompositionStringW(himc, index, ptr::null_mut(), 0);
+        if len >= 0 {
+            let mut buf = vec![0u16; len as usize / mem::size_of::<wchar_t>()];
+            ImmGetCompositionStringW(himc, index, buf.as_mut_ptr() as *mut c_void, len as DWORD);
+            // The cursor position is in UTF-16 code units.
+            let cursor = ImmGetCompositionStringW(himc, GCS_CURSORPOS, ptr::null_mut(), 0);
+            let cursor = (cursor.max(0) as usize).min(buf.len());
+            let cursor = String::from_utf16_lossy(&buf[..cursor]).len();
+            Some((String::from_utf16_lossy(&buf), cursor))
+        } else {
+            None
+        }
+    };
+    ImmReleaseContext(hwnd, himc);
+    result
+}
+
+type GetPointerType = unsafe extern "system" fn(pointer_id: UINT, pointer_type: *mut DWORD) -> BOOL;
+type GetPointerPenInfoHistory = unsafe extern "system" fn(
+    pointer_id: UINT,
+    entries_count: *mut UINT,
+    pen_info: *mut winuser::POINTER_PEN_INFO,
+) -> BOOL;
+
+lazy_static! {
+    // The pointer API is only available on Windows 8 and later.
+    static ref GET_POINTER_TYPE: Option<GetPointerType> =
+        get_function!("user32.dll", GetPointerType);
+    static ref GET_POINTER_PEN_INFO_HISTORY: Option<GetPointerPenInfoHistory> =
+        get_function!("user32.dll", GetPointerPenInfoHistory);
+}
+
+/// Returns the states of the pen `pointer_id` reported since the last pointer message, oldest
+/// first. Returns `None` if the pointer isn't a pen.
+pub fn pen_info_history(pointer_id: UINT) -> Option<Vec<winuser::POINTER_PEN_INFO>> {
+    let get_pointer_type = (*GET_POINTER_TYPE)?;
+    let get_pen_info_history = (*GET_POINTER_PEN_INFO_HISTORY)?;
+    unsafe {
+        let mut pointer_type = 0;
+        if get_pointer_type(pointer_id, &mut pointer_type) == FALSE
+            || pointer_type != winuser::PT_PEN
+        {
+            return None;
+        }
+
+        // The most recent entry tells how many there are in total.
+        let mut count = 1;
+        let mut pen_info: winuser::POINTER_PEN_INFO = mem::zeroed();
+        if get_pen_info_history(pointer_id, &mut count, &mut pen_info) == FALSE {
+            return None;
+        }
+        let mut count = pen_info.pointerInfo.historyCount.max(1);
+        let mut history = Vec::with_capacity(count as usize);
+        if get_pen_info_history(pointer_id, &mut count, history.as_mut_ptr()) == FALSE {
+            return None;
+        }
+        history.set_len(count as usize);
+        history.reverse();
+        Some(history)
+    }
+}
+
+/// Moves the composition and candidate windows of the IME to `(x, y)` in client coordinates.
+pub unsafe fn set_ime_position(hwnd: HWND, x: i32, y: i32) {
+    let himc = ImmGetContext(hwnd);
+    if himc.is_null() {
+        return;
+    }
+    let mut composition_form = CompositionForm {
+        style: CFS_POINT,
+        current_pos: POINT { x, y },
+        area: mem::zeroed(),
+    };
+    ImmSetCompositionWindow(himc, &mut composition_form);
+    let mut candidate_form = CandidateForm {
+        index: 0,
+        style: CFS_CANDIDATEPOS,
+        current_pos: POINT { x, y },
+        area: mem::zeroed(),
+    };
+    ImmSetCandidateWindow(himc, &mut candidate_form);
+    ImmReleaseContext(hwnd, himc);
+}
+
+/// Opens the system menu at `(x, y)` in client coordinates, and runs the command picked from it.
+pub unsafe fn show_window_menu(hwnd: HWND, x: i32, y: i32) {
+    let menu = winuser::GetSystemMenu(hwnd, FALSE);
+    if menu.is_null() {
+        return;
+    }
+
+    // `DefWindowProc` only updates the items for the menus it opens itself.
+    let style = winuser::GetWindowLongW(hwnd, winuser::GWL_STYLE) as DWORD;
+    let maximized = winuser::IsZoomed(hwnd) != 0;
+    let enable = |command: WPARAM, enabled: bool| {
+        let state = if enabled {
+            winuser::MF_ENABLED
+        } else {
+            winuser::MF_GRAYED
+        };
+        winuser::EnableMenuItem(menu, command as UINT, winuser::MF_BYCOMMAND | state);
+    };
+    enable(winuser::SC_RESTORE, maximized);
+    enable(winuser::SC_MOVE, !maximized);
+    enable(
+        winuser::SC_SIZE,
+        !maximized && has_flag(style, winuser::WS_SIZEBOX),
+    );
+    enable(
+        winuser::SC_MINIMIZE,
+        has_flag(style, winuser::WS_MINIMIZEBOX),
+    );
+    enable(
+        winuser::SC_MAXIMIZE,
+        !maximized && has_flag(style, winuser::WS_MAXIMIZEBOX),
+    );
+
+    let mut point = POINT { x, y };
+    winuser::ClientToScreen(hwnd, &mut point);
+    let command = winuser::TrackPopupMenu(
+        menu,
+        winuser::TPM_RETURNCMD | winuser::TPM_LEFTALIGN | winuser::TPM_RIGHTBUTTON,
+        point.x,
+        point.y,
+        0,
+        hwnd,
+        ptr::null(),
+    );
+    // Going through `WM_SYSCOMMAND` lets `DefWindowProc` carry out the command as usual.
+    if command != 0 {
+        winuser::PostMessageW(hwnd, winuser::WM_SYSCOMMAND, command as WPARAM, 0);
+    }
+}
+
 pub fn has_flag<T>(bitset: T, flag: T) -> bool
 where
     T: Copy + PartialEq + BitAnd<T, Output = T>,
@@ -26,6 +374,32 @@ pub fn wchar_to_string(wchar: &[wchar_t]) -> String {
     String::from_utf16_lossy(wchar).to_string()
 }
 
+/// Reads the "Choose your default app mode" setting. Versions of Windows without it are light.
+pub fn system_theme() -> Theme {
+    let subkey: Vec<u16> = "Software\\Microsoft\\Windows\\CurrentVersion\\Themes\\Personalize\0"
+        .encode_utf16()
+        .collect();
+    let value: Vec<u16> = "AppsUseLightTheme\0".encode_utf16().collect();
+    let mut data: DWORD = 1;
+    let mut size = mem::size_of::<DWORD>() as DWORD;
+    let status = unsafe {
+        winreg::RegGetValueW(
+            winreg::HKEY_CURRENT_USER,
+            subkey.as_ptr(),
+            value.as_ptr(),
+            winreg::RRF_RT_REG_DWORD,
+            ptr::null_mut(),
+            &mut data as *mut DWORD as *mut c_void,
+            &mut size,
+        )
+    };
+    if status == ERROR_SUCCESS as _ && data == 0 {
+        Theme::Dark
+    } else {
+        Theme::Light
+    }
+}
+
 pub fn wchar_ptr_to_string(wchar: *const wchar_t) -> String {
     let len = unsafe { lstrlenW(wchar) } as usize;
     let wchar_slice = unsafe { slice::from_raw_parts(wchar, len) };
@@ -73,6 +447,47 @@ pub fn get_client_rect(hwnd: HWND) -> Result<RECT, io::Error> {
     }
 }
 
+/// Clips the window to the union of `rects`, which are in client coordinates, or removes the clip.
+pub fn set_window_region(hwnd: HWND, rects: Option<&[RECT]>) -> Result<(), io::Error> {
+    unsafe {
+        let region = match rects {
+            Some(rects) => {
+                // Window regions are relative to the window's top left corner, not the client
+                // area's.
+                let window_rect = get_window_rect(hwnd).ok_or_else(io::Error::last_os_error)?;
+                let client_rect = get_client_rect(hwnd)?;
+                let (dx, dy) = (
+                    client_rect.left - window_rect.left,
+                    client_rect.top - window_rect.top,
+                );
+
+                let region = wingdi::CreateRectRgn(0, 0, 0, 0);
+                for rect in rects {
+                    let rect_region = wingdi::CreateRectRgn(
+                        rect.left + dx,
+                        rect.top + dy,
+                        rect.right + dx,
+                        rect.bottom + dy,
+                    );
+                    wingdi::CombineRgn(region, region, rect_region, wingdi::RGN_OR);
+                    wingdi::DeleteObject(rect_region as _);
+                }
+                region
+            },
+            None => ptr::null_mut(),
+        };
+
+        // The system owns the region once this succeeds.
+        if winuser::SetWindowRgn(hwnd, region, 1) == 0 {
+            if !region.is_null() {
+                wingdi::DeleteObject(region as _);
+            }
+            return Err(io::Error::last_os_error());
+        }
+        Ok(())
+    }
+}
+
 pub fn adjust_window_rect(hwnd: HWND, rect: RECT) -> Option<RECT> {
     unsafe {
         let style = winuser::GetWindowLongW(hwnd, winuser::GWL_STYLE);
@@ -119,6 +534,23 @@ pub fn is_focused(window: HWND) -> bool {
     window == unsafe { winuser::GetActiveWindow() }
 }
 
+thread_local! {
+    // System cursors are shared and never have to be destroyed, so each one is only loaded once.
+    static SYSTEM_CURSORS: RefCell<HashMap<CursorIcon, HCURSOR>> = RefCell::new(HashMap::new());
+}
+
+/// Returns the handle of a system cursor, loading it on first use.
+pub fn load_system_cursor(cursor: CursorIcon) -> HCURSOR {
+    SYSTEM_CURSORS.with(|cursors| {
+        *cursors
+            .borrow_mut()
+            .entry(cursor)
+            .or_insert_with(|| unsafe {
+                winuser::LoadCursorW(ptr::null_mut(), cursor.to_windows_cursor())
+            })
+    })
+}
+
 impl CursorIcon {
     pub(crate) fn to_windows_cursor(self) -> *const wchar_t {
         match self {
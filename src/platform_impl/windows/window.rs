@@ -13,16 +13,15 @@ use std::{
 use winapi::{
     ctypes::c_int,
     shared::{
-        minwindef::{DWORD, LPARAM, UINT, WORD, WPARAM},
+        minwindef::{BOOL, DWORD, LPARAM, UINT, WORD, WPARAM},
         windef::{HWND, POINT, RECT},
     },
     um::{
-        combaseapi, dwmapi, libloaderapi,
+        combaseapi, libloaderapi,
         objbase::COINIT_APARTMENTTHREADED,
         ole2,
         oleidl::LPDROPTARGET,
-        shobjidl_core::{CLSID_TaskbarList, ITaskbarList2},
-        wingdi::{CreateRectRgn, DeleteObject},
+        shobjidl_core::{CLSID_TaskbarList, ITaskbarList3},
         winnt::{LONG, LPCWSTR},
         winuser,
     },
@@ -32,21 +31,25 @@ use crate::{
     dpi::{LogicalPosition, LogicalSize, PhysicalSize},
     error::{ExternalError, NotSupportedError, OsError as RootOsError},
     monitor::MonitorHandle as RootMonitorHandle,
+    platform::windows::BlurEffect,
     platform_impl::platform::{
         dpi::{dpi_to_scale_factor, hwnd_dpi},
         drop_handler::FileDropHandler,
+        event,
         event_loop::{
             self, EventLoopWindowTarget, DESTROY_MSG_ID, INITIAL_DPI_MSG_ID,
             REQUEST_REDRAW_NO_NEWEVENTS_MSG_ID,
         },
-        icon::{self, IconType, WinIcon},
-        monitor,
-        raw_input::register_all_mice_and_keyboards_for_raw_input,
-        util,
+        icon::{self, IconType, WinCursor, WinIcon},
+        monitor, util,
         window_state::{CursorFlags, SavedWindow, WindowFlags, WindowState},
         PlatformSpecificWindowBuilderAttributes, WindowId,
     },
-    window::{CursorIcon, Icon, WindowAttributes},
+    window::{
+        clamp_inner_size, fit_placement, BadCursor, CursorIcon, FullscreenType, Icon,
+        PlacementArea, Rect, ResizeDirection, UserAttentionType, WindowAttributes, WindowButtons,
+        WindowPlacement,
+    },
 };
 
 /// The Win32 implementation of the main `Window` object.
@@ -71,9 +74,10 @@ impl Window {
         // First person to remove the need for cloning here gets a cookie!
         //
         // done. you owe me -- ossi
+        let drag_and_drop = pl_attr.drag_and_drop;
         unsafe {
             init(w_attr, pl_attr, event_loop).map(|win| {
-                let file_drop_handler = {
+                let file_drop_handler = if drag_and_drop {
                     use winapi::shared::winerror::{OLE_E_WRONGCOMPOBJ, RPC_E_CHANGED_MODE, S_OK};
 
                     let ole_init_result = ole2::OleInitialize(ptr::null_mut());
@@ -101,7 +105,9 @@ impl Window {
                         ole2::RegisterDragDrop(win.window.0, handler_interface_ptr),
                         S_OK
                     );
-                    file_drop_handler
+                    Some(file_drop_handler)
+                } else {
+                    None
                 };
 
                 let subclass_input = event_loop::SubclassInput {
@@ -117,18 +123,34 @@ impl Window {
     }
 
     pub fn set_title(&self, text: &str) {
+        self.window_state.lock().title = text.to_owned();
         let text = OsStr::new(text)
             .encode_wide()
             .chain(Some(0).into_iter())
             .collect::<Vec<_>>();
-        unsafe {
-            winuser::SetWindowTextW(self.window.0, text.as_ptr() as LPCWSTR);
-        }
+        let window = self.window.clone();
+        // `SetWindowTextW` sends `WM_SETTEXT`, which would block other threads until the event
+        // loop gets around to it.
+        self.thread_executor.execute_in_thread(move || unsafe {
+            winuser::SetWindowTextW(window.0, text.as_ptr() as LPCWSTR);
+        });
+    }
+
+    pub fn get_title(&self) -> String {
+        self.window_state.lock().title.clone()
     }
 
     #[inline]
     pub fn set_visible(&self, visible: bool) {
+        let no_activate = self
+            .window_state
+            .lock()
+            .window_flags()
+            .contains(WindowFlags::NO_ACTIVATE);
         match visible {
+            true if no_activate => unsafe {
+                winuser::ShowWindow(self.window.0, winuser::SW_SHOWNA);
+            },
             true => unsafe {
                 winuser::ShowWindow(self.window.0, winuser::SW_SHOW);
             },
@@ -138,6 +160,32 @@ impl Window {
         }
     }
 
+    #[inline]
+    pub fn close(&self) {
+        // The window must be destroyed from the same thread that created it, so we send a
+        // custom message to be handled by our callback to do the actual work. Posting to a window
+        // that's already been destroyed simply fails.
+        unsafe {
+            winuser::PostMessageW(self.window.0, *DESTROY_MSG_ID, 0, 0);
+        }
+    }
+
+    #[inline]
+    pub fn is_focused(&self) -> bool {
+        // `GetActiveWindow` only works on the thread that owns the window, unlike this.
+        unsafe { winuser::GetForegroundWindow() == self.window.0 }
+    }
+
+    #[inline]
+    pub fn is_visible(&self) -> Option<bool> {
+        unsafe {
+            if winuser::IsWindow(self.window.0) == 0 {
+                return None;
+            }
+            Some(winuser::IsWindowVisible(self.window.0) != 0)
+        }
+    }
+
     #[inline]
     pub fn request_redraw(&self) {
         unsafe {
@@ -154,10 +202,14 @@ impl Window {
         }
     }
 
+    #[inline]
+    pub fn pre_present_notify(&self) {}
+
     pub(crate) fn outer_position_physical(&self) -> (i32, i32) {
+        // This only fails once the window has been closed.
         util::get_window_rect(self.window.0)
             .map(|rect| (rect.left as i32, rect.top as i32))
-            .unwrap()
+            .unwrap_or_default()
     }
 
     #[inline]
@@ -173,6 +225,9 @@ impl Window {
     pub(crate) fn inner_position_physical(&self) -> (i32, i32) {
         let mut position: POINT = unsafe { mem::zeroed() };
         if unsafe { winuser::ClientToScreen(self.window.0, &mut position) } == 0 {
+            if unsafe { winuser::IsWindow(self.window.0) } == 0 {
+                return (0, 0);
+            }
             panic!("Unexpected ClientToScreen failure: please report this error to https://github.com/rust-windowing/winit")
         }
         (position.x, position.y)
@@ -189,9 +244,11 @@ impl Window {
     }
 
     pub(crate) fn set_position_physical(&self, x: i32, y: i32) {
-        unsafe {
+        let window = self.window.clone();
+        // `UpdateWindow` paints right away, which other threads would have to wait for.
+        self.thread_executor.execute_in_thread(move || unsafe {
             winuser::SetWindowPos(
-                self.window.0,
+                window.0,
                 ptr::null_mut(),
                 x as c_int,
                 y as c_int,
@@ -199,8 +256,8 @@ impl Window {
                 0,
                 winuser::SWP_ASYNCWINDOWPOS | winuser::SWP_NOZORDER | winuser::SWP_NOSIZE,
             );
-            winuser::UpdateWindow(self.window.0);
-        }
+            winuser::UpdateWindow(window.0);
+        });
     }
 
     #[inline]
@@ -213,6 +270,9 @@ impl Window {
     pub(crate) fn inner_size_physical(&self) -> (u32, u32) {
         let mut rect: RECT = unsafe { mem::uninitialized() };
         if unsafe { winuser::GetClientRect(self.window.0, &mut rect) } == 0 {
+            if unsafe { winuser::IsWindow(self.window.0) } == 0 {
+                return (0, 0);
+            }
             panic!("Unexpected GetClientRect failure: please report this error to https://github.com/rust-windowing/winit")
         }
         (
@@ -229,6 +289,7 @@ impl Window {
     }
 
     pub(crate) fn outer_size_physical(&self) -> (u32, u32) {
+        // This only fails once the window has been closed.
         util::get_window_rect(self.window.0)
             .map(|rect| {
                 (
@@ -236,7 +297,7 @@ impl Window {
                     (rect.bottom - rect.top) as u32,
                 )
             })
-            .unwrap()
+            .unwrap_or_default()
     }
 
     #[inline]
@@ -278,10 +339,30 @@ impl Window {
     }
 
     #[inline]
-    pub fn set_inner_size(&self, logical_size: LogicalSize) {
+    pub fn set_inner_size(&self, logical_size: LogicalSize) -> Option<LogicalSize> {
         let dpi_factor = self.hidpi_factor();
+        let inner_size = self.inner_size_physical();
+        let available = {
+            let (outer_width, outer_height) = self.outer_size_physical();
+            let (work_width, work_height): (u32, u32) = monitor::current_monitor(self.window.0)
+                .work_area_size()
+                .into();
+            let width = work_width.saturating_sub(outer_width - inner_size.0);
+            let height = work_height.saturating_sub(outer_height - inner_size.1);
+            LogicalSize::from_physical((width, height), dpi_factor)
+        };
+        let (min_size, max_size) = {
+            let window_state = self.window_state.lock();
+            (window_state.min_size, window_state.max_size)
+        };
+        let logical_size = clamp_inner_size(logical_size, min_size, max_size, Some(available));
         let (width, height) = logical_size.to_physical(dpi_factor).into();
+        if (width, height) == inner_size {
+            return Some(logical_size);
+        }
+        // The `WM_SIZE` caused by this is what produces the `Resized` event.
         self.set_inner_size_physical(width, height);
+        Some(logical_size)
     }
 
     pub(crate) fn set_min_inner_size_physical(&self, dimensions: Option<(u32, u32)>) {
@@ -316,13 +397,123 @@ impl Window {
         self.set_max_inner_size_physical(physical_size);
     }
 
+    /// The size of the window's decorations.
+    fn frame_size(&self) -> Option<(LONG, LONG)> {
+        let zero = RECT {
+            left: 0,
+            top: 0,
+            right: 0,
+            bottom: 0,
+        };
+        util::adjust_window_rect(self.window.0, zero)
+            .map(|rect| (rect.right - rect.left, rect.bottom - rect.top))
+    }
+
+    pub fn get_placement(&self) -> Option<WindowPlacement> {
+        let mut placement: winuser::WINDOWPLACEMENT = unsafe { mem::zeroed() };
+        placement.length = mem::size_of::<winuser::WINDOWPLACEMENT>() as UINT;
+        if unsafe { winuser::GetWindowPlacement(self.window.0, &mut placement) } == 0 {
+            return None;
+        }
+        let (frame_width, frame_height) = self.frame_size()?;
+
+        // `rcNormalPosition` is where the window goes when it's restored, even while it's
+        // maximized or minimized.
+        let (offset_x, offset_y) = monitor::workspace_offset();
+        let rect = RECT {
+            left: placement.rcNormalPosition.left + offset_x,
+            top: placement.rcNormalPosition.top + offset_y,
+            right: placement.rcNormalPosition.right + offset_x,
+            bottom: placement.rcNormalPosition.bottom + offset_y,
+        };
+        let monitor = monitor::MonitorHandle::from_hmonitor(unsafe {
+            winuser::MonitorFromRect(&rect, winuser::MONITOR_DEFAULTTONEAREST)
+        });
+        let dpi_factor = monitor.hidpi_factor();
+        let inner_size = (
+            (rect.right - rect.left - frame_width).max(0) as u32,
+            (rect.bottom - rect.top - frame_height).max(0) as u32,
+        );
+        let maximized = placement.showCmd == winuser::SW_SHOWMAXIMIZED as UINT
+            || (placement.showCmd == winuser::SW_SHOWMINIMIZED as UINT
+                && util::has_flag(placement.flags, winuser::WPF_RESTORETOMAXIMIZED));
+
+        Some(WindowPlacement {
+            position: LogicalPosition::from_physical((rect.left, rect.top), dpi_factor),
+            inner_size: LogicalSize::from_physical(inner_size, dpi_factor),
+            maximized,
+            monitor_native_id: Some(monitor.native_identifier()),
+        })
+    }
+
+    pub fn set_placement(&self, placement: &WindowPlacement) {
+        let areas: Vec<_> = monitor::available_monitors()
+            .iter()
+            .map(|monitor| {
+                PlacementArea {
+                    native_id: monitor.native_identifier(),
+                    position: monitor.work_area_position(),
+                    size: monitor.work_area_size(),
+                    hidpi_factor: monitor.hidpi_factor(),
+                }
+            })
+            .collect();
+        let (position, size, _) = match fit_placement(placement, &areas) {
+            Some(fitted) => fitted,
+            None => return,
+        };
+        let (frame_width, frame_height) = match self.frame_size() {
+            Some(frame_size) => frame_size,
+            None => return,
+        };
+        let (x, y): (i32, i32) = position.into();
+        let (width, height): (u32, u32) = size.into();
+        let maximized = placement.maximized;
+
+        let window = self.window.clone();
+        let window_state = Arc::clone(&self.window_state);
+
+        self.thread_executor.execute_in_thread(move || unsafe {
+            let visible = winuser::IsWindowVisible(window.0) != 0;
+            let (offset_x, offset_y) = monitor::workspace_offset();
+            let mut window_placement: winuser::WINDOWPLACEMENT = mem::zeroed();
+            window_placement.length = mem::size_of::<winuser::WINDOWPLACEMENT>() as UINT;
+            window_placement.showCmd = match (visible, maximized) {
+                (false, _) => winuser::SW_HIDE,
+                (true, true) => winuser::SW_SHOWMAXIMIZED,
+                (true, false) => winuser::SW_SHOWNOACTIVATE,
+            } as UINT;
+            // This keeps the restored geometry even when the window is maximized right away.
+            window_placement.rcNormalPosition = RECT {
+                left: x - offset_x,
+                top: y - offset_y,
+                right: x - offset_x + width as LONG + frame_width,
+                bottom: y - offset_y + height as LONG + frame_height,
+            };
+            winuser::SetWindowPlacement(window.0, &window_placement);
+
+            // The `WM_SIZE` from `SetWindowPlacement` only updates the flags of visible windows.
+            if !visible {
+                WindowState::set_window_flags(window_state.lock(), window.0, None, |f| {
+                    f.set(WindowFlags::MAXIMIZED, maximized)
+                });
+            }
+        });
+    }
+
     #[inline]
     pub fn set_resizable(&self, resizable: bool) {
         let window = self.window.clone();
         let window_state = Arc::clone(&self.window_state);
 
         self.thread_executor.execute_in_thread(move || {
-            WindowState::set_window_flags(window_state.lock(), window.0, None, |f| {
+            // The resizable frame is thicker, so the client area is kept in place to keep the
+            // window's size. A maximized window keeps filling the monitor instead.
+            let client_rect = match unsafe { winuser::IsZoomed(window.0) } {
+                0 => util::get_client_rect(window.0).ok(),
+                _ => None,
+            };
+            WindowState::set_window_flags(window_state.lock(), window.0, client_rect, |f| {
                 f.set(WindowFlags::RESIZABLE, resizable)
             });
         });
@@ -336,10 +527,34 @@ impl Window {
 
     #[inline]
     pub fn set_cursor_icon(&self, cursor: CursorIcon) {
-        self.window_state.lock().mouse.cursor = cursor;
+        {
+            let mut window_state = self.window_state.lock();
+            window_state.mouse.cursor = cursor;
+            window_state.mouse.custom_cursor = None;
+        }
         self.thread_executor.execute_in_thread(move || unsafe {
-            let cursor = winuser::LoadCursorW(ptr::null_mut(), cursor.to_windows_cursor());
-            winuser::SetCursor(cursor);
+            winuser::SetCursor(util::load_system_cursor(cursor));
+        });
+    }
+
+    #[inline]
+    pub fn create_custom_cursor(
+        &self,
+        rgba: &[u8],
+        width: u32,
+        height: u32,
+        hotspot_x: u32,
+        hotspot_y: u32,
+    ) -> Result<WinCursor, BadCursor> {
+        WinCursor::from_rgba(rgba, width, height, hotspot_x, hotspot_y)
+    }
+
+    #[inline]
+    pub fn set_custom_cursor(&self, cursor: &WinCursor) {
+        self.window_state.lock().mouse.custom_cursor = Some(cursor.clone());
+        let cursor = cursor.clone();
+        self.thread_executor.execute_in_thread(move || unsafe {
+            winuser::SetCursor(cursor.handle());
         });
     }
 
@@ -405,6 +620,108 @@ impl Window {
         self.set_cursor_position_physical(x, y)
     }
 
+    /// Starts a modal move/resize loop by faking a click on the given non-client area.
+    fn drag_initiate(&self, hit_test: isize) -> Result<(), ExternalError> {
+        let mut point = POINT { x: 0, y: 0 };
+        unsafe {
+            if winuser::GetAsyncKeyState(winuser::VK_LBUTTON) as u16 & 0x8000 == 0 {
+                return Err(ExternalError::Os(os_error!(io::Error::new(
+                    io::ErrorKind::Other,
+                    "Window drag could not be initiated: the left mouse button isn't pressed",
+                ))));
+            }
+            if winuser::GetCursorPos(&mut point) == 0 {
+                return Err(ExternalError::Os(os_error!(io::Error::last_os_error())));
+            }
+        }
+
+        let window = self.window.clone();
+        let lparam = ((point.y as WORD as DWORD) << 16 | point.x as WORD as DWORD) as LPARAM;
+        self.thread_executor.execute_in_thread(move || unsafe {
+            // The button press captured the mouse, which would keep the system from tracking it.
+            winuser::ReleaseCapture();
+            winuser::PostMessageW(
+                window.0,
+                winuser::WM_NCLBUTTONDOWN,
+                hit_test as WPARAM,
+                lparam,
+            );
+        });
+        Ok(())
+    }
+
+    #[inline]
+    pub fn drag_window(&self) -> Result<(), ExternalError> {
+        self.drag_initiate(winuser::HTCAPTION)
+    }
+
+    #[inline]
+    pub fn drag_resize_window(&self, direction: ResizeDirection) -> Result<(), ExternalError> {
+        self.drag_initiate(match direction {
+            ResizeDirection::East => winuser::HTRIGHT,
+            ResizeDirection::North => winuser::HTTOP,
+            ResizeDirection::NorthEast => winuser::HTTOPRIGHT,
+            ResizeDirection::NorthWest => winuser::HTTOPLEFT,
+            ResizeDirection::South => winuser::HTBOTTOM,
+            ResizeDirection::SouthEast => winuser::HTBOTTOMRIGHT,
+            ResizeDirection::SouthWest => winuser::HTBOTTOMLEFT,
+            ResizeDirection::West => winuser::HTLEFT,
+        })
+    }
+
+    #[inline]
+    pub fn set_enabled(&self, enabled: bool) {
+        let window = self.window.clone();
+        self.thread_executor.execute_in_thread(move || unsafe {
+            // A disabled window can't keep the keyboard focus, so hand it over to the parent or
+            // owner window, if there's one.
+            if !enabled && winuser::GetFocus() == window.0 {
+                let parent = winuser::GetParent(window.0);
+                if !parent.is_null() {
+                    winuser::SetFocus(parent);
+                }
+            }
+            winuser::EnableWindow(window.0, enabled as BOOL);
+        });
+    }
+
+    #[inline]
+    pub fn show_window_menu(&self, position: LogicalPosition) {
+        let window = self.window.clone();
+        let (x, y) = position.to_physical(self.hidpi_factor()).into();
+        self.thread_executor.execute_in_thread(move || unsafe {
+            util::show_window_menu(window.0, x, y);
+        });
+    }
+
+    #[inline]
+    pub fn request_user_attention(&self, request_type: Option<UserAttentionType>) {
+        let window = self.window.clone();
+
+        self.thread_executor.execute_in_thread(move || unsafe {
+            let (flags, count) = match request_type {
+                Some(UserAttentionType::Critical) => {
+                    (
+                        winuser::FLASHW_ALL | winuser::FLASHW_TIMERNOFG,
+                        UINT::max_value(),
+                    )
+                },
+                Some(UserAttentionType::Informational) => {
+                    (winuser::FLASHW_TRAY | winuser::FLASHW_TIMERNOFG, 0)
+                },
+                None => (winuser::FLASHW_STOP, 0),
+            };
+            let mut flash_info = winuser::FLASHWINFO {
+                cbSize: mem::size_of::<winuser::FLASHWINFO>() as UINT,
+                hwnd: window.0,
+                dwFlags: flags,
+                uCount: count,
+                dwTimeout: 0,
+            };
+            winuser::FlashWindowEx(&mut flash_info);
+        });
+    }
+
     #[inline]
     pub fn id(&self) -> WindowId {
         WindowId(self.window.0)
@@ -423,73 +740,109 @@ impl Window {
     }
 
     #[inline]
-    pub fn fullscreen(&self) -> Option<RootMonitorHandle> {
+    pub fn get_maximized(&self) -> bool {
+        unsafe { winuser::IsWindow(self.window.0) != 0 && winuser::IsZoomed(self.window.0) != 0 }
+    }
+
+    #[inline]
+    pub fn fullscreen(&self) -> Option<FullscreenType> {
         let window_state = self.window_state.lock();
         window_state.fullscreen.clone()
     }
 
     #[inline]
-    pub fn set_fullscreen(&self, monitor: Option<RootMonitorHandle>) {
-        unsafe {
-            let window = self.window.clone();
-            let window_state = Arc::clone(&self.window_state);
+    pub fn set_fullscreen(&self, fullscreen: Option<FullscreenType>) {
+        let window = self.window.clone();
+        let window_state = Arc::clone(&self.window_state);
+
+        let mut fullscreen = fullscreen.map(|fullscreen| {
+            match fullscreen {
+                // The window may be entirely offscreen, in which case the nearest monitor is used.
+                FullscreenType::Borderless(None) => {
+                    FullscreenType::Borderless(Some(RootMonitorHandle {
+                        inner: monitor::current_monitor(self.window.0),
+                    }))
+                },
+                fullscreen => fullscreen,
+            }
+        });
+
+        self.thread_executor.execute_in_thread(move || {
+            // There's nothing left to do once the window has been closed.
+            if unsafe { winuser::IsWindow(window.0) } == 0 {
+                return;
+            }
+            let mut window_state_lock = window_state.lock();
+
+            if let Some(FullscreenType::Exclusive(ref monitor)) = window_state_lock.fullscreen {
+                if let Err(e) = monitor.inner.set_exclusive(false, None) {
+                    warn!("Failed to leave exclusive fullscreen: {}", e);
+                }
+            }
 
-            match &monitor {
-                &Some(RootMonitorHandle { ref inner }) => {
-                    let (x, y): (i32, i32) = inner.position().into();
-                    let (width, height): (u32, u32) = inner.size().into();
+            match fullscreen.take() {
+                Some(fullscreen) => {
+                    let monitor = fullscreen
+                        .monitor()
+                        .expect("fullscreen monitor should have been resolved")
+                        .clone();
+                    if let FullscreenType::Exclusive(_) = fullscreen {
+                        // Still cover the monitor like borderless fullscreen if this fails.
+                        let video_mode = window_state_lock.video_mode.as_ref();
+                        if let Err(e) = monitor.inner.set_exclusive(true, video_mode) {
+                            warn!("Failed to enter exclusive fullscreen: {}", e);
+                        }
+                    }
 
-                    let mut monitor = monitor.clone();
-                    self.thread_executor.execute_in_thread(move || {
-                        let mut window_state_lock = window_state.lock();
+                    let (x, y): (i32, i32) = monitor.inner.position().into();
+                    let (width, height): (u32, u32) = monitor.inner.size().into();
 
+                    // Only remember the windowed state, not that of a previous fullscreen.
+                    if window_state_lock.fullscreen.is_none() {
                         let client_rect =
                             util::get_client_rect(window.0).expect("get client rect failed!");
                         window_state_lock.saved_window = Some(SavedWindow {
                             client_rect,
                             dpi_factor: window_state_lock.dpi_factor,
                         });
+                    }
+
+                    window_state_lock.fullscreen = Some(fullscreen);
+                    WindowState::refresh_window_state(
+                        window_state_lock,
+                        window.0,
+                        Some(RECT {
+                            left: x,
+                            top: y,
+                            right: x + width as c_int,
+                            bottom: y + height as c_int,
+                        }),
+                    );
+
+                    unsafe { mark_fullscreen(window.0, true) };
+                },
+                None => {
+                    window_state_lock.fullscreen = None;
+
+                    if let Some(SavedWindow {
+                        client_rect,
+                        dpi_factor,
+                    }) = window_state_lock.saved_window
+                    {
+                        window_state_lock.dpi_factor = dpi_factor;
+                        window_state_lock.saved_window = None;
 
-                        window_state_lock.fullscreen = monitor.take();
                         WindowState::refresh_window_state(
                             window_state_lock,
                             window.0,
-                            Some(RECT {
-                                left: x,
-                                top: y,
-                                right: x + width as c_int,
-                                bottom: y + height as c_int,
-                            }),
+                            Some(client_rect),
                         );
+                    }
 
-                        mark_fullscreen(window.0, true);
-                    });
-                },
-                &None => {
-                    self.thread_executor.execute_in_thread(move || {
-                        let mut window_state_lock = window_state.lock();
-                        window_state_lock.fullscreen = None;
-
-                        if let Some(SavedWindow {
-                            client_rect,
-                            dpi_factor,
-                        }) = window_state_lock.saved_window
-                        {
-                            window_state_lock.dpi_factor = dpi_factor;
-                            window_state_lock.saved_window = None;
-
-                            WindowState::refresh_window_state(
-                                window_state_lock,
-                                window.0,
-                                Some(client_rect),
-                            );
-                        }
-
-                        mark_fullscreen(window.0, false);
-                    });
+                    unsafe { mark_fullscreen(window.0, false) };
                 },
             }
-        }
+        });
     }
 
     #[inline]
@@ -498,13 +851,35 @@ impl Window {
         let window_state = Arc::clone(&self.window_state);
 
         self.thread_executor.execute_in_thread(move || {
-            let client_rect = util::get_client_rect(window.0).expect("get client rect failed!");
+            // This only fails once the window has been closed.
+            let client_rect = match util::get_client_rect(window.0) {
+                Ok(client_rect) => client_rect,
+                Err(_) => return,
+            };
             WindowState::set_window_flags(window_state.lock(), window.0, Some(client_rect), |f| {
                 f.set(WindowFlags::DECORATIONS, decorations)
             });
         });
     }
 
+    #[inline]
+    pub fn get_resizable(&self) -> bool {
+        // Unlike `WS_SIZEBOX`, which is also removed from windows without decorations or in
+        // fullscreen, the flag keeps what was asked for.
+        self.window_state
+            .lock()
+            .window_flags()
+            .contains(WindowFlags::RESIZABLE)
+    }
+
+    #[inline]
+    pub fn get_decorations(&self) -> bool {
+        self.window_state
+            .lock()
+            .window_flags()
+            .contains(WindowFlags::DECORATIONS)
+    }
+
     #[inline]
     pub fn set_always_on_top(&self, always_on_top: bool) {
         let window = self.window.clone();
@@ -518,12 +893,119 @@ impl Window {
     }
 
     #[inline]
-    pub fn current_monitor(&self) -> RootMonitorHandle {
-        RootMonitorHandle {
-            inner: monitor::current_monitor(self.window.0),
+    pub fn set_skip_taskbar(&self, skip: bool) {
+        let window = self.window.clone();
+        let window_state = Arc::clone(&self.window_state);
+
+        self.thread_executor.execute_in_thread(move || {
+            WindowState::set_window_flags(window_state.lock(), window.0, None, |f| {
+                f.set(WindowFlags::ON_TASKBAR, !skip)
+            });
+        });
+    }
+
+    #[inline]
+    pub fn current_monitor(&self) -> Option<RootMonitorHandle> {
+        let hmonitor =
+            unsafe { winuser::MonitorFromWindow(self.window.0, winuser::MONITOR_DEFAULTTONULL) };
+        if hmonitor.is_null() {
+            None
+        } else {
+            Some(RootMonitorHandle {
+                inner: monitor::MonitorHandle::from_hmonitor(hmonitor),
+            })
         }
     }
 
+    #[inline]
+    pub fn set_enabled_buttons(&self, buttons: WindowButtons) {
+        let window = self.window.clone();
+        let window_state = Arc::clone(&self.window_state);
+
+        self.thread_executor.execute_in_thread(move || {
+            WindowState::set_window_flags(window_state.lock(), window.0, None, |f| {
+                f.set(
+                    WindowFlags::NO_CLOSE_BUTTON,
+                    !buttons.contains(WindowButtons::CLOSE),
+                );
+                f.set(
+                    WindowFlags::NO_MINIMIZE_BUTTON,
+                    !buttons.contains(WindowButtons::MINIMIZE),
+                );
+                f.set(
+                    WindowFlags::NO_MAXIMIZE_BUTTON,
+                    !buttons.contains(WindowButtons::MAXIMIZE),
+                );
+            });
+        });
+    }
+
+    #[inline]
+    pub fn set_content_protected(&self, protected: bool) {
+        let window = self.window.clone();
+        self.thread_executor.execute_in_thread(move || {
+            util::set_content_protected(window.0, protected);
+        });
+    }
+
+    #[inline]
+    pub fn set_opacity(&self, opacity: f32) {
+        let window = self.window.clone();
+        let window_state = Arc::clone(&self.window_state);
+
+        self.thread_executor.execute_in_thread(move || {
+            // `WS_EX_LAYERED` is only added once it's needed, since layered windows are more
+            // expensive to compose.
+            WindowState::set_window_flags(window_state.lock(), window.0, None, |f| {
+                f.set(WindowFlags::LAYERED, true)
+            });
+            unsafe {
+                winuser::SetLayeredWindowAttributes(
+                    window.0,
+                    0,
+                    (opacity * 255.0).round() as u8,
+                    winuser::LWA_ALPHA,
+                );
+            }
+        });
+    }
+
+    #[inline]
+    pub fn set_cursor_hittest(&self, hittest: bool) -> Result<(), ExternalError> {
+        let window = self.window.clone();
+        let window_state = Arc::clone(&self.window_state);
+
+        self.thread_executor.execute_in_thread(move || {
+            let window_state_lock = window_state.lock();
+            let was_layered = window_state_lock
+                .window_flags()
+                .contains(WindowFlags::LAYERED);
+            WindowState::set_window_flags(window_state_lock, window.0, None, |f| {
+                f.set(WindowFlags::IGNORE_CURSOR_EVENT, !hittest);
+                f.set(WindowFlags::LAYERED, was_layered || !hittest);
+            });
+            if !was_layered && !hittest {
+                // A layered window isn't drawn at all until its attributes are set.
+                unsafe {
+                    winuser::SetLayeredWindowAttributes(window.0, 0, 255, winuser::LWA_ALPHA);
+                }
+            }
+        });
+
+        Ok(())
+    }
+
+    #[inline]
+    pub fn set_shape(&self, shape: Option<&[Rect]>) -> Result<(), ExternalError> {
+        let shape_region = {
+            let mut window_state = self.window_state.lock();
+            window_state.shape = shape.map(<[Rect]>::to_vec);
+            window_state.shape_region()
+        };
+        util::set_window_region(self.window.0, shape_region.as_ref().map(Vec::as_slice))
+            .map_err(|e| ExternalError::Os(os_error!(e)))
+    }
+
     #[inline]
     pub fn set_window_icon(&self, mut window_icon: Option<Icon>) {
         let window_icon = window_icon
@@ -551,19 +1033,84 @@ impl Window {
     }
 
     #[inline]
-    pub fn set_ime_position(&self, _logical_spot: LogicalPosition) {
-        unimplemented!();
+    pub fn set_taskbar_overlay_icon(&self, overlay_icon: Option<Icon>, description: &str) {
+        let overlay_icon = overlay_icon.map(|icon| {
+            WinIcon::from_icon(icon).expect("Failed to create the taskbar overlay icon")
+        });
+        let description = OsStr::new(description)
+            .encode_wide()
+            .chain(Some(0).into_iter())
+            .collect::<Vec<_>>();
+        let handle = overlay_icon
+            .as_ref()
+            .map_or(ptr::null_mut(), |overlay_icon| overlay_icon.handle);
+        unsafe {
+            with_taskbar_list(|task_bar_list| {
+                (*task_bar_list).SetOverlayIcon(
+                    self.window.0,
+                    handle,
+                    description.as_ptr() as LPCWSTR,
+                );
+            });
+        }
+        // The taskbar has let go of the previous icon by now, so it can be destroyed.
+        self.window_state.lock().taskbar_overlay_icon = overlay_icon;
+    }
+
+    #[inline]
+    pub fn set_blur_behind(&self, effect: BlurEffect) -> Result<(), ExternalError> {
+        let transparent = self
+            .window_state
+            .lock()
+            .window_flags()
+            .contains(WindowFlags::TRANSPARENT);
+        if !transparent || !util::is_blur_effect_supported(effect) {
+            return Err(ExternalError::NotSupported(NotSupportedError::new()));
+        }
+
+        let window = self.window.clone();
+        self.thread_executor.execute_in_thread(move || {
+            util::set_blur_behind(window.0, effect);
+        });
+        Ok(())
+    }
+
+    #[inline]
+    pub fn set_ime_position(&self, logical_spot: LogicalPosition) {
+        let (x, y): (i32, i32) = {
+            let mut window_state = self.window_state.lock();
+            window_state.ime_position = Some(logical_spot);
+            logical_spot.to_physical(window_state.dpi_factor).into()
+        };
+        let window = self.window.clone();
+        self.thread_executor.execute_in_thread(move || unsafe {
+            util::set_ime_position(window.0, x, y);
+        });
+    }
+
+    #[inline]
+    pub fn reset_dead_keys(&self) {
+        // The dead key is kept for the thread that receives the keyboard input.
+        self.thread_executor
+            .execute_in_thread(event::reset_dead_keys);
+    }
+
+    #[inline]
+    pub fn set_ime_allowed(&self, allowed: bool) {
+        let window = self.window.clone();
+        self.thread_executor.execute_in_thread(move || unsafe {
+            // Associating a null input context disables IME for the window, while `IACE_DEFAULT`
+            // restores the default one.
+            let flags = if allowed { util::IACE_DEFAULT } else { 0 };
+            util::ImmAssociateContextEx(window.0, ptr::null_mut(), flags);
+        });
     }
 }
 
 impl Drop for Window {
     #[inline]
     fn drop(&mut self) {
-        unsafe {
-            // The window must be destroyed from the same thread that created it, so we send a
-            // custom message to be handled by our callback to do the actual work.
-            winuser::PostMessageW(self.window.0, *DESTROY_MSG_ID, 0, 0);
-        }
+        self.close();
     }
 }
 
@@ -600,6 +1147,17 @@ unsafe fn init<T: 'static>(
     pl_attribs: PlatformSpecificWindowBuilderAttributes,
     event_loop: &EventLoopWindowTarget<T>,
 ) -> Result<Window, RootOsError> {
+    if let (Some(FullscreenType::Exclusive(monitor)), Some(video_mode)) =
+        (&attributes.fullscreen, &attributes.video_mode)
+    {
+        if !monitor.video_modes().any(|mode| mode == *video_mode) {
+            return Err(os_error!(io::Error::new(
+                io::ErrorKind::NotFound,
+                format!("video mode {} isn't supported by the monitor", video_mode),
+            )));
+        }
+    }
+
     let title = OsStr::new(&attributes.title)
         .encode_wide()
         .chain(Some(0).into_iter())
@@ -671,7 +1229,8 @@ unsafe fn init<T: 'static>(
     // WindowFlags::VISIBLE and MAXIMIZED are set down below after the window has been configured.
     window_flags.set(WindowFlags::RESIZABLE, attributes.resizable);
     window_flags.set(WindowFlags::CHILD, pl_attribs.parent.is_some());
-    window_flags.set(WindowFlags::ON_TASKBAR, true);
+    window_flags.set(WindowFlags::ON_TASKBAR, !attributes.skip_taskbar);
+    window_flags.set(WindowFlags::NO_ACTIVATE, !attributes.active);
 
     // creating the real window this time, by using the functions in `extra_functions`
     let real_window = {
@@ -699,7 +1258,11 @@ unsafe fn init<T: 'static>(
     };
 
     // Set up raw input
-    register_all_mice_and_keyboards_for_raw_input(real_window.0);
+    event_loop.register_for_raw_input(real_window.0);
+
+    if attributes.content_protected {
+        util::set_content_protected(real_window.0, true);
+    }
 
     // Register for touch events if applicable
     {
@@ -728,17 +1291,7 @@ unsafe fn init<T: 'static>(
 
     // making the window transparent
     if attributes.transparent && !pl_attribs.no_redirection_bitmap {
-        let region = CreateRectRgn(0, 0, -1, -1); // makes the window transparent
-
-        let bb = dwmapi::DWM_BLURBEHIND {
-            dwFlags: dwmapi::DWM_BB_ENABLE | dwmapi::DWM_BB_BLURREGION,
-            fEnable: 1,
-            hRgnBlur: region,
-            fTransitionOnMaximized: 0,
-        };
-
-        dwmapi::DwmEnableBlurBehindWindow(real_window.0, &bb);
-        DeleteObject(region as _);
+        util::enable_blur_behind(real_window.0, false);
 
         if attributes.decorations {
             // HACK: When opaque (opacity 255), there is a trail whenever
@@ -845,7 +1398,7 @@ thread_local! {
         }
     };
 
-    static TASKBAR_LIST: Cell<*mut ITaskbarList2> = Cell::new(ptr::null_mut());
+    static TASKBAR_LIST: Cell<*mut ITaskbarList3> = Cell::new(ptr::null_mut());
 }
 
 pub fn com_initialized() {
@@ -861,6 +1414,12 @@ pub fn com_initialized() {
 // heuristics to determine how the window should be treated, which means
 // that it could still consider the window as fullscreen. :(
 unsafe fn mark_fullscreen(handle: HWND, fullscreen: bool) {
+    with_taskbar_list(|task_bar_list| {
+        (*task_bar_list).MarkFullscreenWindow(handle, if fullscreen { 1 } else { 0 });
+    })
+}
+
+unsafe fn with_taskbar_list<F: FnOnce(*mut ITaskbarList3)>(f: F) {
     com_initialized();
 
     TASKBAR_LIST.with(|task_bar_list_ptr| {
@@ -873,7 +1432,7 @@ unsafe fn mark_fullscreen(handle: HWND, fullscreen: bool) {
                 &CLSID_TaskbarList,
                 ptr::null_mut(),
                 combaseapi::CLSCTX_ALL,
-                &ITaskbarList2::uuidof(),
+                &ITaskbarList3::uuidof(),
                 &mut task_bar_list as *mut _ as *mut _,
             );
 
@@ -884,8 +1443,7 @@ unsafe fn mark_fullscreen(handle: HWND, fullscreen: bool) {
             task_bar_list_ptr.set(task_bar_list)
         }
 
-        task_bar_list = task_bar_list_ptr.get();
-        (*task_bar_list).MarkFullscreenWindow(handle, if fullscreen { 1 } else { 0 });
+        f(task_bar_list_ptr.get());
     })
 }
 
@@ -1,14 +1,19 @@
 use crate::{
-    dpi::LogicalSize,
-    monitor::MonitorHandle,
-    platform_impl::platform::{event_loop, icon::WinIcon, util},
-    window::{CursorIcon, WindowAttributes},
+    dpi::{LogicalPosition, LogicalSize},
+    event::ModifiersState,
+    monitor::VideoMode,
+    platform_impl::platform::{
+        event_loop,
+        icon::{WinCursor, WinIcon},
+        util,
+    },
+    window::{CursorIcon, FullscreenType, Rect, Theme, WindowAttributes},
 };
 use parking_lot::MutexGuard;
 use std::{io, ptr};
 use winapi::{
     shared::{
-        minwindef::DWORD,
+        minwindef::{DWORD, FALSE, UINT},
         windef::{HWND, RECT},
     },
     um::winuser,
@@ -25,11 +30,32 @@ pub struct WindowState {
 
     pub window_icon: Option<WinIcon>,
     pub taskbar_icon: Option<WinIcon>,
+    /// Kept alive for as long as the taskbar shows it.
+    pub taskbar_overlay_icon: Option<WinIcon>,
+
+    /// Mirrors the window text, updated on `WM_SETTEXT` and cleared on `WM_DESTROY`. Returned by
+    /// `Window::get_title`, since `GetWindowTextW` would block on the event loop.
+    pub title: String,
 
     pub saved_window: Option<SavedWindow>,
     pub dpi_factor: f64,
 
-    pub fullscreen: Option<MonitorHandle>,
+    /// `Borderless(None)` is resolved to the window's monitor when entering fullscreen.
+    pub fullscreen: Option<FullscreenType>,
+    /// The video mode the monitor is switched to in exclusive fullscreen.
+    pub video_mode: Option<VideoMode>,
+    /// Reapplied whenever a composition starts, since the IME may reset it in between.
+    pub ime_position: Option<LogicalPosition>,
+    /// The modifiers last reported through `ModifiersChanged`.
+    pub modifiers_state: ModifiersState,
+    /// Set through `Window::set_shape`, and applied again after every resize.
+    pub shape: Option<Vec<Rect>>,
+    /// `WM_CHAR` delivers characters outside the BMP as two UTF-16 surrogates in a row.
+    pub high_surrogate: Option<u16>,
+    /// The theme last reported through `ThemeChanged`.
+    pub theme: Theme,
+    /// The state last reported through `Occluded`.
+    pub occluded: bool,
     window_flags: WindowFlags,
 }
 
@@ -42,6 +68,7 @@ pub struct SavedWindow {
 #[derive(Clone)]
 pub struct MouseProperties {
     pub cursor: CursorIcon,
+    pub custom_cursor: Option<WinCursor>,
     pub buttons_down: u32,
     cursor_flags: CursorFlags,
 }
@@ -64,17 +91,23 @@ bitflags! {
         const TRANSPARENT    = 1 << 6;
         const CHILD          = 1 << 7;
         const MAXIMIZED      = 1 << 8;
+        const LAYERED        = 1 << 9;
+        const IGNORE_CURSOR_EVENT = 1 << 10;
+        const NO_ACTIVATE    = 1 << 13;
+        const NO_CLOSE_BUTTON    = 1 << 14;
+        const NO_MINIMIZE_BUTTON = 1 << 15;
+        const NO_MAXIMIZE_BUTTON = 1 << 16;
 
         /// Marker flag for fullscreen. Should always match `WindowState::fullscreen`, but is
         /// included here to make masking easier.
-        const MARKER_FULLSCREEN = 1 << 9;
+        const MARKER_FULLSCREEN = 1 << 11;
 
         /// The `WM_SIZE` event contains some parameters that can effect the state of `WindowFlags`.
         /// In most cases, it's okay to let those parameters change the state. However, when we're
         /// running the `WindowFlags::apply_diff` function, we *don't* want those parameters to
         /// effect our stored state, because the purpose of `apply_diff` is to update the actual
         /// window's state to match our stored state. This controls whether to accept those changes.
-        const MARKER_RETAIN_STATE_ON_SIZE = 1 << 10;
+        const MARKER_RETAIN_STATE_ON_SIZE = 1 << 12;
 
         const FULLSCREEN_AND_MASK = !(
             WindowFlags::DECORATIONS.bits |
@@ -96,6 +129,7 @@ impl WindowState {
         WindowState {
             mouse: MouseProperties {
                 cursor: CursorIcon::default(),
+                custom_cursor: None,
                 buttons_down: 0,
                 cursor_flags: CursorFlags::empty(),
             },
@@ -105,15 +139,46 @@ impl WindowState {
 
             window_icon,
             taskbar_icon,
+            taskbar_overlay_icon: None,
+
+            title: attributes.title.clone(),
 
             saved_window: None,
             dpi_factor,
 
             fullscreen: None,
+            video_mode: attributes.video_mode.clone(),
+            ime_position: None,
+            modifiers_state: ModifiersState::default(),
+            shape: None,
+            high_surrogate: None,
+            theme: util::system_theme(),
+            occluded: false,
             window_flags: WindowFlags::empty(),
         }
     }
 
+    /// The window shape in physical client coordinates, for `util::set_window_region`.
+    pub fn shape_region(&self) -> Option<Vec<RECT>> {
+        self.shape.as_ref().map(|shape| {
+            shape
+                .iter()
+                .map(|rect| {
+                    let position = rect.position.to_physical(self.dpi_factor);
+                    let size = rect.size.to_physical(self.dpi_factor);
+                    let (x, y): (i32, i32) = position.into();
+                    let (width, height): (u32, u32) = size.into();
+                    RECT {
+                        left: x,
+                        top: y,
+                        right: x + width as i32,
+                        bottom: y + height as i32,
+                    }
+                })
+                .collect()
+        })
+    }
+
     pub fn window_flags(&self) -> WindowFlags {
         self.window_flags
     }
@@ -197,17 +262,25 @@ impl WindowFlags {
         let (mut style, mut style_ex) = (0, 0);
 
         if self.contains(WindowFlags::RESIZABLE) {
-            style |= WS_SIZEBOX | WS_MAXIMIZEBOX;
+            style |= WS_SIZEBOX;
+            if !self.contains(WindowFlags::NO_MAXIMIZE_BUTTON) {
+                style |= WS_MAXIMIZEBOX;
+            }
         }
         if self.contains(WindowFlags::DECORATIONS) {
-            style |= WS_CAPTION | WS_MINIMIZEBOX | WS_BORDER;
+            style |= WS_CAPTION | WS_BORDER;
             style_ex = WS_EX_WINDOWEDGE;
+            if !self.contains(WindowFlags::NO_MINIMIZE_BUTTON) {
+                style |= WS_MINIMIZEBOX;
+            }
         }
         if self.contains(WindowFlags::VISIBLE) {
             style |= WS_VISIBLE;
         }
         if self.contains(WindowFlags::ON_TASKBAR) {
             style_ex |= WS_EX_APPWINDOW;
+        } else {
+            style_ex |= WS_EX_TOOLWINDOW;
         }
         if self.contains(WindowFlags::ALWAYS_ON_TOP) {
             style_ex |= WS_EX_TOPMOST;
@@ -223,6 +296,16 @@ impl WindowFlags {
         if self.contains(WindowFlags::MAXIMIZED) {
             style |= WS_MAXIMIZE;
         }
+        if self.contains(WindowFlags::LAYERED) {
+            style_ex |= WS_EX_LAYERED;
+        }
+        if self.contains(WindowFlags::IGNORE_CURSOR_EVENT) {
+            // Only has an effect on layered windows, which `set_cursor_hittest` takes care of.
+            style_ex |= WS_EX_TRANSPARENT;
+        }
+        if self.contains(WindowFlags::NO_ACTIVATE) {
+            style_ex |= WS_EX_NOACTIVATE;
+        }
 
         style |= WS_CLIPSIBLINGS | WS_CLIPCHILDREN | WS_SYSMENU;
         style_ex |= WS_EX_ACCEPTFILES;
@@ -244,13 +327,34 @@ impl WindowFlags {
             unsafe {
                 winuser::ShowWindow(
                     window,
-                    match new.contains(WindowFlags::VISIBLE) {
-                        true => winuser::SW_SHOW,
-                        false => winuser::SW_HIDE,
+                    match (
+                        new.contains(WindowFlags::VISIBLE),
+                        new.contains(WindowFlags::NO_ACTIVATE),
+                    ) {
+                        (true, false) => winuser::SW_SHOW,
+                        (true, true) => winuser::SW_SHOWNA,
+                        (false, _) => winuser::SW_HIDE,
                     },
                 );
             }
         }
+        if diff.contains(WindowFlags::NO_CLOSE_BUTTON) {
+            // There's no style for the close button, but it follows the item in the system menu.
+            unsafe {
+                let menu = winuser::GetSystemMenu(window, FALSE);
+                if !menu.is_null() {
+                    let state = match new.contains(WindowFlags::NO_CLOSE_BUTTON) {
+                        true => winuser::MF_GRAYED,
+                        false => winuser::MF_ENABLED,
+                    };
+                    winuser::EnableMenuItem(
+                        menu,
+                        winuser::SC_CLOSE as UINT,
+                        winuser::MF_BYCOMMAND | state,
+                    );
+                }
+            }
+        }
         if diff.contains(WindowFlags::ALWAYS_ON_TOP) {
             unsafe {
                 winuser::SetWindowPos(
@@ -329,6 +433,22 @@ impl WindowFlags {
                 winuser::SendMessageW(window, *event_loop::SET_RETAIN_STATE_ON_SIZE_MSG_ID, 0, 0);
             }
         }
+
+        // The taskbar only looks at the styles of a window when it's shown. Hiding the window
+        // deactivates it, so it's activated again if it had focus.
+        if diff.contains(WindowFlags::ON_TASKBAR) && new.contains(WindowFlags::VISIBLE) {
+            unsafe {
+                let was_foreground = winuser::GetForegroundWindow() == window;
+                winuser::ShowWindow(window, winuser::SW_HIDE);
+                winuser::ShowWindow(
+                    window,
+                    match was_foreground {
+                        true => winuser::SW_SHOW,
+                        false => winuser::SW_SHOWNA,
+                    },
+                );
+            }
+        }
     }
 }
 
@@ -1,18 +1,22 @@
 //! The `Window` struct and associated types.
-use std::fmt;
+use std::{cmp::Ordering, fmt};
 
 use crate::{
-    dpi::{LogicalPosition, LogicalSize},
+    dpi::{LogicalPosition, LogicalSize, PhysicalPosition, PhysicalSize},
     error::{ExternalError, NotSupportedError, OsError},
     event_loop::EventLoopWindowTarget,
-    monitor::{AvailableMonitorsIter, MonitorHandle},
+    monitor::{AvailableMonitorsIter, MonitorHandle, VideoMode},
     platform_impl,
 };
 
-pub use crate::icon::*;
+pub use crate::{cursor::*, icon::*};
 
 /// Represents a window.
 ///
+/// `Window` is `Send` and `Sync`, so it can be shared with other threads, for example in an `Arc`.
+/// Calls such as `set_title`, `request_redraw` and `set_outer_position` made from other threads
+/// are handed over to the event loop without waiting for it.
+///
 /// # Example
 ///
 /// ```no_run
@@ -51,6 +55,8 @@ impl fmt::Debug for Window {
 ///
 /// Whenever you receive an event specific to a window, this event contains a `WindowId` which you
 /// can then compare to the ids of your windows.
+///
+/// The id of a window stays the same after it has been closed.
 #[derive(Debug, Copy, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
 pub struct WindowId(pub(crate) platform_impl::WindowId);
 
@@ -66,11 +72,15 @@ impl WindowId {
 }
 
 /// Object that allows you to build windows.
-#[derive(Clone)]
+#[derive(Clone, Default)]
 pub struct WindowBuilder {
     /// The attributes to use to create the window.
     pub window: WindowAttributes,
 
+    // The size requested through `with_surface_size`, which can only be converted to the
+    // logical `inner_size` once the target monitor is known.
+    surface_size: Option<PhysicalSize>,
+
     // Platform-specific configuration. Private.
     pub(crate) platform_specific: platform_impl::PlatformSpecificWindowBuilderAttributes,
 }
@@ -79,6 +89,7 @@ impl fmt::Debug for WindowBuilder {
     fn fmt(&self, fmtr: &mut fmt::Formatter<'_>) -> fmt::Result {
         fmtr.debug_struct("WindowBuilder")
             .field("window", &self.window)
+            .field("surface_size", &self.surface_size)
             .finish()
     }
 }
@@ -110,7 +121,13 @@ pub struct WindowAttributes {
     /// Whether the window should be set as fullscreen upon creation.
     ///
     /// The default is `None`.
-    pub fullscreen: Option<MonitorHandle>,
+    pub fullscreen: Option<FullscreenType>,
+
+    /// The video mode the monitor is switched to while the window is in exclusive fullscreen. If
+    /// this is `None`, the monitor keeps its current video mode.
+    ///
+    /// The default is `None`.
+    pub video_mode: Option<VideoMode>,
 
     /// The title of the window in the title bar.
     ///
@@ -127,6 +144,11 @@ pub struct WindowAttributes {
     /// The default is `true`.
     pub visible: bool,
 
+    /// Whether the window takes keyboard focus when it's shown or clicked.
+    ///
+    /// The default is `true`.
+    pub active: bool,
+
     /// Whether the the window should be transparent. If this is true, writing colors
     /// with alpha values different than `1.0` will produce a transparent window.
     ///
@@ -143,10 +165,26 @@ pub struct WindowAttributes {
     /// The default is `false`.
     pub always_on_top: bool,
 
+    /// Whether the window should be left out of the taskbar.
+    ///
+    /// The default is `false`.
+    pub skip_taskbar: bool,
+
     /// The window icon.
     ///
     /// The default is `None`.
     pub window_icon: Option<Icon>,
+
+    /// Whether the window's content should be excluded from screen captures.
+    ///
+    /// The default is `false`.
+    pub content_protected: bool,
+
+    /// The placement to restore the window to, as returned by `Window::get_placement`. This takes
+    /// precedence over `inner_size` and `maximized`.
+    ///
+    /// The default is `None`.
+    pub placement: Option<WindowPlacement>,
 }
 
 impl Default for WindowAttributes {
@@ -160,28 +198,133 @@ impl Default for WindowAttributes {
             title: "winit window".to_owned(),
             maximized: false,
             fullscreen: None,
+            video_mode: None,
             visible: true,
+            active: true,
             transparent: false,
             decorations: true,
             always_on_top: false,
+            skip_taskbar: false,
             window_icon: None,
+            content_protected: false,
+            placement: None,
         }
     }
 }
+
+/// Clamps the size passed to `Window::set_inner_size` to the minimum and maximum inner size, and
+/// to the inner size `available` on the monitor once the decorations are taken away.
+pub(crate) fn clamp_inner_size(
+    size: LogicalSize,
+    min_size: Option<LogicalSize>,
+    max_size: Option<LogicalSize>,
+    available: Option<LogicalSize>,
+) -> LogicalSize {
+    let (mut width, mut height) = (size.width, size.height);
+    if let Some(available) = available {
+        width = width.min(available.width);
+        height = height.min(available.height);
+    }
+    if let Some(max_size) = max_size {
+        width = width.min(max_size.width);
+        height = height.min(max_size.height);
+    }
+    // The minimum size wins over everything else, as it does for user resizes.
+    if let Some(min_size) = min_size {
+        width = width.max(min_size.width);
+        height = height.max(min_size.height);
+    }
+    LogicalSize::new(width, height)
+}
+
+/// The part of a monitor that `Window::set_placement` may put windows on.
+pub(crate) struct PlacementArea {
+    pub native_id: String,
+    pub position: PhysicalPosition,
+    pub size: PhysicalSize,
+    pub hidpi_factor: f64,
+}
+
+/// Moves `placement` onto the area of the monitor it was saved on, or onto the closest area if
+/// that monitor is gone, and shrinks it to fit. Returns the outer position and inner size to use,
+/// along with the DPI factor of the area, or `None` if there are no areas at all.
+pub(crate) fn fit_placement(
+    placement: &WindowPlacement,
+    areas: &[PlacementArea],
+) -> Option<(PhysicalPosition, PhysicalSize, f64)> {
+    let distance = |area: &PlacementArea| {
+        let position = placement.position.to_physical(area.hidpi_factor);
+        let dx = (area.position.x - position.x)
+            .max(position.x - (area.position.x + area.size.width))
+            .max(0.0);
+        let dy = (area.position.y - position.y)
+            .max(position.y - (area.position.y + area.size.height))
+            .max(0.0);
+        dx * dx + dy * dy
+    };
+    let area = areas
+        .iter()
+        .find(|area| placement.monitor_native_id.as_ref() == Some(&area.native_id))
+        .or_else(|| {
+            areas.iter().min_by(|a, b| {
+                distance(a)
+                    .partial_cmp(&distance(b))
+                    .unwrap_or(Ordering::Equal)
+            })
+        })?;
+
+    let size = placement.inner_size.to_physical(area.hidpi_factor);
+    let width = size.width.min(area.size.width);
+    let height = size.height.min(area.size.height);
+    // Keeping the top-left corner inside the area means the window can't end up offscreen, even
+    // if the decorations (which aren't accounted for here) stick out past the far edges.
+    let position = placement.position.to_physical(area.hidpi_factor);
+    let x = position
+        .x
+        .min(area.position.x + area.size.width - width)
+        .max(area.position.x);
+    let y = position
+        .y
+        .min(area.position.y + area.size.height - height)
+        .max(area.position.y);
+    Some((
+        PhysicalPosition::new(x, y),
+        PhysicalSize::new(width, height),
+        area.hidpi_factor,
+    ))
+}
+
 impl WindowBuilder {
     /// Initializes a new `WindowBuilder` with default values.
     #[inline]
     pub fn new() -> WindowBuilder {
-        WindowBuilder {
-            window: Default::default(),
-            platform_specific: Default::default(),
-        }
+        Default::default()
     }
 
-    /// Requests the window to be of specific dimensions.
+    /// Requests the window's client area to be of specific dimensions, in logical points.
+    ///
+    /// The size of the framebuffer is this size multiplied by the DPI factor of the monitor the
+    /// window is created on. Use `with_surface_size` to request a framebuffer size instead.
+    ///
+    /// This overrides any previous call to `with_surface_size`.
     #[inline]
     pub fn with_inner_size(mut self, size: LogicalSize) -> WindowBuilder {
         self.window.inner_size = Some(size);
+        self.surface_size = None;
+        self
+    }
+
+    /// Requests the window's client area, and thus its framebuffer, to be of specific
+    /// dimensions, in physical pixels.
+    ///
+    /// The size is converted to logical points using the DPI factor of the monitor the window
+    /// is made fullscreen on, or of the primary monitor otherwise. If the window ends up on a
+    /// monitor with a different DPI factor, its physical size is scaled along with it.
+    ///
+    /// This overrides any previous call to `with_inner_size`.
+    #[inline]
+    pub fn with_surface_size(mut self, width: u32, height: u32) -> WindowBuilder {
+        self.surface_size = Some(PhysicalSize::new(width as f64, height as f64));
         self
     }
 
@@ -222,11 +365,26 @@ impl WindowBuilder {
         self
     }
 
-    /// Sets the window fullscreen state. None means a normal window, Some(MonitorHandle)
-    /// means a fullscreen window on that specific monitor
+    /// Sets the window fullscreen state. `None` means a normal window.
+    ///
+    /// See `Window::set_fullscreen` for details.
     #[inline]
-    pub fn with_fullscreen(mut self, monitor: Option<MonitorHandle>) -> WindowBuilder {
-        self.window.fullscreen = monitor;
+    pub fn with_fullscreen(mut self, fullscreen: Option<FullscreenType>) -> WindowBuilder {
+        self.window.fullscreen = fullscreen;
+        self
+    }
+
+    /// Sets the video mode the monitor is switched to while the window is in exclusive
+    /// fullscreen. The mode should be one of the monitor's `video_modes`, and building the window
+    /// fails if it's requested along with exclusive fullscreen on a monitor that doesn't support it.
+    ///
+    /// ## Platform-specific
+    ///
+    /// - **X11 / Wayland / iOS / Android:** Has no effect, the monitor keeps its current video
+    ///   mode.
+    #[inline]
+    pub fn with_video_mode(mut self, video_mode: VideoMode) -> WindowBuilder {
+        self.window.video_mode = Some(video_mode);
         self
     }
 
@@ -265,12 +423,49 @@ impl WindowBuilder {
         self
     }
 
+    /// Sets whether the window is left out of the taskbar, which suits tool palettes and the
+    /// windows of tray applications.
+    ///
+    /// See `Window::set_skip_taskbar` for details.
+    #[inline]
+    pub fn with_skip_taskbar(mut self, skip: bool) -> WindowBuilder {
+        self.window.skip_taskbar = skip;
+        self
+    }
+
+    /// Sets whether the window takes keyboard focus when it's shown or clicked. An inactive
+    /// window never steals focus from the window the user is typing into, which suits popups and
+    /// autocompletion overlays.
+    ///
+    /// ## Platform-specific
+    ///
+    /// - **X11:** Depends on the window manager honoring the `WM_HINTS` input field.
+    /// - **macOS:** The application isn't activated either.
+    /// - **iOS / Android / Emscripten / Wayland:** Unsupported.
+    #[inline]
+    pub fn with_active(mut self, active: bool) -> WindowBuilder {
+        self.window.active = active;
+        self
+    }
+
+    /// Sets whether or not the window's content will be protected from screen capture.
+    ///
+    /// See `Window::set_content_protected` for details.
+    #[inline]
+    pub fn with_content_protected(mut self, protected: bool) -> WindowBuilder {
+        self.window.content_protected = protected;
+        self
+    }
+
     /// Sets the window icon. On Windows and X11, this is typically the small icon in the top-left
     /// corner of the titlebar.
     ///
     /// ## Platform-specific
     ///
-    /// This only has an effect on Windows and X11.
+    /// This only has an effect on Windows, X11 and macOS.
+    ///
+    /// macOS has no window icons, so this sets the application icon shown in the Dock instead. As
+    /// it's shared by the whole application, the last window to set an icon wins.
     ///
     /// On Windows, this sets `ICON_SMALL`. The base size for a window icon is 16x16, but it's
     /// recommended to account for screen scaling and pick a multiple of that, i.e. 32x32.
@@ -283,6 +478,15 @@ impl WindowBuilder {
         self
     }
 
+    /// Restores a placement saved with `Window::get_placement`.
+    ///
+    /// See `Window::set_placement` for how it's applied.
+    #[inline]
+    pub fn with_placement(mut self, placement: WindowPlacement) -> WindowBuilder {
+        self.window.placement = Some(placement);
+        self
+    }
+
     /// Builds the window.
     ///
     /// Possible causes of error include denied permission, incompatible system, and lack of memory.
@@ -291,8 +495,20 @@ impl WindowBuilder {
         mut self,
         window_target: &EventLoopWindowTarget<T>,
     ) -> Result<Window, OsError> {
+        if let Some(size) = self.surface_size.take() {
+            let hidpi_factor = match self.window.fullscreen.as_ref().and_then(|f| f.monitor()) {
+                Some(monitor) => monitor.hidpi_factor(),
+                None => window_target.p.primary_monitor().hidpi_factor(),
+            };
+            self.window.inner_size = Some(LogicalSize::from_physical(size, hidpi_factor));
+        }
+        let placement = self.window.placement.take();
+        if let Some(ref placement) = placement {
+            self.window.inner_size = Some(placement.inner_size);
+            self.window.maximized = placement.maximized;
+        }
         self.window.inner_size = Some(self.window.inner_size.unwrap_or_else(|| {
-            if let Some(ref monitor) = self.window.fullscreen {
+            if let Some(monitor) = self.window.fullscreen.as_ref().and_then(|f| f.monitor()) {
                 // resizing the window to the dimensions of the monitor when fullscreen
                 LogicalSize::from_physical(monitor.size(), monitor.hidpi_factor()) // DPI factor applies here since this is a borderless window and not real fullscreen
             } else {
@@ -302,8 +518,13 @@ impl WindowBuilder {
         }));
 
         // building
-        platform_impl::Window::new(&window_target.p, self.window, self.platform_specific)
-            .map(|window| Window { window })
+        let window =
+            platform_impl::Window::new(&window_target.p, self.window, self.platform_specific)
+                .map(|window| Window { window })?;
+        if let Some(placement) = placement {
+            window.set_placement(&placement);
+        }
+        Ok(window)
     }
 }
 
@@ -332,7 +553,7 @@ impl Window {
     /// See the [`dpi`](dpi/index.html) module for more information.
     ///
     /// Note that this value can change depending on user action (for example if the window is
-    /// moved to another screen); as such, tracking `WindowEvent::HiDpiFactorChanged` events is
+    /// moved to another screen); as such, tracking `WindowEvent::ScaleFactorChanged` events is
     /// the most robust way to track the DPI you need to use to draw.
     ///
     /// ## Platform-specific
@@ -367,6 +588,38 @@ impl Window {
     pub fn request_redraw(&self) {
         self.window.request_redraw()
     }
+
+    /// Notifies the platform that the window is about to present a new frame.
+    ///
+    /// Call this right before swapping buffers or otherwise presenting what was drawn. Some
+    /// platforms use it to schedule the next frame and to time the presentation more accurately.
+    ///
+    /// ## Platform-specific
+    ///
+    /// - **Wayland:** Requests a frame callback for the window's surface.
+    /// - **Others:** Has no effect.
+    #[inline]
+    pub fn pre_present_notify(&self) {
+        self.window.pre_present_notify()
+    }
+
+    /// Closes the window right away, destroying the native window.
+    ///
+    /// Just like when the window is dropped, a `WindowEvent::Destroyed` event is delivered through
+    /// the event loop afterwards. From then on, setters have no effect and `is_visible` no longer
+    /// reports the window as visible. What the other getters return is unspecified, so the
+    /// state of the window should be queried before closing it. Closing a window that's already
+    /// closed does nothing.
+    ///
+    /// ## Platform-specific
+    ///
+    /// - **Windows:** The window is destroyed on the thread that created it, and the getters
+    ///   return zeroed sizes and positions afterwards.
+    /// - **iOS / Android:** Has no effect.
+    #[inline]
+    pub fn close(&self) {
+        self.window.close()
+    }
 }
 
 /// Position and size functions.
@@ -374,12 +627,16 @@ impl Window {
     /// Returns the position of the top-left hand corner of the window's client area relative to the
     /// top-left hand corner of the desktop.
     ///
-    /// The same conditions that apply to `outer_position` apply to this method.
+    /// The same conditions that apply to `outer_position` apply to this method. Unlike the outer
+    /// position, this doesn't depend on the size of the decorations, so it's the one to use for
+    /// placing things relative to the window's content.
     ///
     /// ## Platform-specific
     ///
     /// - **iOS:** Can only be called on the main thread. Returns the top left coordinates of the
     ///   window's [safe area] in the screen space coordinate system.
+    /// - **Wayland:** Always returns `Err(NotSupportedError)`, since Wayland doesn't let clients
+    ///   know where their windows are.
     ///
     /// [safe area]: https://developer.apple.com/documentation/uikit/uiview/2891103-safeareainsets?language=objc
     #[inline]
@@ -397,10 +654,23 @@ impl Window {
     /// The coordinates can be negative if the top-left hand corner of the window is outside
     ///  of the visible screen region.
     ///
+    /// This is the position of the decorations, so subtracting it from `inner_position` gives the
+    /// size of the decorations to the left of and above the client area:
+    ///
+    /// ```no_run
+    /// # use winit::{event_loop::EventLoop, window::Window};
+    /// # let event_loop = EventLoop::new();
+    /// # let window = Window::new(&event_loop).unwrap();
+    /// let outer = window.outer_position().unwrap();
+    /// let inner = window.inner_position().unwrap();
+    /// println!("left: {}, top: {}", inner.x - outer.x, inner.y - outer.y);
+    /// ```
+    ///
     /// ## Platform-specific
     ///
     /// - **iOS:** Can only be called on the main thread. Returns the top left coordinates of the
     ///   window in the screen space coordinate system.
+    /// - **Wayland:** Always returns `Err(NotSupportedError)`.
     #[inline]
     pub fn outer_position(&self) -> Result<LogicalPosition, NotSupportedError> {
         self.window.outer_position()
@@ -442,12 +712,21 @@ impl Window {
     ///
     /// See `inner_size` for more information about the values.
     ///
+    /// The size is first clamped to the minimum and maximum inner size, and so that the window
+    /// fits on the monitor it's on. The clamped size is returned, and a `WindowEvent::Resized`
+    /// with it follows once the window has been resized. If the window already has that size,
+    /// it's returned without any `Resized` event. `None` is only returned if the size can't be
+    /// changed at all.
+    ///
     /// ## Platform-specific
     ///
+    /// - **X11:** The window manager may still constrain the size further, in which case the
+    ///   `Resized` event reports the size the window actually got.
+    /// - **Android:** Has no effect, and always returns `None`.
     /// - **iOS:** Unimplemented. Currently this panics, as it's not clear what `set_inner_size`
     ///   would mean for iOS.
     #[inline]
-    pub fn set_inner_size(&self, size: LogicalSize) {
+    pub fn set_inner_size(&self, size: LogicalSize) -> Option<LogicalSize> {
         self.window.set_inner_size(size)
     }
 
@@ -484,6 +763,39 @@ impl Window {
     pub fn set_max_inner_size(&self, dimensions: Option<LogicalSize>) {
         self.window.set_max_inner_size(dimensions)
     }
+
+    /// Returns where the window is and how big it is, to be saved and later passed to
+    /// `Window::set_placement` or `WindowBuilder::with_placement` so the window reopens where the
+    /// user left it.
+    ///
+    /// ## Platform-specific
+    ///
+    /// - **Windows / macOS:** While the window is maximized, the position and size are the ones
+    ///   it's restored to.
+    /// - **X11:** While the window is maximized, the position and size are those of the maximized
+    ///   window.
+    /// - **iOS / Android / Wayland:** Always returns `None`.
+    #[inline]
+    pub fn get_placement(&self) -> Option<WindowPlacement> {
+        self.window.get_placement()
+    }
+
+    /// Moves and resizes the window to a placement returned by `Window::get_placement`.
+    ///
+    /// If the monitor the placement was saved on is gone, the window is moved to the closest
+    /// remaining one. The window is then moved and shrunk as needed to fit on that monitor, so it
+    /// never ends up offscreen.
+    ///
+    /// ## Platform-specific
+    ///
+    /// - **Windows:** The window is fit into the work area of the monitor, leaving out the
+    ///   taskbar.
+    /// - **Wayland:** Only the size and the maximized state are applied.
+    /// - **iOS / Android:** Unsupported.
+    #[inline]
+    pub fn set_placement(&self, placement: &WindowPlacement) {
+        self.window.set_placement(placement)
+    }
 }
 
 /// Misc. attribute functions.
@@ -498,6 +810,36 @@ impl Window {
         self.window.set_title(title)
     }
 
+    /// Returns the current title of the window, as reported by the platform.
+    ///
+    /// This includes changes made to the title outside of winit. Returns an empty string if the
+    /// window has been closed.
+    ///
+    /// ## Platform-specific
+    ///
+    /// - **Wayland:** Returns the title as last set by winit, since the compositor can't be queried.
+    /// - **iOS / Android / Emscripten:** Always returns an empty string.
+    #[inline]
+    pub fn get_title(&self) -> String {
+        self.window.get_title()
+    }
+
+    /// Returns whether the window currently has input focus.
+    ///
+    /// This reports the same state as the last `WindowEvent::Focused` event, but is also
+    /// available before any such event was delivered. Returns `false` if the window has been
+    /// closed.
+    ///
+    /// ## Platform-specific
+    ///
+    /// - **Wayland:** Returns whether the compositor reported the window as activated.
+    /// - **iOS:** Can only be called on the main thread.
+    /// - **Android / Emscripten:** Always returns `true`.
+    #[inline]
+    pub fn is_focused(&self) -> bool {
+        self.window.is_focused()
+    }
+
     /// Modifies the window's visibility.
     ///
     /// If `false`, this will hide the window. If `true`, this will show the window.
@@ -510,11 +852,31 @@ impl Window {
         self.window.set_visible(visible)
     }
 
+    /// Gets the window's current visibility state.
+    ///
+    /// This follows `set_visible` as well as changes made by the system. A window that's
+    /// minimized or on another workspace still counts as visible. Returns `None` if the state
+    /// can't be determined, or if the window has been closed or destroyed by the system.
+    ///
+    /// ## Platform-specific
+    ///
+    /// - **X11:** A window only counts as visible once it's been mapped, which is reported
+    ///   through the event loop after `set_visible(true)`.
+    /// - **Wayland / Android:** Always returns `None`.
+    /// - **iOS:** Can only be called on the main thread.
+    #[inline]
+    pub fn is_visible(&self) -> Option<bool> {
+        self.window.is_visible()
+    }
+
     /// Sets whether the window is resizable or not.
     ///
     /// Note that making the window unresizable doesn't exempt you from handling `Resized`, as that event can still be
     /// triggered by DPI scaling, entering fullscreen mode, etc.
     ///
+    /// The window keeps its size either way, and `set_inner_size` still works while the window
+    /// isn't resizable. The maximize button is disabled while the window isn't resizable.
+    ///
     /// ## Platform-specific
     ///
     /// This only has an effect on desktop platforms.
@@ -529,6 +891,18 @@ impl Window {
         self.window.set_resizable(resizable)
     }
 
+    /// Returns whether the window is resizable, as last set by `set_resizable` or
+    /// `WindowBuilder::with_resizable`.
+    ///
+    /// ## Platform-specific
+    ///
+    /// - **X11:** Returns `true` on Xfwm, where `set_resizable(false)` has no effect.
+    /// - **iOS / Android / Emscripten:** Always returns `false`.
+    #[inline]
+    pub fn get_resizable(&self) -> bool {
+        self.window.get_resizable()
+    }
+
     /// Sets the window to maximized or back.
     ///
     /// ## Platform-specific
@@ -539,28 +913,57 @@ impl Window {
         self.window.set_maximized(maximized)
     }
 
+    /// Returns whether the window is maximized, or `false` once it's been closed.
+    ///
+    /// ## Platform-specific
+    ///
+    /// - **Wayland:** Only `true` once the compositor has maximized the window.
+    /// - **iOS / Android:** Always returns `false`.
+    #[inline]
+    pub fn get_maximized(&self) -> bool {
+        self.window.get_maximized()
+    }
+
     /// Sets the window to fullscreen or back.
     ///
+    /// Passing `None` returns the window to its size and position from before it entered
+    /// fullscreen.
+    ///
     /// ## Platform-specific
     ///
+    /// - **X11 / Wayland:** Exclusive fullscreen currently behaves like borderless fullscreen on
+    ///   the same monitor.
+    /// - **macOS:** Exclusive fullscreen behaves like borderless fullscreen on the same monitor,
+    ///   apart from switching to the video mode requested with `WindowBuilder::with_video_mode`.
     /// - **iOS:** Can only be called on the main thread.
     #[inline]
-    pub fn set_fullscreen(&self, monitor: Option<MonitorHandle>) {
-        self.window.set_fullscreen(monitor)
+    pub fn set_fullscreen(&self, fullscreen: Option<FullscreenType>) {
+        self.window.set_fullscreen(fullscreen)
     }
 
     /// Gets the window's current fullscreen state.
     ///
+    /// This is the state last requested with `set_fullscreen`, even if the platform is still
+    /// transitioning to it. Borderless fullscreen is always reported with the monitor it's on.
+    ///
+    /// This also reflects fullscreen changes made by the user or the system, such as leaving
+    /// fullscreen through the title bar button on macOS or a window manager shortcut on X11.
+    /// Since those change the window's size, they're accompanied by `WindowEvent::Resized`, which
+    /// is a good time to query this again.
+    ///
     /// ## Platform-specific
     ///
     /// - **iOS:** Can only be called on the main thread.
     #[inline]
-    pub fn fullscreen(&self) -> Option<MonitorHandle> {
+    pub fn fullscreen(&self) -> Option<FullscreenType> {
         self.window.fullscreen()
     }
 
     /// Turn window decorations on or off.
     ///
+    /// This can be done at any time, and the inner size of the window is kept, so the outer size
+    /// grows or shrinks by the size of the decorations.
+    ///
     /// ## Platform-specific
     ///
     /// - **iOS:** Can only be called on the main thread. Controls whether the status bar is hidden
@@ -572,6 +975,18 @@ impl Window {
         self.window.set_decorations(decorations)
     }
 
+    /// Returns whether the window has decorations, as last set by `set_decorations` or
+    /// `WindowBuilder::with_decorations`.
+    ///
+    /// ## Platform-specific
+    ///
+    /// - **iOS:** Can only be called on the main thread. Returns whether the status bar is shown.
+    /// - **Android:** Always returns `true`.
+    #[inline]
+    pub fn get_decorations(&self) -> bool {
+        self.window.get_decorations()
+    }
+
     /// Change whether or not the window will always be on top of other windows.
     ///
     /// ## Platform-specific
@@ -582,6 +997,89 @@ impl Window {
         self.window.set_always_on_top(always_on_top)
     }
 
+    /// Change whether or not the window is left out of the taskbar.
+    ///
+    /// ## Platform-specific
+    ///
+    /// - **Windows:** The window becomes a tool window, which has a smaller title bar and isn't
+    ///   listed by Alt+Tab either.
+    /// - **macOS:** The Dock only lists applications, so this leaves the window out of the Window
+    ///   menu, Mission Control and window cycling instead. Hiding the application from the Dock is
+    ///   done with `ActivationPolicy::Accessory`.
+    /// - **X11:** The window is left out of the pager as well.
+    /// - **iOS / Android / Emscripten / Wayland:** Has no effect.
+    #[inline]
+    pub fn set_skip_taskbar(&self, skip: bool) {
+        self.window.set_skip_taskbar(skip)
+    }
+
+    /// Enables or disables the buttons in the title bar. Disabled buttons are greyed out or left
+    /// out.
+    ///
+    /// ## Platform-specific
+    ///
+    /// - **Windows:** The maximize button is always disabled on windows that aren't resizable.
+    /// - **macOS:** `Window::set_resizable` enables the maximize button again.
+    /// - **iOS / Android / Emscripten / X11 / Wayland:** Has no effect.
+    #[inline]
+    pub fn set_enabled_buttons(&self, buttons: WindowButtons) {
+        self.window.set_enabled_buttons(buttons)
+    }
+
+    /// Prevents the window's content from being captured by other applications, such as screen
+    /// recorders or remote desktop software.
+    ///
+    /// This is only a hint to the system: it can't prevent every capture method, so it shouldn't be
+    /// relied upon as a security measure on its own.
+    ///
+    /// ## Platform-specific
+    ///
+    /// - **Windows:** The window is excluded from captures on Windows 10 version 2004 and later.
+    ///   Earlier versions capture it as a black rectangle instead.
+    /// - **iOS / Android / X11 / Wayland:** Has no effect.
+    #[inline]
+    pub fn set_content_protected(&self, protected: bool) {
+        self.window.set_content_protected(protected)
+    }
+
+    /// Sets the opacity of the whole window, including its decorations, where `0.0` is fully
+    /// transparent and `1.0` is fully opaque.
+    ///
+    /// Values outside of that range are clamped, and `NaN` is ignored. This is independent of
+    /// `WindowBuilder::with_transparent`, which allows the window's content to be transparent on
+    /// a per-pixel basis: the two combine, so a transparent window can also be faded out.
+    ///
+    /// ## Platform-specific
+    ///
+    /// - **X11:** Requires a compositing window manager that honors `_NET_WM_WINDOW_OPACITY`.
+    /// - **iOS / Android / Wayland:** Has no effect.
+    #[inline]
+    pub fn set_opacity(&self, opacity: f32) {
+        let opacity = match opacity {
+            _ if opacity.is_nan() => return,
+            _ if opacity < 0.0 => 0.0,
+            _ if opacity > 1.0 => 1.0,
+            _ => opacity,
+        };
+        self.window.set_opacity(opacity)
+    }
+
+    /// Restricts the window to the union of the given rectangles, which are in client area
+    /// coordinates relative to the top left. `None` restores the full rectangular shape.
+    ///
+    /// Both drawing and input are clipped to the shape, so clicks outside of it go to whatever
+    /// is below the window. The shape isn't scaled along with the window: to adjust it to a new
+    /// size, set it again when handling `WindowEvent::Resized`.
+    ///
+    /// ## Platform-specific
+    ///
+    /// - **X11:** Requires the XFixes extension.
+    /// - **macOS / iOS / Android / Wayland:** Unsupported.
+    #[inline]
+    pub fn set_shape(&self, shape: Option<&[Rect]>) -> Result<(), ExternalError> {
+        self.window.set_shape(shape)
+    }
+
     /// Sets the window icon. On Windows and X11, this is typically the small icon in the top-left
     /// corner of the titlebar.
     ///
@@ -589,14 +1087,36 @@ impl Window {
     ///
     /// ## Platform-specific
     ///
-    /// This only has an effect on Windows and X11.
+    /// - **macOS:** Sets the application icon in the Dock. `None` restores the icon from the
+    ///   application bundle.
+    /// - **iOS / Android / Wayland:** Unsupported.
     #[inline]
     pub fn set_window_icon(&self, window_icon: Option<Icon>) {
         self.window.set_window_icon(window_icon)
     }
 
+    /// Requests user attention to the window, for example by flashing its taskbar button or
+    /// bouncing the application's dock icon. This has no effect if the application is already
+    /// focused.
+    ///
+    /// How the request is presented depends on the `UserAttentionType`. Passing `None` cancels
+    /// a previous request.
+    ///
+    /// ## Platform-specific
+    ///
+    /// - **macOS:** `None` only cancels the last request made through this window.
+    /// - **X11:** Requests are indicated with `_NET_WM_STATE_DEMANDS_ATTENTION`, and both types
+    ///   behave the same.
+    /// - **iOS / Android / Wayland:** Has no effect.
+    #[inline]
+    pub fn request_user_attention(&self, request_type: Option<UserAttentionType>) {
+        self.window.request_user_attention(request_type)
+    }
+
     /// Sets location of IME candidate box in client area coordinates relative to the top left.
     ///
+    /// The position is remembered, and applied again whenever a new composition starts.
+    ///
     /// ## Platform-specific
     ///
     /// **iOS:** Has no effect.
@@ -604,12 +1124,56 @@ impl Window {
     pub fn set_ime_position(&self, position: LogicalPosition) {
         self.window.set_ime_position(position)
     }
+
+    /// Throws away a dead key that has been pressed but not yet combined with a following key.
+    ///
+    /// This is useful when the application moves focus between its own widgets, so that an accent
+    /// typed in one text field doesn't end up on the first character typed into the next one.
+    ///
+    /// ## Platform-specific
+    ///
+    /// - **iOS / Android / Wayland:** Has no effect.
+    #[inline]
+    pub fn reset_dead_keys(&self) {
+        self.window.reset_dead_keys()
+    }
+
+    /// Sets whether the window accepts input through the input method (IME).
+    ///
+    /// When IME is disallowed, keystrokes are no longer intercepted for composition, and
+    /// `ReceivedCharacter` events are emitted directly for the characters produced by the
+    /// keyboard layout. This is useful for applications such as games that handle keys on their
+    /// own. IME is allowed by default.
+    ///
+    /// ## Platform-specific
+    ///
+    /// - **iOS / Android / Wayland:** Has no effect.
+    #[inline]
+    pub fn set_ime_allowed(&self, allowed: bool) {
+        self.window.set_ime_allowed(allowed)
+    }
 }
 
 /// Cursor functions.
 impl Window {
+    /// Sets the cursor of the window.
+    ///
+    /// ## Platform-specific
+    ///
+    /// - **iOS:** Has no effect.
+    /// - **Android:** Has no effect.
+    #[inline]
+    pub fn set_cursor(&self, cursor: &Cursor) {
+        match cursor.inner {
+            CursorInner::System(icon) => self.window.set_cursor_icon(icon),
+            CursorInner::Custom(ref cursor) => self.window.set_custom_cursor(&cursor.inner),
+        }
+    }
+
     /// Modifies the cursor icon of the window.
     ///
+    /// This is the same as calling `set_cursor` with `Cursor::from_system(cursor)`.
+    ///
     /// ## Platform-specific
     ///
     /// - **iOS:** Has no effect.
@@ -619,11 +1183,54 @@ impl Window {
         self.window.set_cursor_icon(cursor);
     }
 
+    /// Creates a cursor from 32bpp RGBA data, with its hotspot at (`hotspot_x`, `hotspot_y`)
+    /// measured from the top-left corner of the image.
+    ///
+    /// The length of `rgba` must be divisible by 4, `width * height` must equal `rgba.len() / 4`
+    /// and the hotspot must lie within the image. Otherwise, this will return a `BadCursor` error.
+    ///
+    /// ## Platform-specific
+    ///
+    /// - **iOS:** Always returns `Err(BadCursor::NotSupported)`.
+    /// - **Android:** Always returns `Err(BadCursor::NotSupported)`.
+    #[inline]
+    pub fn create_custom_cursor(
+        &self,
+        rgba: &[u8],
+        width: u32,
+        height: u32,
+        hotspot_x: u32,
+        hotspot_y: u32,
+    ) -> Result<CustomCursor, BadCursor> {
+        crate::cursor::validate_cursor(rgba, width, height, hotspot_x, hotspot_y)?;
+        self.window
+            .create_custom_cursor(rgba, width, height, hotspot_x, hotspot_y)
+            .map(|inner| CustomCursor { inner })
+    }
+
+    /// Sets the cursor of the window to a cursor created with `create_custom_cursor`.
+    ///
+    /// The custom cursor stays in place until `set_cursor_icon` is called.
+    ///
+    /// ## Platform-specific
+    ///
+    /// - **iOS:** Has no effect.
+    /// - **Android:** Has no effect.
+    #[inline]
+    pub fn set_custom_cursor(&self, cursor: &CustomCursor) {
+        self.window.set_custom_cursor(&cursor.inner);
+    }
+
     /// Changes the position of the cursor in window coordinates.
     ///
+    /// Returns `Err(ExternalError::NotSupported(_))` if the platform doesn't let applications
+    /// move the cursor, so the request is never silently dropped.
+    ///
     /// ## Platform-specific
     ///
-    /// - **iOS:** Always returns an `Err`.
+    /// - **Wayland:** Always returns `Err(ExternalError::NotSupported(_))`, since the protocol
+    ///   doesn't allow clients to warp the cursor.
+    /// - **iOS / Android / Emscripten:** Always returns `Err(ExternalError::NotSupported(_))`.
     #[inline]
     pub fn set_cursor_position(&self, position: LogicalPosition) -> Result<(), ExternalError> {
         self.window.set_cursor_position(position)
@@ -631,10 +1238,17 @@ impl Window {
 
     /// Grabs the cursor, preventing it from leaving the window.
     ///
+    /// Returns `Err(ExternalError::NotSupported(_))` if cursor grabbing isn't available on the
+    /// platform, and `Err(ExternalError::NotFocused)` if the cursor can't be grabbed right now
+    /// (typically because the window isn't focused or mapped yet), in which case it's worth
+    /// retrying upon receiving `WindowEvent::Focused(true)`.
+    ///
     /// ## Platform-specific
     ///
     /// - **macOS:** This presently merely locks the cursor in a fixed location, which looks visually
     ///   awkward.
+    /// - **Windows:** The grab is deferred until the window gains focus, so this never returns
+    ///   `NotFocused`.
     /// - **Android:** Has no effect.
     /// - **iOS:** Always returns an Err.
     #[inline]
@@ -642,16 +1256,104 @@ impl Window {
         self.window.set_cursor_grab(grab)
     }
 
+    /// Sets whether the window is hit by the cursor. When `false`, the window ignores the mouse
+    /// entirely: clicks and movement go to whatever is below it, and no `CursorMoved`,
+    /// `MouseInput` or `MouseWheel` events are delivered for it. Passing `true` restores the
+    /// default.
+    ///
+    /// ## Platform-specific
+    ///
+    /// - **X11:** Requires the XFixes extension.
+    /// - **Wayland:** Decorations drawn by winit still receive input.
+    /// - **iOS / Android:** Always returns `Err(ExternalError::NotSupported(_))`.
+    #[inline]
+    pub fn set_cursor_hittest(&self, hittest: bool) -> Result<(), ExternalError> {
+        self.window.set_cursor_hittest(hittest)
+    }
+
+    /// Moves the window with the left mouse button until the button is released.
+    ///
+    /// There's no guarantee that this will work unless the left mouse button was pressed
+    /// immediately before this function is called, so it's meant to be called from a
+    /// `WindowEvent::MouseInput` handler. This is mostly useful for borderless windows, which
+    /// have no title bar to drag. The window manager takes over the move, and the usual
+    /// `WindowEvent::Moved` events are delivered while it's in progress.
+    ///
+    /// Returns an `Err` if the left mouse button isn't currently pressed.
+    ///
+    /// ## Platform-specific
+    ///
+    /// - **Wayland:** Always returns `Err(ExternalError::NotSupported(_))`.
+    /// - **iOS / Android:** Always returns `Err(ExternalError::NotSupported(_))`.
+    #[inline]
+    pub fn drag_window(&self) -> Result<(), ExternalError> {
+        self.window.drag_window()
+    }
+
+    /// Resizes the window with the left mouse button until the button is released, moving the
+    /// edge or corner given by `direction`.
+    ///
+    /// Like `drag_window`, this is meant to be called from a `WindowEvent::MouseInput` handler,
+    /// typically when the user presses the button on a custom resize border. The window manager
+    /// takes over the resize, so the minimum and maximum inner sizes are respected, and the usual
+    /// `WindowEvent::Resized` events are delivered while it's in progress.
+    ///
+    /// Returns an `Err` if the left mouse button isn't currently pressed.
+    ///
+    /// ## Platform-specific
+    ///
+    /// - **macOS / Wayland:** Always returns `Err(ExternalError::NotSupported(_))`.
+    /// - **iOS / Android:** Always returns `Err(ExternalError::NotSupported(_))`.
+    #[inline]
+    pub fn drag_resize_window(&self, direction: ResizeDirection) -> Result<(), ExternalError> {
+        self.window.drag_resize_window(direction)
+    }
+
+    /// Sets whether the window accepts mouse, keyboard and touch input.
+    ///
+    /// This is useful for keeping the user out of a window while a modal dialog is up. Input that
+    /// arrives while the window is disabled is dropped, rather than delivered once it's enabled
+    /// again. If the window has the keyboard focus when it's disabled, the focus is handed over to
+    /// its parent or owner window, if there's one.
+    ///
+    /// ## Platform-specific
+    ///
+    /// - **Windows:** Uses `EnableWindow`, so the window can't be activated while it's disabled.
+    /// - **Wayland / iOS / Android:** Has no effect.
+    #[inline]
+    pub fn set_enabled(&self, enabled: bool) {
+        self.window.set_enabled(enabled)
+    }
+
+    /// Shows the system's window menu, with entries such as Restore, Move, Minimize and Close, at
+    /// `position` relative to the top-left hand corner of the window's client area.
+    ///
+    /// This is meant for custom decorations, typically when the user right-clicks the title bar.
+    /// Picking an entry has the same effect as doing it through native decorations, so the usual
+    /// events are delivered afterwards.
+    ///
+    /// ## Platform-specific
+    ///
+    /// - **X11:** Only has an effect on window managers supporting `_GTK_SHOW_WINDOW_MENU`.
+    /// - **macOS / Wayland / iOS / Android:** Has no effect.
+    #[inline]
+    pub fn show_window_menu(&self, position: LogicalPosition) {
+        self.window.show_window_menu(position)
+    }
+
     /// Modifies the cursor's visibility.
     ///
     /// If `false`, this will hide the cursor. If `true`, this will show the cursor.
     ///
+    /// This is independent of `set_cursor_grab`, so a cursor can be hidden without being grabbed,
+    /// and vice versa.
+    ///
     /// ## Platform-specific
     ///
     /// - **Windows:** The cursor is only hidden within the confines of the window.
     /// - **X11:** The cursor is only hidden within the confines of the window.
-    /// - **macOS:** The cursor is hidden as long as the window has input focus, even if the cursor is
-    ///   outside of the window.
+    /// - **Wayland:** The cursor is only hidden within the confines of the window.
+    /// - **macOS:** The cursor is only hidden within the confines of the window.
     /// - **iOS:** Has no effect.
     /// - **Android:** Has no effect.
     #[inline]
@@ -662,13 +1364,20 @@ impl Window {
 
 /// Monitor info functions.
 impl Window {
-    /// Returns the monitor on which the window currently resides
+    /// Returns the monitor on which the window currently resides, or `None` if the window isn't
+    /// on any monitor, for instance because it has been moved offscreen.
+    ///
+    /// If the window spans several monitors, this is the one containing the largest part of it.
+    /// The returned handle compares equal to the matching one from `available_monitors`.
     ///
     /// ## Platform-specific
     ///
-    /// **iOS:** Can only be called on the main thread.
+    /// - **iOS:** Can only be called on the main thread.
+    /// - **Wayland:** Returns the monitor the window most recently entered, since the compositor
+    ///   doesn't say how much of the window is on each one. Returns `None` before the window is
+    ///   first shown.
     #[inline]
-    pub fn current_monitor(&self) -> MonitorHandle {
+    pub fn current_monitor(&self) -> Option<MonitorHandle> {
         self.window.current_monitor()
     }
 
@@ -702,6 +1411,28 @@ impl Window {
     }
 }
 
+/// Describes how a window is made fullscreen.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum FullscreenType {
+    /// Takes exclusive control of the given monitor, which may let the window be presented
+    /// without going through the compositor. The monitor keeps its current video mode, unless
+    /// another one was requested with `WindowBuilder::with_video_mode`.
+    Exclusive(MonitorHandle),
+    /// Covers the given monitor with a borderless window. `None` means the monitor the window is
+    /// currently on.
+    Borderless(Option<MonitorHandle>),
+}
+
+impl FullscreenType {
+    /// The monitor explicitly requested for fullscreen, if any.
+    pub(crate) fn monitor(&self) -> Option<&MonitorHandle> {
+        match self {
+            FullscreenType::Exclusive(monitor) => Some(monitor),
+            FullscreenType::Borderless(monitor) => monitor.as_ref(),
+        }
+    }
+}
+
 /// Describes the appearance of the mouse cursor.
 #[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
 #[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
@@ -764,3 +1495,138 @@ impl Default for CursorIcon {
         CursorIcon::Default
     }
 }
+
+/// A rectangle in client area coordinates, as used by `Window::set_shape`.
+#[derive(Debug, Copy, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct Rect {
+    pub position: LogicalPosition,
+    pub size: LogicalSize,
+}
+
+impl Rect {
+    #[inline]
+    pub fn new(position: LogicalPosition, size: LogicalSize) -> Self {
+        Rect { position, size }
+    }
+}
+
+/// Where a window is and how big it is, as returned by `Window::get_placement`.
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct WindowPlacement {
+    /// The position of the top-left corner of the window, decorations included.
+    pub position: LogicalPosition,
+    /// The size of the window's client area.
+    pub inner_size: LogicalSize,
+    /// Whether the window is maximized.
+    pub maximized: bool,
+    /// The native identifier of the monitor the window is on, as returned by `native_id` from the
+    /// platform's `MonitorHandleExt`. The identifiers are only meaningful on the same machine.
+    pub monitor_native_id: Option<String>,
+}
+
+/// The kind of attention requested with `Window::request_user_attention`.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub enum UserAttentionType {
+    /// Keeps drawing the user's attention until the application is focused.
+    ///
+    /// ## Platform-specific
+    ///
+    /// - **macOS:** Bounces the dock icon until the application is focused.
+    /// - **Windows:** Flashes both the window and the taskbar button until the application is
+    ///   focused.
+    Critical,
+    /// Draws the user's attention once, without insisting.
+    ///
+    /// ## Platform-specific
+    ///
+    /// - **macOS:** Bounces the dock icon once.
+    /// - **Windows:** Flashes the taskbar button until the application is focused.
+    Informational,
+}
+
+/// The light or dark appearance of the system, as reported by `WindowEvent::ThemeChanged`.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub enum Theme {
+    Light,
+    Dark,
+}
+
+bitflags! {
+    /// The buttons in the title bar, as passed to `Window::set_enabled_buttons`.
+    pub struct WindowButtons: u32 {
+        const CLOSE    = 1 << 0;
+        const MINIMIZE = 1 << 1;
+        const MAXIMIZE = 1 << 2;
+    }
+}
+
+/// The edge or corner of the window moved by `Window::drag_resize_window`.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub enum ResizeDirection {
+    East,
+    North,
+    NorthEast,
+    NorthWest,
+    South,
+    SouthEast,
+    SouthWest,
+    West,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn area(native_id: &str, position: (f64, f64), size: (f64, f64)) -> PlacementArea {
+        PlacementArea {
+            native_id: native_id.to_owned(),
+            position: position.into(),
+            size: size.into(),
+            hidpi_factor: 1.0,
+        }
+    }
+
+    fn placement(
+        monitor_native_id: &str,
+        position: (f64, f64),
+        size: (f64, f64),
+    ) -> WindowPlacement {
+        WindowPlacement {
+            position: position.into(),
+            inner_size: size.into(),
+            maximized: false,
+            monitor_native_id: Some(monitor_native_id.to_owned()),
+        }
+    }
+
+    #[test]
+    fn fit_placement_moves_offscreen_window_back() {
+        let areas = [area("a", (0.0, 0.0), (1920.0, 1080.0))];
+        let (position, size, _) =
+            fit_placement(&placement("a", (5000.0, -300.0), (800.0, 600.0)), &areas).unwrap();
+        assert_eq!(position, PhysicalPosition::new(1120.0, 0.0));
+        assert_eq!(size, PhysicalSize::new(800.0, 600.0));
+    }
+
+    #[test]
+    fn fit_placement_uses_closest_area_when_monitor_is_gone() {
+        let areas = [
+            area("a", (0.0, 0.0), (1920.0, 1080.0)),
+            area("b", (1920.0, 0.0), (1280.0, 1024.0)),
+        ];
+        let (position, size, _) =
+            fit_placement(&placement("gone", (3000.0, 100.0), (1600.0, 900.0)), &areas).unwrap();
+        assert_eq!(position, PhysicalPosition::new(1920.0, 100.0));
+        assert_eq!(size, PhysicalSize::new(1280.0, 900.0));
+    }
+
+    #[test]
+    fn fit_placement_without_areas() {
+        assert!(fit_placement(&placement("a", (0.0, 0.0), (800.0, 600.0)), &[]).is_none());
+    }
+}
@@ -0,0 +1,9 @@
+#[allow(dead_code)]
+fn needs_clone<T: Clone>() {}
+
+#[test]
+fn monitors_clone() {
+    // ensures that the monitor list can be iterated several times
+    needs_clone::<winit::monitor::MonitorHandle>();
+    needs_clone::<winit::monitor::AvailableMonitorsIter>();
+}
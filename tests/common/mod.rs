@@ -0,0 +1,28 @@
+use winit::event_loop::EventLoop;
+
+// Uses X11 explicitly, so that the tests are skipped rather than run against Wayland when there's
+// no X server to connect to
+#[cfg(any(
+    target_os = "linux",
+    target_os = "dragonfly",
+    target_os = "freebsd",
+    target_os = "netbsd",
+    target_os = "openbsd"
+))]
+#[allow(dead_code)]
+pub fn new_event_loop() -> Option<EventLoop<()>> {
+    use winit::platform::unix::EventLoopExtUnix;
+    EventLoop::new_x11().ok()
+}
+
+#[cfg(not(any(
+    target_os = "linux",
+    target_os = "dragonfly",
+    target_os = "freebsd",
+    target_os = "netbsd",
+    target_os = "openbsd"
+)))]
+#[allow(dead_code)]
+pub fn new_event_loop() -> Option<EventLoop<()>> {
+    Some(EventLoop::new())
+}
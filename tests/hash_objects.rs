@@ -0,0 +1,30 @@
+mod common;
+
+use std::hash::Hash;
+
+#[allow(dead_code)]
+fn needs_hash<T: Eq + Hash>() {}
+
+#[test]
+fn ids_hash() {
+    // ensures that the various `..Id` types can be used as `HashSet`/`HashMap` keys
+    needs_hash::<winit::window::WindowId>();
+    needs_hash::<winit::event::DeviceId>();
+    needs_hash::<winit::monitor::MonitorHandle>();
+}
+
+// macOS and iOS only create the event loop on the main thread, which tests don't run on
+#[cfg(not(any(target_os = "macos", target_os = "ios")))]
+#[test]
+fn monitor_handles_hash() {
+    use std::collections::HashSet;
+
+    // ensures that distinct monitors don't end up as the same `HashSet` key
+    let event_loop = match common::new_event_loop() {
+        Some(event_loop) => event_loop,
+        None => return,
+    };
+    let count = event_loop.available_monitors().count();
+    let monitors: HashSet<_> = event_loop.available_monitors().collect();
+    assert_eq!(monitors.len(), count);
+}
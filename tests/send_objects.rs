@@ -23,3 +23,15 @@ fn ids_send() {
     needs_send::<winit::event::DeviceId>();
     needs_send::<winit::monitor::MonitorHandle>();
 }
+
+#[test]
+fn custom_cursor_send() {
+    // ensures that `winit::window::CustomCursor` implements `Send`
+    needs_send::<winit::window::CustomCursor>();
+}
+
+#[test]
+fn cursor_send() {
+    // ensures that `winit::window::Cursor` implements `Send`
+    needs_send::<winit::window::Cursor>();
+}
@@ -7,7 +7,7 @@ use winit::{
         ElementState, KeyboardInput, ModifiersState, MouseButton, MouseScrollDelta, TouchPhase,
         VirtualKeyCode,
     },
-    window::CursorIcon,
+    window::{CursorIcon, Rect, ResizeDirection, UserAttentionType, WindowPlacement},
 };
 
 #[allow(dead_code)]
@@ -16,6 +16,10 @@ fn needs_serde<S: Serialize + Deserialize<'static>>() {}
 #[test]
 fn window_serde() {
     needs_serde::<CursorIcon>();
+    needs_serde::<UserAttentionType>();
+    needs_serde::<ResizeDirection>();
+    needs_serde::<Rect>();
+    needs_serde::<WindowPlacement>();
 }
 
 #[test]
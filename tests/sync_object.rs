@@ -1,8 +1,37 @@
 #[allow(dead_code)]
 fn needs_sync<T: Sync>() {}
 
+#[test]
+fn event_loop_proxy_sync() {
+    #[allow(dead_code)]
+    fn is_sync<T: 'static + Send>() {
+        // ensures that `winit::EventLoopProxy` implements `Sync`
+        needs_sync::<winit::event_loop::EventLoopProxy<T>>();
+    }
+}
+
 #[test]
 fn window_sync() {
     // ensures that `winit::Window` implements `Sync`
     needs_sync::<winit::window::Window>();
 }
+
+#[test]
+fn ids_sync() {
+    // ensures that the various `..Id` types implement `Sync`
+    needs_sync::<winit::window::WindowId>();
+    needs_sync::<winit::event::DeviceId>();
+    needs_sync::<winit::monitor::MonitorHandle>();
+}
+
+#[test]
+fn custom_cursor_sync() {
+    // ensures that `winit::window::CustomCursor` implements `Sync`
+    needs_sync::<winit::window::CustomCursor>();
+}
+
+#[test]
+fn cursor_sync() {
+    // ensures that `winit::window::Cursor` implements `Sync`
+    needs_sync::<winit::window::Cursor>();
+}
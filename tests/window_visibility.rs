@@ -0,0 +1,59 @@
+// The other platforms only create the event loop on the main thread, which tests don't run on
+#![cfg(any(
+    target_os = "windows",
+    target_os = "linux",
+    target_os = "dragonfly",
+    target_os = "freebsd",
+    target_os = "netbsd",
+    target_os = "openbsd"
+))]
+
+mod common;
+
+use std::{thread, time::Duration};
+
+use winit::{
+    event::Event,
+    event_loop::{ControlFlow, EventLoop},
+    platform::desktop::EventLoopExtDesktop,
+    window::{Window, WindowBuilder},
+};
+
+#[test]
+fn show_hide_show() {
+    // ensures that `Window::is_visible` follows `set_visible` once the events are in
+    let mut event_loop = match common::new_event_loop() {
+        Some(event_loop) => event_loop,
+        None => return,
+    };
+    let window = WindowBuilder::new()
+        .with_visible(false)
+        .build(&event_loop)
+        .unwrap();
+    assert_eq!(window.is_visible(), Some(false));
+
+    for &visible in &[true, false, true] {
+        window.set_visible(visible);
+        assert!(
+            wait_for_visibility(&mut event_loop, &window, visible),
+            "`is_visible` didn't become {}",
+            visible
+        );
+    }
+}
+
+// The window manager may take a moment to map the window, so the events are drained a few times.
+fn wait_for_visibility(event_loop: &mut EventLoop<()>, window: &Window, visible: bool) -> bool {
+    for _ in 0..100 {
+        event_loop.run_return(|event, _, control_flow| {
+            if let Event::EventsCleared = event {
+                *control_flow = ControlFlow::Exit;
+            }
+        });
+        if window.is_visible() == Some(visible) {
+            return true;
+        }
+        thread::sleep(Duration::from_millis(10));
+    }
+    false
+}